@@ -1,16 +1,31 @@
 use crate::config::ForgepointConfig;
 use crate::document::ForgepointDocument;
+use crate::due::{find_due_items, DueItem};
+use crate::flags::{exported_flags, flagd_config};
 use crate::formatter::ResultFormatter;
+use crate::gherkin::exported_features;
+use crate::history::GitRevision;
+use crate::ical::{document_events, render_calendar, CalendarEvent};
+use crate::junit::{match_test_case, JunitReport, ResultsSummary, TestCaseDocument, TestResultsContent};
+use crate::migration::{migrate_document, MigrationSet};
+use crate::okr::{build_report, parent_cycles, Objective};
 use crate::parser::DocumentParser;
-use crate::schema::SchemaLoader;
-use crate::validator::DocumentValidator;
-use crate::{Cli, OutputFormat};
+use crate::progress::{document_progress, roll_up, DocumentProgress};
+use crate::embedded::EMBEDDED_SCHEMAS;
+use crate::schema::{SchemaLoader, SchemaSource};
+use crate::schema_check::SchemaChecker;
+use crate::schema_docs::{extension, render_index, render_type_page};
+use crate::sprint::{SprintItem, SprintPlan, SprintReport};
+use crate::timeline::Timeline;
+use crate::validator::{DocumentValidator, Severity};
+use crate::wsjf::{is_rankable, rank_by_wsjf, RankedItem};
+use crate::{Cli, DocsFormat, FlagFormat, OkrFormat, OutputFormat, RankBy, ReportFormat, TimelineFormat};
 use anyhow::{Context, Result};
 use glob::glob;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 pub struct LintArgs {
@@ -22,6 +37,8 @@ pub struct LintArgs {
     pub no_check_ids: bool,
     pub no_check_refs: bool,
     pub fail_on_warnings: bool,
+    pub due: bool,
+    pub against: Option<String>,
 }
 
 pub struct CreateArgs {
@@ -33,13 +50,49 @@ pub struct CreateArgs {
     pub output: Option<PathBuf>,
 }
 
+pub struct MigrateArgs {
+    pub cli: Cli,
+    pub patterns: Vec<String>,
+    pub to: String,
+    pub document_type: Option<String>,
+    pub dry_run: bool,
+}
+
+pub struct ImportJunitArgs {
+    pub cli: Cli,
+    pub report: PathBuf,
+    pub test_plan: String,
+    pub id: String,
+    pub title: Option<String>,
+    pub version: Option<String>,
+    pub environment: Option<String>,
+    pub executor: Option<String>,
+    pub output: Option<PathBuf>,
+}
+
+pub struct TimelineArgs {
+    pub cli: Cli,
+    pub patterns: Vec<String>,
+    pub format: TimelineFormat,
+    pub product: Option<String>,
+    pub team: Option<String>,
+    pub status: Option<String>,
+    pub output: Option<PathBuf>,
+}
+
+pub struct DueArgs {
+    pub cli: Cli,
+    pub patterns: Vec<String>,
+    pub format: ReportFormat,
+    pub owner: Option<String>,
+    pub as_of: Option<chrono::NaiveDate>,
+}
+
 pub async fn lint_command(args: LintArgs) -> Result<()> {
     let config = load_config(&args.cli)?;
     
     println!("{}", "Loading schemas...".dimmed());
-    let mut schema_loader = SchemaLoader::new(&config.schema_path);
-    schema_loader.load_schemas()
-        .context("Failed to load schemas")?;
+    let schema_loader = load_schemas(&config)?;
 
     println!("{}", "Finding documents...".dimmed());
     let files = find_files(&args.patterns, &get_exclude_patterns(&args, &config))?;
@@ -49,7 +102,19 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
         return Ok(());
     }
 
-    println!("Found {} documents to validate", files.len());
+    let revision = match &args.against {
+        Some(revision) => Some(GitRevision::resolve(revision, Path::new("."))?),
+        None => None,
+    };
+
+    match &revision {
+        Some(revision) => println!(
+            "Found {} documents to validate, checking status changes since {}",
+            files.len(),
+            revision.name()
+        ),
+        None => println!("Found {} documents to validate", files.len()),
+    }
     
     let progress = ProgressBar::new(files.len() as u64);
     progress.set_style(
@@ -59,23 +124,44 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
     );
 
     let parser = DocumentParser::new();
-    let validator = Mutex::new(DocumentValidator::new(schema_loader));
+    let mut validator = DocumentValidator::new(schema_loader);
+    if args.due || config.due.lint {
+        validator = validator.with_due_checks(config.due.clone(), chrono::Utc::now().date_naive());
+    }
+    let validator = Mutex::new(validator);
     
-    // First pass: Parse and validate documents
+    // First pass: Parse and validate documents, splitting bundles into their children
     let results: Vec<_> = files
         .par_iter()
-        .map(|file_path| {
+        .flat_map(|file_path| {
             progress.set_message(format!("Processing {}", file_path.file_name().unwrap_or_default().to_string_lossy()));
             progress.inc(1);
 
-            let result = match parser.parse_file(file_path) {
-                Ok(doc) => {
+            let results = match parser.parse_file_documents(file_path) {
+                Ok(docs) => {
+                    let previous_docs = revision
+                        .as_ref()
+                        .map(|revision| previous_documents(revision, &parser, file_path))
+                        .unwrap_or_default();
+
                     let mut validator = validator.lock().unwrap();
-                    validator.validate_document(&doc)
+                    docs.iter()
+                        .map(|doc| {
+                            let mut result = validator.validate_document(doc);
+                            let previous = previous_docs
+                                .iter()
+                                .find(|previous| previous.document_id().is_some() && previous.document_id() == doc.document_id());
+                            if let Some(error) = previous.and_then(|previous| validator.validate_transition(doc, previous)) {
+                                result.errors.push(error);
+                                result.valid = false;
+                            }
+                            result
+                        })
+                        .collect()
                 }
                 Err(e) => {
                     use crate::validator::{ValidationResult, ValidationError, ErrorType, Severity};
-                    ValidationResult {
+                    vec![ValidationResult {
                         file_path: file_path.to_string_lossy().to_string(),
                         document_type: None,
                         document_id: None,
@@ -89,11 +175,11 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
                             suggestion: None,
                         }],
                         warnings: Vec::new(),
-                    }
+                    }]
                 }
             };
 
-            result
+            results
         })
         .collect();
 
@@ -101,8 +187,8 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
 
     // Second pass: Check ID uniqueness if enabled
     let mut final_results = results;
+    let validator = validator.into_inner().unwrap();
     if !args.no_check_ids && config.rules.check_id_uniqueness {
-        let validator = validator.into_inner().unwrap();
         let duplicate_errors = validator.check_id_uniqueness();
         
         // Add duplicate errors to affected results
@@ -118,6 +204,13 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
         }
     }
 
+    // Sprint commitments depend on every story and task, so they're checked last too
+    for (plan_id, warning) in validator.check_sprint_commitments() {
+        if let Some(result) = final_results.iter_mut().find(|result| result.document_id.as_ref() == Some(&plan_id)) {
+            result.warnings.push(warning);
+        }
+    }
+
     // Format and output results
     let output_text = match args.format {
         OutputFormat::Text => {
@@ -150,12 +243,253 @@ pub async fn lint_command(args: LintArgs) -> Result<()> {
     Ok(())
 }
 
+/// The documents a file contained at an earlier revision, if it existed then
+fn previous_documents(revision: &GitRevision, parser: &DocumentParser, file_path: &Path) -> Vec<ForgepointDocument> {
+    let content = match revision.read_file(file_path) {
+        Ok(Some(content)) => content,
+        Ok(None) => return Vec::new(),
+        Err(e) => {
+            eprintln!("{} Not checking status changes in {}: {}", "⚠".yellow(), file_path.display(), e);
+            return Vec::new();
+        }
+    };
+    parser.parse_content_documents(&content, file_path.to_path_buf()).unwrap_or_else(|e| {
+        eprintln!(
+            "{} Not checking status changes in {}: it didn't parse at {}: {}",
+            "⚠".yellow(),
+            file_path.display(),
+            revision.name(),
+            e
+        );
+        Vec::new()
+    })
+}
+
+pub async fn due_command(args: DueArgs) -> Result<()> {
+    let config = load_config(&args.cli)?;
+    let schema_loader = load_schemas(&config)?;
+    let files = find_files(&args.patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+    let today = args.as_of.unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+    let mut items: Vec<DueItem> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+
+        for doc in &docs {
+            let Some(doc_type) = doc.document_type() else {
+                continue;
+            };
+            let Some(schema) = schema_loader
+                .get_schema_for_version(doc_type, doc.schema_version().map(String::as_str))
+                .or_else(|| schema_loader.get_schema(doc_type))
+            else {
+                continue;
+            };
+            items.extend(find_due_items(doc, schema, &config.due, today));
+        }
+    }
+
+    if let Some(owner) = &args.owner {
+        items.retain(|item| item.owner.as_ref().is_some_and(|o| o.eq_ignore_ascii_case(owner)));
+    }
+
+    match args.format {
+        ReportFormat::Text => print!("{}", ResultFormatter::format_due_items(&items, today)),
+        ReportFormat::Json => println!("{}", ResultFormatter::format_due_items_json(&items, today)?),
+    }
+
+    Ok(())
+}
+
+pub async fn progress_command(
+    cli: Cli,
+    patterns: Vec<String>,
+    format: ReportFormat,
+    document_type: Option<String>,
+) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut documents: Vec<DocumentProgress> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        documents.extend(docs.iter().filter(|doc| doc.has_forgepoint_structure()).map(document_progress));
+    }
+
+    // Roll up across every document before filtering, so an epic still
+    // counts its stories when only epics are reported
+    roll_up(&mut documents);
+    documents.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    documents.retain(|doc| doc.completion.total > 0 || doc.rollup.is_some());
+    if let Some(document_type) = &document_type {
+        documents.retain(|doc| doc.document_type.as_ref() == Some(document_type));
+    }
+
+    match format {
+        ReportFormat::Text => print!("{}", ResultFormatter::format_progress(&documents)),
+        ReportFormat::Json => println!("{}", ResultFormatter::format_progress_json(&documents)?),
+    }
+
+    Ok(())
+}
+
+pub async fn okr_command(cli: Cli, patterns: Vec<String>, format: OkrFormat, period: Option<String>) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut objectives: Vec<Objective> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        objectives.extend(
+            docs.iter()
+                .filter(|doc| doc.document_type().map(String::as_str) == Some("okr"))
+                .map(Objective::from_document),
+        );
+    }
+
+    if let Some(period) = &period {
+        objectives.retain(|objective| objective.period.as_ref() == Some(period));
+    }
+    let cycles = parent_cycles(&objectives);
+    if !cycles.is_empty() {
+        eprintln!(
+            "{} The parents of these OKRs form a cycle, so they are listed at the top level: {}",
+            "⚠".yellow(),
+            cycles.join(", ")
+        );
+    }
+    let report = build_report(objectives);
+
+    match format {
+        OkrFormat::Text => print!("{}", ResultFormatter::format_okr_report(&report)),
+        OkrFormat::Json => println!("{}", ResultFormatter::format_okr_report_json(&report)?),
+        OkrFormat::Html => print!("{}", ResultFormatter::format_okr_report_html(&report)),
+    }
+
+    Ok(())
+}
+
+pub async fn sprint_command(cli: Cli, patterns: Vec<String>, format: ReportFormat, sprint: Option<String>) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut plans: Vec<SprintPlan> = Vec::new();
+    let mut items: Vec<SprintItem> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        plans.extend(docs.iter().filter_map(SprintPlan::from_document));
+        items.extend(docs.iter().filter_map(SprintItem::from_document));
+    }
+
+    if let Some(sprint) = &sprint {
+        plans.retain(|plan| &plan.document_id == sprint);
+        if plans.is_empty() {
+            anyhow::bail!("No sprint plan found with ID '{}'", sprint);
+        }
+    }
+    plans.sort_by(|a, b| a.document_id.cmp(&b.document_id));
+    let reports: Vec<SprintReport> = plans.into_iter().map(|plan| SprintReport::build(plan, &items)).collect();
+
+    match format {
+        ReportFormat::Text => print!("{}", ResultFormatter::format_sprint_reports(&reports)),
+        ReportFormat::Json => println!("{}", ResultFormatter::format_sprint_reports_json(&reports)?),
+    }
+
+    Ok(())
+}
+
+pub async fn rank_command(
+    cli: Cli,
+    document_type: String,
+    patterns: Vec<String>,
+    by: RankBy,
+    pi: Option<String>,
+    format: ReportFormat,
+) -> Result<()> {
+    let config = load_config(&cli)?;
+    let schema_loader = load_schemas(&config)?;
+    let schema = schema_loader
+        .get_schema(&document_type)
+        .ok_or_else(|| anyhow::anyhow!("Unknown document type: {}", document_type))?;
+    if !is_rankable(schema) {
+        anyhow::bail!("{} documents have no job-size, so they can't be ranked by WSJF", document_type);
+    }
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut items: Vec<RankedItem> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        items.extend(
+            docs.iter()
+                .filter(|doc| doc.document_type() == Some(&document_type))
+                .map(RankedItem::from_document),
+        );
+    }
+
+    if let Some(pi) = &pi {
+        items.retain(|item| item.pi.as_ref() == Some(pi));
+    }
+    let backlogs = match by {
+        RankBy::Wsjf => rank_by_wsjf(items),
+    };
+
+    match format {
+        ReportFormat::Text => print!("{}", ResultFormatter::format_ranked_backlogs(&backlogs)),
+        ReportFormat::Json => println!("{}", ResultFormatter::format_ranked_backlogs_json(&backlogs)?),
+    }
+
+    Ok(())
+}
+
+pub async fn timeline_command(args: TimelineArgs) -> Result<()> {
+    let config = load_config(&args.cli)?;
+    let files = find_files(&args.patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut docs: Vec<ForgepointDocument> = Vec::new();
+    for file in files {
+        docs.extend(
+            parser.parse_file_documents(&file)
+                .with_context(|| format!("Failed to parse file {}", file.display()))?,
+        );
+    }
+
+    let mut timeline = Timeline::build(&docs, &config.timeline);
+    timeline.retain(args.product.as_deref(), args.team.as_deref(), args.status.as_deref());
+    let title = match &args.product {
+        Some(product) => format!("{} Roadmap", product),
+        None => "Roadmap".to_string(),
+    };
+
+    let output_text = match args.format {
+        TimelineFormat::Mermaid => timeline.render_gantt(&title),
+        TimelineFormat::Html => ResultFormatter::format_timeline_html(&timeline, &title),
+        TimelineFormat::Json => format!("{}\n", ResultFormatter::format_timeline_json(&timeline)?),
+    };
+    if let Some(output_file) = args.output {
+        fs::write(&output_file, output_text)
+            .with_context(|| format!("Failed to write {}", output_file.display()))?;
+        println!("Timeline written to {}", output_file.display());
+    } else {
+        print!("{}", output_text);
+    }
+    Ok(())
+}
+
 pub async fn create_command(args: CreateArgs) -> Result<()> {
     let config = load_config(&args.cli)?;
     
-    let mut schema_loader = SchemaLoader::new(&config.schema_path);
-    schema_loader.load_schemas()
-        .context("Failed to load schemas")?;
+    let schema_loader = load_schemas(&config)?;
 
     let template = create_document_template(
         &schema_loader,
@@ -177,9 +511,7 @@ pub async fn create_command(args: CreateArgs) -> Result<()> {
 pub async fn list_types_command(cli: Cli) -> Result<()> {
     let config = load_config(&cli)?;
     
-    let mut schema_loader = SchemaLoader::new(&config.schema_path);
-    schema_loader.load_schemas()
-        .context("Failed to load schemas")?;
+    let schema_loader = load_schemas(&config)?;
 
     let document_types = schema_loader.get_document_types();
     println!("{}", ResultFormatter::format_document_types(&document_types));
@@ -190,28 +522,395 @@ pub async fn list_types_command(cli: Cli) -> Result<()> {
 pub async fn check_command(cli: Cli, file: PathBuf) -> Result<()> {
     let config = load_config(&cli)?;
     
-    let mut schema_loader = SchemaLoader::new(&config.schema_path);
-    schema_loader.load_schemas()
-        .context("Failed to load schemas")?;
+    let schema_loader = load_schemas(&config)?;
 
     let parser = DocumentParser::new();
     let mut validator = DocumentValidator::new(schema_loader);
 
-    let doc = parser.parse_file(&file)
+    let docs = parser.parse_file_documents(&file)
         .with_context(|| format!("Failed to parse file {}", file.display()))?;
 
-    let result = validator.validate_document(&doc);
-    let output = ResultFormatter::format_text(&[result.clone()], true);
+    let results: Vec<_> = docs.iter().map(|doc| validator.validate_document(doc)).collect();
+    let output = ResultFormatter::format_text(&results, true);
     
     print!("{}", output);
     
-    if !result.valid {
+    if results.iter().any(|result| !result.valid) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+pub async fn migrate_command(args: MigrateArgs) -> Result<()> {
+    let config = load_config(&args.cli)?;
+
+    let migrations = MigrationSet::load(&config.schema_source())
+        .context("Failed to load migrations")?;
+    let files = find_files(&args.patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut migrated_documents = 0;
+    let mut migrated_files = 0;
+    let mut failures = 0;
+
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let docs = parser.parse_content_documents(&content, file.clone())
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+
+        let mut lines: Vec<String> = content.split('\n').map(String::from).collect();
+        let mut changes = Vec::new();
+
+        for doc in &docs {
+            let (Some(doc_type), Some(version)) = (doc.document_type(), doc.schema_version()) else {
+                continue;
+            };
+            if args.document_type.as_ref().is_some_and(|t| t != doc_type) || *version == args.to {
+                continue;
+            }
+
+            match migrations.plan(doc_type, version, &args.to) {
+                Some(steps) => {
+                    changes.extend(migrate_document(doc, &mut lines, &steps));
+                    migrated_documents += 1;
+                }
+                None => {
+                    eprintln!(
+                        "{} {}: no migration for '{}' from {} to {}",
+                        "✗".red(),
+                        file.display(),
+                        doc_type,
+                        version,
+                        args.to
+                    );
+                    failures += 1;
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            continue;
+        }
+
+        changes.sort_by_key(|change| change.line);
+        changes.dedup_by_key(|change| change.line);
+        for change in &mut changes {
+            // Show each line as it ended up after every document's migration
+            change.new = lines[change.line - 1].clone();
+            println!("{}", format!("{}:{}", file.display(), change.line).bold());
+            println!("{}", format!("- {}", change.old).red());
+            // A declared attribute can add a line after the one changed
+            for new_line in change.new.split('\n') {
+                println!("{}", format!("+ {}", new_line).green());
+            }
+        }
+
+        if !args.dry_run {
+            fs::write(&file, lines.join("\n"))
+                .with_context(|| format!("Failed to write {}", file.display()))?;
+        }
+        migrated_files += 1;
+    }
+
+    println!(
+        "{} {} documents in {} files to schema version {}",
+        if args.dry_run { "Would migrate" } else { "Migrated" },
+        migrated_documents,
+        migrated_files,
+        args.to
+    );
+
+    if failures > 0 {
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+pub async fn schema_export_command(dir: PathBuf, force: bool) -> Result<()> {
+    let existing = EMBEDDED_SCHEMAS
+        .iter()
+        .filter(|(name, _)| dir.join(name).exists())
+        .count();
+    if !force && existing > 0 {
+        anyhow::bail!(
+            "{} already contains {} of the schema files (use --force to overwrite)",
+            dir.display(),
+            existing
+        );
+    }
+
+    for (name, content) in EMBEDDED_SCHEMAS {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    println!("Exported {} schemas to {}", EMBEDDED_SCHEMAS.len(), dir.display());
+    println!("Use them with --schema-path {} or schema_path in your configuration", dir.display());
+    Ok(())
+}
+
+pub async fn export_gherkin_command(cli: Cli, dir: PathBuf, patterns: Vec<String>, force: bool) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut docs = Vec::new();
+    for file in files {
+        docs.extend(parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?);
+    }
+    let features: Vec<(PathBuf, String)> = exported_features(&docs)?
+        .into_iter()
+        .map(|(id, feature)| (dir.join(format!("{}.feature", id)), feature))
+        .collect();
+
+    let existing = features.iter().filter(|(path, _)| path.exists()).count();
+    if !force && existing > 0 {
+        anyhow::bail!(
+            "{} already contains {} of the feature files (use --force to overwrite)",
+            dir.display(),
+            existing
+        );
+    }
+
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for (path, feature) in &features {
+        fs::write(path, feature).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    println!("Exported {} feature files to {}", features.len(), dir.display());
+    Ok(())
+}
+
+pub async fn export_flags_command(
+    cli: Cli,
+    patterns: Vec<String>,
+    format: FlagFormat,
+    environment: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut docs = Vec::new();
+    for file in files {
+        docs.extend(parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?);
+    }
+    let flags = exported_flags(&docs, environment.as_deref())?;
+
+    let output_text = match format {
+        FlagFormat::Flagd => serde_json::to_string_pretty(&flagd_config(&flags))?,
+        FlagFormat::Json => serde_json::to_string_pretty(&serde_json::json!({ "flags": flags }))?,
+    };
+    if let Some(output_file) = output {
+        fs::write(&output_file, format!("{}\n", output_text))
+            .with_context(|| format!("Failed to write {}", output_file.display()))?;
+        println!("Exported {} flags to {}", flags.len(), output_file.display());
+    } else {
+        println!("{}", output_text);
+    }
+    Ok(())
+}
+
+pub async fn export_ical_command(
+    cli: Cli,
+    patterns: Vec<String>,
+    base_url: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let config = load_config(&cli)?;
+    let files = find_files(&patterns, &config.exclude_patterns)?;
+    let parser = DocumentParser::new();
+
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    for file in files {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        events.extend(docs.iter().flat_map(document_events));
+    }
+
+    let calendar = render_calendar(&events, base_url.as_deref(), chrono::Utc::now().naive_utc());
+    if let Some(output_file) = output {
+        fs::write(&output_file, calendar)
+            .with_context(|| format!("Failed to write {}", output_file.display()))?;
+        println!("Exported {} events to {}", events.len(), output_file.display());
+    } else {
+        print!("{}", calendar);
+    }
+    Ok(())
+}
+
+pub async fn import_junit_command(args: ImportJunitArgs) -> Result<()> {
+    let config = load_config(&args.cli)?;
+    let xml = fs::read_to_string(&args.report)
+        .with_context(|| format!("Failed to read {}", args.report.display()))?;
+    let report = JunitReport::parse(&xml)?;
+
+    // Find the test cases to map to, the test plan and any earlier import
+    let parser = DocumentParser::new();
+    let mut test_cases: Vec<TestCaseDocument> = Vec::new();
+    let mut has_test_plan = false;
+    let mut existing: Option<(PathBuf, usize)> = None;
+    for file in find_files(&["**/*.adoc".to_string()], &config.exclude_patterns)? {
+        let docs = parser.parse_file_documents(&file)
+            .with_context(|| format!("Failed to parse file {}", file.display()))?;
+        test_cases.extend(docs.iter().filter_map(TestCaseDocument::from_document));
+        for doc in &docs {
+            let id = doc.document_id().map(String::as_str);
+            match doc.document_type().map(String::as_str) {
+                Some("test-plan") if id == Some(args.test_plan.as_str()) => has_test_plan = true,
+                Some("test-results") if id == Some(args.id.as_str()) => existing = Some((file.clone(), docs.len())),
+                _ => {}
+            }
+        }
+    }
+    if !has_test_plan {
+        eprintln!("{} No test-plan document with ID '{}' found", "⚠".yellow(), args.test_plan);
+    }
+
+    let matches: Vec<Option<&TestCaseDocument>> = report
+        .cases
+        .iter()
+        .map(|case| match_test_case(case, &test_cases))
+        .collect();
+    let attributes: Vec<(String, String)> = [
+        ("version", &args.version),
+        ("environment", &args.environment),
+        ("executor", &args.executor),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name.to_string(), value.clone()?)))
+    .collect();
+    let source = args.report.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let today = chrono::Utc::now().date_naive();
+    let content = TestResultsContent::from_report(&report, &source, &args.test_plan, &matches, attributes, today);
+
+    let path = match existing {
+        Some((path, documents)) => {
+            if documents > 1 {
+                anyhow::bail!("{} is a bundle; import into a document of its own", path.display());
+            }
+            let current = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            fs::write(&path, content.apply(&current))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Updated {}", path.display());
+            path
+        }
+        None => {
+            let path = args.output.unwrap_or_else(|| PathBuf::from(format!("{}.adoc", args.id)));
+            if path.exists() {
+                anyhow::bail!("{} already exists and isn't test-results document '{}'", path.display(), args.id);
+            }
+            let title = args.title.unwrap_or_else(|| format!("{} Test Results", args.test_plan));
+            fs::write(&path, content.render(&args.id, &title, today))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Created {}", path.display());
+            path
+        }
+    };
+
+    let summary = ResultsSummary::of(&report.cases);
+    println!(
+        "Imported {} tests: {} passed, {} failed, {} skipped",
+        summary.total, summary.passed, summary.failed, summary.skipped
+    );
+    let unmatched: Vec<String> = report
+        .cases
+        .iter()
+        .zip(&matches)
+        .filter(|(_, doc)| doc.is_none())
+        .map(|(case, _)| case.full_name())
+        .collect();
+    if !unmatched.is_empty() {
+        println!(
+            "{} test{} matched no test-case document:",
+            unmatched.len(),
+            if unmatched.len() == 1 { "" } else { "s" }
+        );
+        for name in unmatched {
+            println!("  {}", name.dimmed());
+        }
+    }
+
+    let written = parser.parse_file(&path)
+        .with_context(|| format!("Failed to parse file {}", path.display()))?;
+    let missing: Vec<&str> = ["version", "environment", "executor"]
+        .into_iter()
+        .filter(|name| !written.attributes.contains_key(*name))
+        .collect();
+    if !missing.is_empty() {
+        println!(
+            "Set {} in {} (or pass --tested-version, --environment and --executor)",
+            missing.iter().map(|name| format!(":{}:", name)).collect::<Vec<_>>().join(", "),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn schema_check_command(cli: Cli, dir: Option<PathBuf>) -> Result<()> {
+    let config = load_config(&cli)?;
+
+    let mut issues = match dir {
+        Some(dir) => SchemaChecker::new(&SchemaSource::Directory(dir), false).check(),
+        None => {
+            let mut issues = SchemaChecker::new(&config.schema_source(), false).check();
+            for schema_path in &config.additional_schema_paths {
+                issues.extend(SchemaChecker::new(&SchemaSource::Directory(schema_path.clone()), true).check());
+            }
+            issues
+        }
+    };
+    issues.sort_by(|a, b| a.file.cmp(&b.file));
+
+    print!("{}", ResultFormatter::format_schema_issues(&issues));
+
+    if issues.iter().any(|issue| matches!(issue.severity, Severity::Error)) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+pub async fn schema_docs_command(cli: Cli, format: DocsFormat, output: PathBuf) -> Result<()> {
+    let config = load_config(&cli)?;
+    let schema_loader = load_schemas(&config)?;
+
+    fs::create_dir_all(&output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+
+    let schemas: Vec<_> = schema_loader
+        .get_document_types()
+        .iter()
+        .filter_map(|definition| schema_loader.get_schema(&definition.doc_type))
+        .collect();
+
+    for schema in &schemas {
+        let path = output.join(format!("{}.{}", schema.definition.doc_type, extension(format)));
+        fs::write(&path, render_type_page(schema, format))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    let index_path = output.join(format!("index.{}", extension(format)));
+    fs::write(&index_path, render_index(&schemas, format))
+        .with_context(|| format!("Failed to write {}", index_path.display()))?;
+
+    println!("Wrote {} document type pages to {}", schemas.len(), output.display());
+    Ok(())
+}
+
 pub async fn init_command(cli: Cli, _example: bool) -> Result<()> {
     println!("Initializing Forgepoint...");
     
@@ -266,6 +965,21 @@ fn load_config(cli: &Cli) -> Result<ForgepointConfig> {
     Ok(config)
 }
 
+/// Load the built-in schemas plus any repository-local schema directories
+fn load_schemas(config: &ForgepointConfig) -> Result<SchemaLoader> {
+    let mut schema_loader = match &config.schema_path {
+        Some(schema_path) => SchemaLoader::new(schema_path),
+        None => SchemaLoader::embedded(),
+    };
+    for schema_path in &config.additional_schema_paths {
+        schema_loader.add_schema_path(schema_path);
+    }
+
+    schema_loader.load_schemas()
+        .context("Failed to load schemas")?;
+    Ok(schema_loader)
+}
+
 fn find_files(patterns: &[String], exclude_patterns: &[String]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     
@@ -335,7 +1049,11 @@ fn create_document_template(
     template.push_str(":status: draft\n");
     template.push_str(&format!(":created: {}\n", date));
     template.push_str(&format!(":author: {}\n", author));
-    template.push_str(":schema-version: 1.0\n\n");
+    let schema_version = schema_loader
+        .get_schema(doc_type)
+        .and_then(|s| s.version.clone())
+        .unwrap_or_else(|| "1.0".to_string());
+    template.push_str(&format!(":schema-version: {}\n\n", schema_version));
 
     if is_abstract_required {
         template.push_str("[abstract]\n");
@@ -1,15 +1,28 @@
+use crate::schema::SchemaSource;
 use anyhow::Result;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForgepointConfig {
-    pub schema_path: PathBuf,
+    /// Schema directory replacing the built-in schemas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_path: Option<PathBuf>,
+    /// Repository-local schema directories with custom document types
+    #[serde(default)]
+    pub additional_schema_paths: Vec<PathBuf>,
     pub exclude_patterns: Vec<String>,
     pub rules: ValidationRules,
     pub output: OutputConfig,
     pub templates: Option<HashMap<String, String>>,
+    /// Review-due and staleness reporting
+    #[serde(default)]
+    pub due: DueConfig,
+    /// Dates for roadmap timelines
+    #[serde(default)]
+    pub timeline: TimelineConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +35,26 @@ pub struct ValidationRules {
     pub required_attributes: Vec<String>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DueConfig {
+    /// Report overdue documents as warnings when linting
+    #[serde(default)]
+    pub lint: bool,
+    /// Maximum number of days since a document's `updated` date, per document type
+    #[serde(default)]
+    pub max_age_days: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineConfig {
+    /// Release dates by version, for releases that have no release notes yet
+    #[serde(default)]
+    pub releases: HashMap<String, NaiveDate>,
+    /// Start dates of Shape Up cycles
+    #[serde(default)]
+    pub cycles: HashMap<String, NaiveDate>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputConfig {
     pub format: String,
@@ -33,7 +66,8 @@ pub struct OutputConfig {
 impl Default for ForgepointConfig {
     fn default() -> Self {
         Self {
-            schema_path: PathBuf::from("schema"),
+            schema_path: None,
+            additional_schema_paths: Vec::new(),
             exclude_patterns: vec![
                 "node_modules/**".to_string(),
                 "target/**".to_string(),
@@ -60,6 +94,8 @@ impl Default for ForgepointConfig {
                 color: true,
             },
             templates: None,
+            due: DueConfig::default(),
+            timeline: TimelineConfig::default(),
         }
     }
 }
@@ -122,7 +158,7 @@ impl ForgepointConfig {
         verbose: bool,
     ) -> Self {
         if let Some(path) = schema_path {
-            self.schema_path = path;
+            self.schema_path = Some(path);
         }
         
         if verbose {
@@ -132,14 +168,30 @@ impl ForgepointConfig {
         self
     }
 
+    /// The configured schema directory, or the built-in schemas when there is none
+    pub fn schema_source(&self) -> SchemaSource {
+        match &self.schema_path {
+            Some(schema_path) => SchemaSource::Directory(schema_path.clone()),
+            None => SchemaSource::Embedded,
+        }
+    }
+
     /// Resolve relative paths to absolute paths
     pub fn resolve_paths(mut self, base_dir: Option<&PathBuf>) -> Self {
         let base = base_dir
             .cloned()
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-        if self.schema_path.is_relative() {
-            self.schema_path = base.join(&self.schema_path);
+        if let Some(schema_path) = &mut self.schema_path {
+            if schema_path.is_relative() {
+                *schema_path = base.join(&*schema_path);
+            }
+        }
+
+        for schema_path in &mut self.additional_schema_paths {
+            if schema_path.is_relative() {
+                *schema_path = base.join(&*schema_path);
+            }
         }
 
         self
@@ -10,6 +10,15 @@ pub struct ForgepointDocument {
     pub attributes: HashMap<String, String>,
     pub content: String,
     pub sections: Vec<Section>,
+    #[serde(default)]
+    pub attribute_locations: HashMap<String, AttributeLocation>,
+}
+
+/// Where a header attribute is declared; the column is where its value starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeLocation {
+    pub line: usize,
+    pub column: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +46,16 @@ pub struct ChecklistItem {
     pub line_number: usize,
 }
 
+/// A `[source,gherkin]` block, with the document line number of each line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GherkinBlock {
+    /// Line of the `[source,gherkin]` attribute
+    pub line_number: usize,
+    pub lines: Vec<(usize, String)>,
+    /// Whether the block's closing delimiter was found
+    pub closed: bool,
+}
+
 impl ForgepointDocument {
     /// Check if document has the required Forgepoint structure
     pub fn has_forgepoint_structure(&self) -> bool {
@@ -61,6 +80,19 @@ impl ForgepointDocument {
         self.attributes.get("schema-version")
     }
 
+    /// Check if the document declares itself a bundle of child documents
+    pub fn is_bundle(&self) -> bool {
+        self.attributes
+            .get("forgepoint-bundle")
+            .map(|value| value != "false")
+            .unwrap_or(false)
+    }
+
+    /// Get where a header attribute is declared
+    pub fn attribute_location(&self, name: &str) -> Option<AttributeLocation> {
+        self.attribute_locations.get(name).copied()
+    }
+
     /// Validate ID format
     pub fn validate_id_format(&self) -> Result<()> {
         let id = self.document_id().ok_or_else(|| {
@@ -109,6 +141,48 @@ impl ForgepointDocument {
             .collect()
     }
 
+    /// Get the sections nested directly below the section at `index`
+    pub fn child_sections(&self, index: usize) -> Vec<(usize, &Section)> {
+        let level = match self.sections.get(index) {
+            Some(section) => section.level,
+            None => return Vec::new(),
+        };
+
+        self.sections
+            .iter()
+            .enumerate()
+            .skip(index + 1)
+            .take_while(|(_, section)| section.level > level)
+            .filter(|(_, section)| section.level == level + 1)
+            .collect()
+    }
+
+    /// Get the raw lines of a section and its subsections with their line numbers
+    pub fn section_lines(&self, index: usize) -> Vec<(usize, &str)> {
+        let section = match self.sections.get(index) {
+            Some(section) => section,
+            None => return Vec::new(),
+        };
+        let start = match section.line_number {
+            Some(line) => line,
+            None => return Vec::new(),
+        };
+
+        // The section ends where the next section of the same or higher level begins
+        let end = self.sections[index + 1..]
+            .iter()
+            .find(|next| next.level <= section.level)
+            .and_then(|next| next.line_number)
+            .unwrap_or(usize::MAX);
+
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(line_no, line)| (line_no + 1, line))
+            .filter(|(line_number, _)| *line_number > start && *line_number < end)
+            .collect()
+    }
+
     /// Get the abstract content if it exists
     pub fn abstract_content(&self) -> Option<String> {
         // Look for [abstract] block in content
@@ -153,7 +227,7 @@ impl ForgepointDocument {
         let internal_regex = regex::Regex::new(r"xref:([a-z-]+):([a-z0-9-]+)(?:\[[^\]]*\])?").unwrap();
         
         // Regex for external references: xref:github.com/org/repo#type:id@version[]
-        let external_regex = regex::Regex::new(r"xref:([^#]+)#([a-z-]+):([a-z0-9-]+)(?:@([^[\]]+))?(?:\[[^\]]*\])?").unwrap();
+        let external_regex = regex::Regex::new(r"xref:([^#]+)#([a-z-]+):([a-z0-9-]+)(?:@([^\[\]]+))?(?:\[[^\]]*\])?").unwrap();
 
         for (line_no, line) in lines.iter().enumerate() {
             // Check for internal references
@@ -204,4 +278,63 @@ impl ForgepointDocument {
 
         items
     }
+
+    /// Extract the `[source,gherkin]` blocks of the document
+    pub fn extract_gherkin_blocks(&self) -> Vec<GherkinBlock> {
+        let mut blocks = Vec::new();
+        let lines: Vec<&str> = self.content.lines().collect();
+        let source_regex = regex::Regex::new(r"^\[source\s*,\s*gherkin\s*(,[^\]]*)?\]$").unwrap();
+
+        let mut index = 0;
+        while index < lines.len() {
+            if !source_regex.is_match(lines[index].trim()) {
+                index += 1;
+                continue;
+            }
+            let line_number = index + 1;
+
+            // A block title may sit between the attribute and the delimiter
+            let mut open = index + 1;
+            while open < lines.len() && lines[open].starts_with('.') && !lines[open].starts_with("..") {
+                open += 1;
+            }
+            let delimiter = match lines.get(open).map(|line| line.trim_end()) {
+                Some(line) if line.len() >= 4 && line.chars().all(|c| c == '-') => line,
+                _ => {
+                    index = open;
+                    continue;
+                }
+            };
+
+            let body: Vec<(usize, String)> = lines[open + 1..]
+                .iter()
+                .enumerate()
+                .take_while(|(_, line)| line.trim_end() != delimiter)
+                .map(|(offset, line)| (open + offset + 2, line.to_string()))
+                .collect();
+            let end = open + 1 + body.len();
+            blocks.push(GherkinBlock {
+                line_number,
+                lines: body,
+                closed: end < lines.len(),
+            });
+            index = end + 1;
+        }
+
+        blocks
+    }
+
+    /// Extract checklist items from a section and its subsections
+    pub fn section_checklist_items(&self, index: usize) -> Vec<ChecklistItem> {
+        let lines = self.section_lines(index);
+        let (first, last) = match (lines.first(), lines.last()) {
+            (Some((first, _)), Some((last, _))) => (*first, *last),
+            _ => return Vec::new(),
+        };
+
+        self.extract_checklist_items()
+            .into_iter()
+            .filter(|item| item.line_number >= first && item.line_number <= last)
+            .collect()
+    }
 }
\ No newline at end of file
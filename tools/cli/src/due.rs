@@ -0,0 +1,236 @@
+use crate::config::DueConfig;
+use crate::document::ForgepointDocument;
+use crate::schema::{add_interval, parse_date, CompiledSchema};
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Attributes naming who is responsible for a document, in order of preference
+pub const OWNER_ATTRIBUTES: &[&str] = &["owner", "assignee", "maintainer", "author", "team"];
+
+/// Something about a document that fell due before today
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueItem {
+    pub file_path: String,
+    pub document_type: String,
+    pub document_id: Option<String>,
+    pub owner: Option<String>,
+    /// The attribute the due date comes from
+    pub attribute: String,
+    pub line: Option<usize>,
+    pub due: NaiveDate,
+    pub days_overdue: i64,
+    pub message: String,
+}
+
+impl DueItem {
+    /// The item as a lint warning
+    pub fn to_warning(&self) -> ValidationError {
+        ValidationError {
+            error_type: ErrorType::Schedule,
+            severity: Severity::Warning,
+            message: self.message.clone(),
+            location: Some(Location {
+                line: self.line,
+                column: None,
+                section: Some("attributes".to_string()),
+            }),
+            rule: Some("due".to_string()),
+            suggestion: Some(format!("Update :{}: once this has been dealt with", self.attribute)),
+        }
+    }
+}
+
+/// The person or team responsible for a document
+pub fn document_owner(doc: &ForgepointDocument) -> Option<String> {
+    OWNER_ATTRIBUTES
+        .iter()
+        .filter_map(|name| doc.attributes.get(*name))
+        .find(|value| !value.trim().is_empty())
+        .cloned()
+}
+
+/// Find the schema's due dates that passed before `today`, and whether the
+/// document has gone longer without an update than its type allows
+pub fn find_due_items(
+    doc: &ForgepointDocument,
+    schema: &CompiledSchema,
+    config: &DueConfig,
+    today: NaiveDate,
+) -> Vec<DueItem> {
+    let mut items = Vec::new();
+    let mut handled: Vec<&str> = Vec::new();
+
+    for rule in &schema.due_dates {
+        let description = rule.description.as_deref().unwrap_or(&rule.attribute);
+        if handled.contains(&description) {
+            continue;
+        }
+
+        if let Some(statuses) = &rule.status {
+            if !doc.attributes.get("status").is_some_and(|status| statuses.contains(status)) {
+                continue;
+            }
+        }
+
+        let Some(date) = doc.attributes.get(&rule.attribute).and_then(|v| parse_date(v)).map(|d| d.date()) else {
+            continue;
+        };
+        let due = match &rule.every {
+            Some(every) => match doc.attributes.get(every).and_then(|interval| add_interval(date, interval)) {
+                Some(due) => due,
+                None => continue,
+            },
+            None => date,
+        };
+        handled.push(description);
+
+        if due < today {
+            let days_overdue = (today - due).num_days();
+            items.push(due_item(
+                doc,
+                schema,
+                &rule.attribute,
+                due,
+                days_overdue,
+                format!("{}: due {}, {} overdue", description, due, days(days_overdue)),
+            ));
+        }
+    }
+
+    if let Some(max_age) = config.max_age_days.get(&schema.definition.doc_type) {
+        let updated = ["updated", "created"]
+            .into_iter()
+            .find_map(|name| doc.attributes.get(name).and_then(|v| parse_date(v)).map(|d| (name, d.date())));
+
+        if let Some((attribute, date)) = updated {
+            if let Some(due) = date.checked_add_days(Days::new(*max_age)).filter(|due| *due < today) {
+                let age = (today - date).num_days();
+                items.push(due_item(
+                    doc,
+                    schema,
+                    attribute,
+                    due,
+                    (today - due).num_days(),
+                    format!(
+                        "Not updated in {} (limit for {} is {})",
+                        days(age),
+                        schema.definition.doc_type,
+                        days(*max_age as i64)
+                    ),
+                ));
+            }
+        }
+    }
+
+    items
+}
+
+fn due_item(
+    doc: &ForgepointDocument,
+    schema: &CompiledSchema,
+    attribute: &str,
+    due: NaiveDate,
+    days_overdue: i64,
+    message: String,
+) -> DueItem {
+    DueItem {
+        file_path: doc.file_path.to_string_lossy().to_string(),
+        document_type: schema.definition.doc_type.clone(),
+        document_id: doc.document_id().cloned(),
+        owner: document_owner(doc),
+        attribute: attribute.to_string(),
+        line: doc.attribute_location(attribute).map(|l| l.line),
+        due,
+        days_overdue,
+        message,
+    }
+}
+
+fn days(count: i64) -> String {
+    match count {
+        1 => "1 day".to_string(),
+        count => format!("{} days", count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use crate::schema::SchemaLoader;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn messages(content: &str, config: &DueConfig, today: &str) -> Vec<String> {
+        let mut loader = SchemaLoader::embedded();
+        loader.load_schemas().unwrap();
+        let doc = DocumentParser::new()
+            .parse_content(content, PathBuf::from("doc.adoc"))
+            .unwrap();
+        let schema = loader.get_schema(doc.document_type().unwrap()).unwrap();
+        find_due_items(&doc, schema, config, date(today))
+            .into_iter()
+            .map(|item| item.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_review_due() {
+        let config = DueConfig::default();
+        let register = |next_review: &str| {
+            format!(
+                "= Risks\n:forgepoint-type: risk-register\n:id: payment-risks\n:status: active\n:owner: alice\n:review-frequency: monthly\n:last-review: 2024-01-10\n{}:schema-version: 1.0\n",
+                next_review
+            )
+        };
+
+        // An explicit next review wins over the review frequency
+        assert_eq!(
+            messages(&register(":next-review: 2024-03-01\n"), &config, "2024-03-05"),
+            vec!["Risk review: due 2024-03-01, 4 days overdue"]
+        );
+        assert!(messages(&register(":next-review: 2024-03-01\n"), &config, "2024-02-20").is_empty());
+        assert_eq!(
+            messages(&register(""), &config, "2024-02-11"),
+            vec!["Risk review: due 2024-02-10, 1 day overdue"]
+        );
+    }
+
+    #[test]
+    fn test_expired_flags() {
+        let config = DueConfig::default();
+        let flag = |status: &str| {
+            format!(
+                "= New Checkout\n:forgepoint-type: feature-flag\n:id: new-checkout\n:status: {}\n:flag-key: new-checkout\n:flag-type: release\n:expiry-date: 2024-01-01\n:schema-version: 1.0\n",
+                status
+            )
+        };
+
+        assert_eq!(
+            messages(&flag("active"), &config, "2024-01-15"),
+            vec!["Flag removal: due 2024-01-01, 14 days overdue"]
+        );
+        assert!(messages(&flag("removed"), &config, "2024-01-15").is_empty());
+    }
+
+    #[test]
+    fn test_stale_documents() {
+        let config = DueConfig {
+            lint: false,
+            max_age_days: HashMap::from([("prd".to_string(), 90)]),
+        };
+        let content = "= Payments\n:forgepoint-type: prd\n:id: payments\n:status: draft\n:created: 2024-01-01\n:updated: 2024-02-01\n:schema-version: 1.0\n";
+
+        assert!(messages(content, &config, "2024-04-30").is_empty());
+        assert_eq!(
+            messages(content, &config, "2024-05-10"),
+            vec!["Not updated in 99 days (limit for prd is 90 days)"]
+        );
+        assert!(messages(content, &DueConfig::default(), "2025-01-01").is_empty());
+    }
+}
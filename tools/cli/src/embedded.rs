@@ -0,0 +1,50 @@
+/// The built-in schema pack, compiled into the binary so the CLI works without
+/// a schema directory on disk. Paths are relative to the schema directory.
+pub const EMBEDDED_SCHEMAS: &[(&str, &str)] = &[
+    ("adr.json", include_str!("../../../schema/adr.json")),
+    ("api-spec.json", include_str!("../../../schema/api-spec.json")),
+    ("brd.json", include_str!("../../../schema/brd.json")),
+    ("bug-report.json", include_str!("../../../schema/bug-report.json")),
+    ("changelog.json", include_str!("../../../schema/changelog.json")),
+    ("deployment-plan.json", include_str!("../../../schema/deployment-plan.json")),
+    ("design-doc.json", include_str!("../../../schema/design-doc.json")),
+    ("epic.json", include_str!("../../../schema/epic.json")),
+    ("feature-flag.json", include_str!("../../../schema/feature-flag.json")),
+    ("index.json", include_str!("../../../schema/index.json")),
+    ("jtbd.json", include_str!("../../../schema/jtbd.json")),
+    ("mrd.json", include_str!("../../../schema/mrd.json")),
+    ("okr.json", include_str!("../../../schema/okr.json")),
+    ("one-pager.json", include_str!("../../../schema/one-pager.json")),
+    ("opportunity-assessment.json", include_str!("../../../schema/opportunity-assessment.json")),
+    ("postmortem.json", include_str!("../../../schema/postmortem.json")),
+    ("prd.json", include_str!("../../../schema/prd.json")),
+    ("prfaq.json", include_str!("../../../schema/prfaq.json")),
+    ("product-brief.json", include_str!("../../../schema/product-brief.json")),
+    ("product-roadmap.json", include_str!("../../../schema/product-roadmap.json")),
+    ("release-notes.json", include_str!("../../../schema/release-notes.json")),
+    ("retrospective.json", include_str!("../../../schema/retrospective.json")),
+    ("rfc.json", include_str!("../../../schema/rfc.json")),
+    ("risk-register.json", include_str!("../../../schema/risk-register.json")),
+    ("runbook.json", include_str!("../../../schema/runbook.json")),
+    ("safe-feature.json", include_str!("../../../schema/safe-feature.json")),
+    ("scenario.json", include_str!("../../../schema/scenario.json")),
+    ("shape-up-pitch.json", include_str!("../../../schema/shape-up-pitch.json")),
+    ("sprint-plan.json", include_str!("../../../schema/sprint-plan.json")),
+    ("story.json", include_str!("../../../schema/story.json")),
+    ("task.json", include_str!("../../../schema/task.json")),
+    ("technical-spec.json", include_str!("../../../schema/technical-spec.json")),
+    ("test-case.json", include_str!("../../../schema/test-case.json")),
+    ("test-plan.json", include_str!("../../../schema/test-plan.json")),
+    ("test-results.json", include_str!("../../../schema/test-results.json")),
+    ("use-case.json", include_str!("../../../schema/use-case.json")),
+    ("user-journey.json", include_str!("../../../schema/user-journey.json")),
+    ("vision-strategy.json", include_str!("../../../schema/vision-strategy.json")),
+];
+
+/// Look up a built-in schema file
+pub fn embedded_schema(path: &str) -> Option<&'static str> {
+    EMBEDDED_SCHEMAS
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, content)| *content)
+}
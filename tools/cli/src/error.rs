@@ -34,6 +34,9 @@ pub enum ForgepointError {
 
     #[error("Reference error: {0}")]
     Reference(String),
+
+    #[error("Git error: {0}")]
+    Git(String),
 }
 
 pub type Result<T> = std::result::Result<T, ForgepointError>;
\ No newline at end of file
@@ -0,0 +1,287 @@
+use crate::document::ForgepointDocument;
+use crate::error::{ForgepointError, Result};
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The JSON schema flagd configuration files declare
+pub const FLAGD_SCHEMA: &str = "https://flagd.dev/schema/v0/flags.json";
+
+/// A flag definition taken from a feature-flag document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagDefinition {
+    pub file_path: String,
+    pub document_id: Option<String>,
+    pub key: String,
+    pub flag_type: Option<String>,
+    pub status: Option<String>,
+    pub default_value: String,
+    pub variations: Vec<String>,
+    pub targeting: Option<String>,
+    pub environments: Vec<String>,
+    pub owner: Option<String>,
+}
+
+impl FlagDefinition {
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        if doc.document_type().map(String::as_str) != Some("feature-flag") {
+            return None;
+        }
+        Some(Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_id: doc.document_id().cloned(),
+            key: doc.attributes.get("flag-key")?.trim().to_string(),
+            flag_type: doc.attributes.get("flag-type").cloned(),
+            status: doc.attributes.get("status").cloned(),
+            default_value: doc.attributes.get("default-value")?.trim().to_string(),
+            variations: list_attribute(doc, "variations"),
+            targeting: doc.attributes.get("targeting").map(|value| value.trim().to_string()),
+            environments: list_attribute(doc, "environments"),
+            owner: doc.attributes.get("owner").cloned(),
+        })
+    }
+
+    /// Removed flags are left out of exported configuration
+    pub fn is_removed(&self) -> bool {
+        self.status.as_deref() == Some("removed")
+    }
+
+    /// Whether the flag applies to an environment; flags that list no
+    /// environments apply to all of them
+    pub fn in_environment(&self, environment: &str) -> bool {
+        self.environments.is_empty() || self.environments.iter().any(|env| env == environment)
+    }
+
+    /// The variations to export: those listed, or the default value and,
+    /// for a boolean flag, its opposite
+    pub fn variants(&self) -> Vec<String> {
+        if !self.variations.is_empty() {
+            return self.variations.clone();
+        }
+        match self.default_value.as_str() {
+            "true" => vec!["true".to_string(), "false".to_string()],
+            "false" => vec!["false".to_string(), "true".to_string()],
+            default => vec![default.to_string()],
+        }
+    }
+
+    /// The flagd definition: planned flags are disabled, and targeting that
+    /// isn't a JsonLogic rule is kept as metadata
+    pub fn to_flagd(&self) -> Value {
+        let variants: Map<String, Value> = self
+            .variants()
+            .into_iter()
+            .map(|variant| {
+                let value = variant_value(&variant);
+                (variant, value)
+            })
+            .collect();
+        let state = match self.status.as_deref() {
+            Some("planned") => "DISABLED",
+            _ => "ENABLED",
+        };
+
+        let mut flag = json!({
+            "state": state,
+            "variants": variants,
+            "defaultVariant": self.default_value,
+        });
+        let mut metadata = Map::new();
+        match self.targeting.as_deref().map(serde_json::from_str::<Value>) {
+            Some(Ok(rule @ Value::Object(_))) => {
+                flag["targeting"] = rule;
+            }
+            Some(_) => {
+                metadata.insert("targeting".to_string(), json!(self.targeting));
+            }
+            None => {}
+        }
+        if let Some(flag_type) = &self.flag_type {
+            metadata.insert("flagType".to_string(), json!(flag_type));
+        }
+        if let Some(owner) = &self.owner {
+            metadata.insert("owner".to_string(), json!(owner));
+        }
+        if !self.environments.is_empty() {
+            metadata.insert("environments".to_string(), json!(self.environments.join(",")));
+        }
+        if !metadata.is_empty() {
+            flag["metadata"] = Value::Object(metadata);
+        }
+        flag
+    }
+}
+
+/// A comma-separated list attribute
+fn list_attribute(doc: &ForgepointDocument, name: &str) -> Vec<String> {
+    doc.attributes
+        .get(name)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The typed value of a variation: booleans and numbers, otherwise a string
+fn variant_value(variation: &str) -> Value {
+    match variation {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => variation
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| variation.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number))
+            .unwrap_or_else(|| Value::String(variation.to_string())),
+    }
+}
+
+/// A flagd configuration file with a flag per definition
+pub fn flagd_config(flags: &[FlagDefinition]) -> Value {
+    let definitions: Map<String, Value> = flags.iter().map(|flag| (flag.key.clone(), flag.to_flagd())).collect();
+    json!({
+        "$schema": FLAGD_SCHEMA,
+        "flags": definitions,
+    })
+}
+
+/// The flags to export from a set of documents, sorted by key. Removed flags
+/// and flags outside `environment` are left out before anything is checked;
+/// an invalid default or a flag key used twice is an error
+pub fn exported_flags(docs: &[ForgepointDocument], environment: Option<&str>) -> Result<Vec<FlagDefinition>> {
+    let mut flags: Vec<FlagDefinition> = Vec::new();
+    for doc in docs {
+        let Some(flag) = FlagDefinition::from_document(doc) else {
+            continue;
+        };
+        if flag.is_removed() || environment.is_some_and(|env| !flag.in_environment(env)) {
+            continue;
+        }
+        if let Some(error) = check_flag_variations(doc) {
+            return Err(ForgepointError::Validation(format!("{}: {}", flag.file_path, error.message)));
+        }
+        if let Some(other) = flags.iter().find(|other| other.key == flag.key) {
+            return Err(ForgepointError::Validation(format!(
+                "Flag key '{}' is used by both {} and {}",
+                flag.key, other.file_path, flag.file_path
+            )));
+        }
+        flags.push(flag);
+    }
+    flags.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(flags)
+}
+
+/// Report a `default-value` that isn't one of the flag's `variations`
+pub fn check_flag_variations(doc: &ForgepointDocument) -> Option<ValidationError> {
+    let flag = FlagDefinition::from_document(doc)?;
+    if flag.variations.is_empty() || flag.variations.contains(&flag.default_value) {
+        return None;
+    }
+
+    Some(ValidationError {
+        error_type: ErrorType::Schema,
+        severity: Severity::Error,
+        message: format!(
+            ":default-value: {} isn't one of the :variations: ({})",
+            flag.default_value,
+            flag.variations.join(", ")
+        ),
+        location: doc.attribute_location("default-value").map(|location| Location {
+            line: Some(location.line),
+            column: Some(location.column),
+            section: Some("attributes".to_string()),
+        }),
+        rule: Some("flag-default-variation".to_string()),
+        suggestion: Some("Add the default value to :variations:, or change :default-value:".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn flag(id: &str, attributes: &str) -> ForgepointDocument {
+        DocumentParser::new()
+            .parse_content(
+                &format!(
+                    "= {}\n:forgepoint-type: feature-flag\n:id: {}\n:flag-key: {}\n:flag-type: release\n:owner: payments\n{}:schema-version: 1.0\n",
+                    id, id, id, attributes
+                ),
+                PathBuf::from("flag.adoc"),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_flag_variations() {
+        let valid = flag("new-checkout", ":status: active\n:default-value: off\n:variations: on, off\n");
+        assert!(check_flag_variations(&valid).is_none());
+        assert!(check_flag_variations(&flag("dark-mode", ":status: active\n:default-value: false\n")).is_none());
+
+        let error = check_flag_variations(&flag("theme", ":status: active\n:default-value: blue\n:variations: red, green\n")).unwrap();
+        assert_eq!(error.message, ":default-value: blue isn't one of the :variations: (red, green)");
+        assert_eq!(error.location.unwrap().line, Some(8));
+    }
+
+    #[test]
+    fn test_exported_flags() {
+        let docs = vec![
+            flag("new-checkout", ":status: active\n:default-value: off\n:variations: on, off\n"),
+            flag("old-theme", ":status: removed\n:default-value: blue\n:variations: red, green\n"),
+            flag("staging-only", ":status: active\n:default-value: x\n:variations: y\n:environments: staging\n"),
+        ];
+        let flags = exported_flags(&docs, Some("production")).unwrap();
+        assert_eq!(flags.iter().map(|flag| flag.key.as_str()).collect::<Vec<_>>(), vec!["new-checkout"]);
+
+        let error = exported_flags(&docs, Some("staging")).unwrap_err().to_string();
+        assert!(error.contains("flag.adoc"));
+        assert!(error.contains(":default-value: x isn't one of the :variations: (y)"));
+
+        let duplicate = vec![docs[0].clone(), docs[0].clone()];
+        assert!(exported_flags(&duplicate, None).unwrap_err().to_string().contains("used by both"));
+    }
+
+    #[test]
+    fn test_flagd_config() {
+        let flags: Vec<FlagDefinition> = [
+            flag(
+                "new-checkout",
+                ":status: active\n:default-value: off\n:variations: on, off\n:environments: staging, production\n:targeting: {\"if\": [{\"in\": [\"@example.com\", {\"var\": \"email\"}]}, \"on\", \"off\"]}\n",
+            ),
+            flag("page-size", ":status: planned\n:default-value: 20\n:variations: 20, 50\n:targeting: Beta customers\n"),
+            flag("dark-mode", ":status: graduated\n:default-value: false\n"),
+        ]
+        .iter()
+        .filter_map(FlagDefinition::from_document)
+        .collect();
+
+        assert!(flags[0].in_environment("staging"));
+        assert!(!flags[0].in_environment("development"));
+        assert!(flags[1].in_environment("development"));
+
+        let config = flagd_config(&flags);
+        assert_eq!(config["$schema"], FLAGD_SCHEMA);
+        let checkout = &config["flags"]["new-checkout"];
+        assert_eq!(checkout["state"], "ENABLED");
+        assert_eq!(checkout["variants"], json!({"on": "on", "off": "off"}));
+        assert_eq!(checkout["defaultVariant"], "off");
+        assert_eq!(checkout["targeting"]["if"][1], "on");
+        assert_eq!(checkout["metadata"]["environments"], "staging,production");
+
+        let page_size = &config["flags"]["page-size"];
+        assert_eq!(page_size["state"], "DISABLED");
+        assert_eq!(page_size["variants"], json!({"20": 20, "50": 50}));
+        assert_eq!(page_size["metadata"]["targeting"], "Beta customers");
+        assert!(page_size.get("targeting").is_none());
+
+        assert_eq!(config["flags"]["dark-mode"]["variants"], json!({"false": false, "true": true}));
+    }
+}
@@ -1,8 +1,17 @@
-use crate::schema::DocumentTypeDefinition;
+use crate::due::DueItem;
+use crate::html::{self, escape_html};
+use crate::okr::{KeyResult, PeriodReport};
+use crate::progress::{Completion, DocumentProgress};
+use crate::schema::{DocumentTypeDefinition, BUILTIN_CATEGORIES};
+use crate::schema_check::SchemaIssue;
+use crate::sprint::SprintReport;
+use crate::timeline::Timeline;
 use crate::validator::{ValidationResult, ValidationError, Severity};
+use crate::wsjf::ArtBacklog;
 use colored::*;
 use serde_json;
-use std::collections::HashMap;
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
 
 pub struct ResultFormatter;
 
@@ -182,7 +191,16 @@ impl ResultFormatter {
         let mut output = String::new();
         output.push_str(&format!("{}\n\n", "Available Document Types:".bold()));
 
-        let categories = ["discovery", "design", "development", "testing", "release"];
+        // Built-in categories first, then any introduced by repository-local types
+        let mut categories: Vec<&str> = BUILTIN_CATEGORIES.to_vec();
+        let mut custom_categories: Vec<&str> = document_types
+            .iter()
+            .map(|dt| dt.category.as_str())
+            .filter(|category| !categories.contains(category))
+            .collect();
+        custom_categories.sort();
+        custom_categories.dedup();
+        categories.extend(custom_categories);
 
         for category in &categories {
             let category_types: Vec<_> = document_types
@@ -209,6 +227,510 @@ impl ResultFormatter {
         output
     }
 
+    /// Format the issues found in a schema set
+    pub fn format_schema_issues(issues: &[SchemaIssue]) -> String {
+        if issues.is_empty() {
+            return format!("{} Schemas are valid\n", "✓".green());
+        }
+
+        let mut output = String::new();
+        let mut current_file = None;
+        for issue in issues {
+            if current_file != Some(&issue.file) {
+                output.push_str(&format!("{}\n", issue.file.cyan()));
+                current_file = Some(&issue.file);
+            }
+
+            let icon = match issue.severity {
+                Severity::Error => "  ✗".red(),
+                Severity::Warning => "  ⚠".yellow(),
+            };
+            let doc_type = issue
+                .document_type
+                .as_ref()
+                .map(|t| format!("[{}] ", t).dimmed().to_string())
+                .unwrap_or_default();
+            output.push_str(&format!("{} {}{}\n", icon, doc_type, issue.message));
+        }
+
+        let errors = issues.iter().filter(|i| matches!(i.severity, Severity::Error)).count();
+        output.push_str(&format!(
+            "\n{} errors, {} warnings\n",
+            errors,
+            issues.len() - errors
+        ));
+        output
+    }
+
+    /// Format overdue items as text, one list per owner
+    pub fn format_due_items(items: &[DueItem], today: NaiveDate) -> String {
+        if items.is_empty() {
+            return format!("{} Nothing is overdue as of {}\n", "✓".green(), today);
+        }
+
+        let groups = Self::group_due_items(items);
+        let mut output = String::new();
+        for (owner, owner_items) in &groups {
+            output.push_str(&format!(
+                "{} ({})\n",
+                owner.as_deref().unwrap_or("No owner").bold(),
+                owner_items.len()
+            ));
+            for item in owner_items {
+                let location = match item.line {
+                    Some(line) => format!("{}:{}", item.file_path, line),
+                    None => item.file_path.clone(),
+                };
+                let document = format!(
+                    "[{} {}]",
+                    item.document_type,
+                    item.document_id.as_deref().unwrap_or("?")
+                );
+                output.push_str(&format!(
+                    "  {} {} {} {}\n",
+                    "⚠".yellow(),
+                    location.cyan(),
+                    document.dimmed(),
+                    item.message
+                ));
+            }
+            output.push('\n');
+        }
+
+        output.push_str(&format!(
+            "{} overdue items for {} owners as of {}\n",
+            items.len(),
+            groups.len(),
+            today
+        ));
+        output
+    }
+
+    /// Format overdue items as JSON, grouped by owner
+    pub fn format_due_items_json(items: &[DueItem], today: NaiveDate) -> Result<String, serde_json::Error> {
+        let owners: Vec<serde_json::Value> = Self::group_due_items(items)
+            .into_iter()
+            .map(|(owner, items)| serde_json::json!({ "owner": owner, "items": items }))
+            .collect();
+
+        serde_json::to_string_pretty(&serde_json::json!({ "asOf": today, "owners": owners }))
+    }
+
+    /// Group overdue items by owner, most overdue first, with unowned items last
+    fn group_due_items(items: &[DueItem]) -> Vec<(Option<String>, Vec<&DueItem>)> {
+        let mut groups: BTreeMap<(bool, Option<String>), Vec<&DueItem>> = BTreeMap::new();
+        for item in items {
+            groups
+                .entry((item.owner.is_none(), item.owner.clone()))
+                .or_default()
+                .push(item);
+        }
+
+        groups
+            .into_iter()
+            .map(|((_, owner), mut items)| {
+                items.sort_by(|a, b| b.days_overdue.cmp(&a.days_overdue).then(a.file_path.cmp(&b.file_path)));
+                (owner, items)
+            })
+            .collect()
+    }
+
+    /// Format checklist completion per document, with its sections and the
+    /// rollup of the documents below it
+    pub fn format_progress(documents: &[DocumentProgress]) -> String {
+        if documents.is_empty() {
+            return "No checklist items found\n".to_string();
+        }
+
+        let mut output = String::new();
+        let mut overall = Completion::default();
+        for doc in documents {
+            overall.add(doc.completion);
+
+            let document = format!(
+                "[{} {}]",
+                doc.document_type.as_deref().unwrap_or("?"),
+                doc.document_id.as_deref().unwrap_or("?")
+            );
+            output.push_str(&format!(
+                "{} {} {}",
+                doc.title.as_deref().unwrap_or("Untitled").bold(),
+                document.dimmed(),
+                doc.file_path.cyan()
+            ));
+            if let Some(status) = &doc.status {
+                output.push_str(&format!(" ({})", status));
+            }
+            output.push('\n');
+
+            if doc.completion.total > 0 {
+                output.push_str(&format!("  {} {}\n", Self::progress_bar(doc.completion), Self::completion(doc.completion)));
+            }
+            for section in &doc.sections {
+                output.push_str(&format!("    {}: {}\n", section.section, Self::completion(section.completion)));
+            }
+            if let Some(rollup) = &doc.rollup {
+                output.push_str(&format!(
+                    "  {} {} of {} children complete",
+                    Self::progress_bar(rollup.completion),
+                    rollup.children_completed,
+                    rollup.children
+                ));
+                if rollup.completion.total > 0 {
+                    output.push_str(&format!(", {} across children", Self::completion(rollup.completion)));
+                }
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+
+        output.push_str(&format!(
+            "{} documents, {} of {} checklist items checked ({:.0}%)\n",
+            documents.len(),
+            overall.checked,
+            overall.total,
+            overall.ratio().unwrap_or(0.0) * 100.0
+        ));
+        output
+    }
+
+    /// Format checklist completion as JSON
+    pub fn format_progress_json(documents: &[DocumentProgress]) -> Result<String, serde_json::Error> {
+        let mut overall = Completion::default();
+        for doc in documents {
+            overall.add(doc.completion);
+        }
+
+        serde_json::to_string_pretty(&serde_json::json!({
+            "checked": overall.checked,
+            "total": overall.total,
+            "documents": documents,
+        }))
+    }
+
+    /// Format OKR progress by period, with level summaries and objectives
+    /// nested under the objectives they contribute to
+    pub fn format_okr_report(periods: &[PeriodReport]) -> String {
+        if periods.is_empty() {
+            return "No OKRs found\n".to_string();
+        }
+
+        let mut output = String::new();
+        for period in periods {
+            output.push_str(&format!("{}\n", period.period.bold()));
+            for level in &period.levels {
+                output.push_str(&format!(
+                    "  {}: {} objective{}, {}\n",
+                    level.level,
+                    level.objectives,
+                    if level.objectives == 1 { "" } else { "s" },
+                    Self::percent(level.progress)
+                ));
+            }
+            output.push('\n');
+
+            for (depth, node) in period.flatten() {
+                let objective = &node.objective;
+                let indent = "  ".repeat(depth + 1);
+                output.push_str(&format!(
+                    "{}{} {:>4} {} {} {}",
+                    indent,
+                    Self::ratio_bar(objective.progress.unwrap_or(0.0)),
+                    Self::percent(objective.progress),
+                    objective.title.as_deref().unwrap_or("Untitled").bold(),
+                    format!("[okr {}]", objective.document_id.as_deref().unwrap_or("?")).dimmed(),
+                    objective.file_path.cyan()
+                ));
+                if !node.children.is_empty() {
+                    output.push_str(&format!(" (rolled up: {})", Self::percent(node.rolled_up)));
+                }
+                output.push('\n');
+
+                for kr in &objective.key_results {
+                    output.push_str(&format!(
+                        "{}    {} {}{} {}\n",
+                        indent,
+                        if kr.checked { "✓".green() } else { "○".normal() },
+                        kr.text,
+                        Self::key_result_metrics(kr),
+                        Self::percent(Some(kr.progress)).dimmed()
+                    ));
+                }
+            }
+            output.push('\n');
+        }
+
+        output.trim_end().to_string() + "\n"
+    }
+
+    /// Format the OKR report as JSON
+    pub fn format_okr_report_json(periods: &[PeriodReport]) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({ "periods": periods }))
+    }
+
+    /// Format the OKR report as a standalone HTML page
+    pub fn format_okr_report_html(periods: &[PeriodReport]) -> String {
+        let mut body = String::from("<h1>OKR Report</h1>\n");
+        if periods.is_empty() {
+            body.push_str("<p>No OKRs found.</p>\n");
+        }
+
+        for period in periods {
+            body.push_str(&format!("<h2>{}</h2>\n", escape_html(&period.period)));
+            body.push_str("<table>\n<tr><th>Level</th><th>Objectives</th><th>Progress</th></tr>\n");
+            for level in &period.levels {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{} {}</td></tr>\n",
+                    escape_html(&level.level),
+                    level.objectives,
+                    html::progress_bar(level.progress.unwrap_or(0.0)),
+                    Self::percent(level.progress)
+                ));
+            }
+            body.push_str("</table>\n");
+
+            body.push_str("<table>\n<tr><th>Objective</th><th>Level</th><th>Owner</th><th>Progress</th><th>Rolled up</th><th>Key results</th></tr>\n");
+            for (depth, node) in period.flatten() {
+                let objective = &node.objective;
+                let key_results: String = objective
+                    .key_results
+                    .iter()
+                    .map(|kr| {
+                        format!(
+                            "<li>{} {}{} ({})</li>",
+                            if kr.checked { "&#10003;" } else { "&#9675;" },
+                            escape_html(&kr.text),
+                            escape_html(&Self::key_result_metrics(kr)),
+                            Self::percent(Some(kr.progress))
+                        )
+                    })
+                    .collect();
+                body.push_str(&format!(
+                    "<tr><td style=\"padding-left: {}em\">{}<br><code>{}</code></td><td>{}</td><td>{}</td><td>{} {}</td><td>{}</td><td><ul>{}</ul></td></tr>\n",
+                    0.6 + depth as f64 * 1.5,
+                    escape_html(objective.title.as_deref().unwrap_or("Untitled")),
+                    escape_html(objective.document_id.as_deref().unwrap_or("?")),
+                    escape_html(objective.level.as_deref().unwrap_or("")),
+                    escape_html(objective.owner.as_deref().or(objective.team.as_deref()).unwrap_or("")),
+                    html::progress_bar(objective.progress.unwrap_or(0.0)),
+                    Self::percent(objective.progress),
+                    if node.children.is_empty() { String::new() } else { Self::percent(node.rolled_up) },
+                    key_results
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        html::page("OKR Report", &body)
+    }
+
+    /// Format sprint plans with their committed points against capacity and
+    /// the differences between the backlog and the assigned items
+    pub fn format_sprint_reports(reports: &[SprintReport]) -> String {
+        if reports.is_empty() {
+            return "No sprint plans found\n".to_string();
+        }
+
+        let mut output = String::new();
+        for report in reports {
+            let plan = &report.plan;
+            output.push_str(&format!(
+                "{} {} {}\n",
+                plan.title.as_deref().unwrap_or("Untitled").bold(),
+                format!("[sprint-plan {}]", plan.document_id).dimmed(),
+                plan.file_path.cyan()
+            ));
+
+            let mut committed = format!("  Committed: {} points", report.committed);
+            if report.unestimated > 0 {
+                committed.push_str(&format!(" ({} unestimated)", report.unestimated));
+            }
+            if let Some(capacity) = plan.capacity {
+                committed.push_str(&format!(", capacity {}", capacity));
+            }
+            if let Some(velocity) = plan.velocity {
+                committed.push_str(&format!(", velocity {}", velocity));
+            }
+            output.push_str(&committed);
+            match plan.capacity {
+                Some(capacity) if report.committed > capacity => output.push_str(&format!(
+                    " {}\n",
+                    format!("(over capacity by {})", report.committed - capacity).yellow()
+                )),
+                _ => output.push('\n'),
+            }
+            output.push_str(&format!("  Completed: {} of {} points\n", report.completed, report.committed));
+
+            for item in &report.items {
+                output.push_str(&format!(
+                    "    {} {} {} {}{}\n",
+                    if item.is_completed() { "✓".green() } else { "○".normal() },
+                    item.title.as_deref().unwrap_or("Untitled"),
+                    format!("[{} {}]", item.document_type, item.document_id).dimmed(),
+                    item.points.map(|points| format!("{} pts", points)).unwrap_or_else(|| "? pts".to_string()),
+                    item.status.as_ref().map(|status| format!(" ({})", status)).unwrap_or_default()
+                ));
+            }
+
+            if !report.not_assigned.is_empty() {
+                output.push_str(&format!("  {}\n", "In the backlog but not assigned to the sprint:".yellow()));
+                for unassigned in &report.not_assigned {
+                    let entry = &unassigned.entry;
+                    let reason = match (&unassigned.sprint, unassigned.found) {
+                        (Some(sprint), _) => format!("assigned to {}", sprint),
+                        (None, true) => "no sprint".to_string(),
+                        (None, false) => "not found".to_string(),
+                    };
+                    output.push_str(&format!(
+                        "    {}:{} ({})",
+                        entry.document_type, entry.document_id, reason
+                    ));
+                    if let Some(line) = entry.line {
+                        output.push_str(&format!(" {}", format!("line {}", line).dimmed()));
+                    }
+                    output.push('\n');
+                }
+            }
+            if !report.not_in_backlog.is_empty() {
+                output.push_str(&format!("  {}\n", "Assigned to the sprint but not in the backlog:".yellow()));
+                for item in &report.not_in_backlog {
+                    output.push_str(&format!(
+                        "    {}:{} {}\n",
+                        item.document_type,
+                        item.document_id,
+                        item.file_path.cyan()
+                    ));
+                }
+            }
+            output.push('\n');
+        }
+
+        output.trim_end().to_string() + "\n"
+    }
+
+    /// Format sprint reports as JSON
+    pub fn format_sprint_reports_json(reports: &[SprintReport]) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({ "sprints": reports }))
+    }
+
+    /// Format ranked backlogs per ART, with each item's WSJF components
+    pub fn format_ranked_backlogs(backlogs: &[ArtBacklog]) -> String {
+        if backlogs.is_empty() {
+            return "No documents to rank\n".to_string();
+        }
+
+        let mut output = String::new();
+        for backlog in backlogs {
+            output.push_str(&format!("{}\n", backlog.art.as_deref().unwrap_or("No ART").bold()));
+            for item in &backlog.items {
+                let rank = item.rank.map(|rank| format!("{}.", rank)).unwrap_or_else(|| "-".to_string());
+                let score = match (item.wsjf, item.stated_wsjf) {
+                    (Some(wsjf), _) => format!("WSJF {:.2} ({}/{})", wsjf.score, wsjf.cost_of_delay, wsjf.job_size),
+                    (None, Some(stated)) => format!("WSJF {:.2} (stated)", stated),
+                    (None, None) => "WSJF -".to_string(),
+                };
+                output.push_str(&format!(
+                    "  {:>3} {} {} {} {}",
+                    rank,
+                    score,
+                    item.title.as_deref().unwrap_or("Untitled"),
+                    format!("[{}]", item.document_id.as_deref().unwrap_or("?")).dimmed(),
+                    item.file_path.cyan()
+                ));
+                if let Some(status) = &item.status {
+                    output.push_str(&format!(" ({})", status));
+                }
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+
+        output.trim_end().to_string() + "\n"
+    }
+
+    /// Format ranked backlogs as JSON
+    pub fn format_ranked_backlogs_json(backlogs: &[ArtBacklog]) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({ "arts": backlogs }))
+    }
+
+    pub fn format_timeline_html(timeline: &Timeline, title: &str) -> String {
+        let mut body = format!("<h1>{}</h1>\n", escape_html(title));
+        if timeline.lanes.is_empty() {
+            body.push_str("<p>No roadmap items found.</p>\n");
+        }
+
+        let range = timeline.range();
+        let position = |date: NaiveDate| match range {
+            Some((first, last)) => (date - first).num_days() as f64 / (last - first).num_days().max(1) as f64,
+            None => 0.0,
+        };
+        for lane in &timeline.lanes {
+            body.push_str(&format!("<h2>{}</h2>\n", escape_html(&lane.name())));
+            if let Some(product) = lane.roadmap.as_ref().and_then(|roadmap| roadmap.product.as_ref()) {
+                body.push_str(&format!("<p>Product: {}</p>\n", escape_html(product)));
+            }
+            body.push_str("<table>\n<tr><th>Item</th><th>Status</th><th>Team</th><th>Scheduled by</th><th>Dates</th><th>Timeline</th></tr>\n");
+            for item in &lane.items {
+                let (dates, bar) = match (item.start, item.end) {
+                    (Some(start), Some(end)) => (format!("{} – {}", start, end), html::timeline_bar(position(start), position(end))),
+                    (_, Some(end)) => (end.to_string(), html::timeline_bar(position(end), position(end))),
+                    _ => ("Unscheduled".to_string(), String::new()),
+                };
+                body.push_str(&format!(
+                    "<tr><td>{}<br><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape_html(item.title.as_deref().unwrap_or("Untitled")),
+                    escape_html(&item.document_id),
+                    escape_html(item.status.as_deref().unwrap_or("-")),
+                    escape_html(&item.teams.join(", ")),
+                    escape_html(item.schedule.as_deref().unwrap_or("-")),
+                    dates,
+                    bar
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+
+        body.push_str(&format!(
+            "<details>\n<summary>Mermaid source</summary>\n<pre>{}</pre>\n</details>\n",
+            escape_html(&timeline.render_gantt(title))
+        ));
+        html::page(title, &body)
+    }
+
+    pub fn format_timeline_json(timeline: &Timeline) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(timeline)
+    }
+
+    fn key_result_metrics(kr: &KeyResult) -> String {
+        match (&kr.current, &kr.target) {
+            (Some(current), Some(target)) => format!(" ({} → {})", current.raw, target.raw),
+            _ => String::new(),
+        }
+    }
+
+    fn percent(ratio: Option<f64>) -> String {
+        match ratio {
+            Some(ratio) => format!("{:.0}%", ratio * 100.0),
+            None => "-".to_string(),
+        }
+    }
+
+    fn ratio_bar(ratio: f64) -> String {
+        let filled = (ratio.clamp(0.0, 1.0) * 10.0).round() as usize;
+        format!("{}{}", "█".repeat(filled).green(), "░".repeat(10 - filled).dimmed())
+    }
+
+    fn completion(completion: Completion) -> String {
+        match completion.ratio() {
+            Some(ratio) => format!("{}/{} ({:.0}%)", completion.checked, completion.total, ratio * 100.0),
+            None => "0/0".to_string(),
+        }
+    }
+
+    fn progress_bar(completion: Completion) -> String {
+        Self::ratio_bar(completion.ratio().unwrap_or(0.0))
+    }
+
     /// Get summary statistics
     pub fn get_summary_stats(results: &[ValidationResult]) -> SummaryStats {
         let total_files = results.len();
@@ -319,4 +841,91 @@ mod tests {
         assert!(json_output.contains("test.adoc"));
         assert!(json_output.contains("story"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_format_due_items() {
+        let item = |owner: Option<&str>, file: &str, days_overdue: i64| DueItem {
+            file_path: file.to_string(),
+            document_type: "risk-register".to_string(),
+            document_id: Some(file.trim_end_matches(".adoc").to_string()),
+            owner: owner.map(String::from),
+            attribute: "next-review".to_string(),
+            line: Some(6),
+            due: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            days_overdue,
+            message: format!("Risk review: due 2024-03-01, {} days overdue", days_overdue),
+        };
+        let items = vec![
+            item(None, "orphan.adoc", 3),
+            item(Some("bob"), "billing.adoc", 2),
+            item(Some("alice"), "payments.adoc", 4),
+            item(Some("bob"), "search.adoc", 9),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+
+        let output = ResultFormatter::format_due_items(&items, today);
+        let alice = output.find("alice (1)").unwrap();
+        let bob = output.find("bob (2)").unwrap();
+        let unowned = output.find("No owner (1)").unwrap();
+        assert!(alice < bob && bob < unowned);
+        assert!(output.find("search.adoc:6").unwrap() < output.find("billing.adoc:6").unwrap());
+        assert!(output.contains("4 overdue items for 3 owners as of 2024-03-10"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ResultFormatter::format_due_items_json(&items, today).unwrap()).unwrap();
+        assert_eq!(json["owners"][1]["owner"], "bob");
+        assert_eq!(json["owners"][1]["items"][0]["file_path"], "search.adoc");
+        assert!(json["owners"][2]["owner"].is_null());
+    }
+
+    #[test]
+    fn test_format_progress() {
+        use crate::progress::{Rollup, SectionProgress};
+
+        colored::control::set_override(false);
+        let documents = vec![
+            DocumentProgress {
+                file_path: "epic.adoc".to_string(),
+                document_type: Some("epic".to_string()),
+                document_id: Some("payments".to_string()),
+                title: Some("Payments".to_string()),
+                status: Some("in-progress".to_string()),
+                parent: None,
+                completion: Completion::default(),
+                sections: Vec::new(),
+                rollup: Some(Rollup {
+                    children: 2,
+                    children_completed: 1,
+                    completion: Completion { checked: 3, total: 4 },
+                }),
+            },
+            DocumentProgress {
+                file_path: "card.adoc".to_string(),
+                document_type: Some("story".to_string()),
+                document_id: Some("card".to_string()),
+                title: Some("Card".to_string()),
+                status: Some("done".to_string()),
+                parent: Some("payments".to_string()),
+                completion: Completion { checked: 1, total: 2 },
+                sections: vec![SectionProgress {
+                    section: "Acceptance Criteria".to_string(),
+                    line: Some(8),
+                    completion: Completion { checked: 1, total: 2 },
+                }],
+                rollup: None,
+            },
+        ];
+
+        let output = ResultFormatter::format_progress(&documents);
+        assert!(output.contains("Payments [epic payments] epic.adoc (in-progress)\n"));
+        assert!(output.contains("1 of 2 children complete, 3/4 (75%) across children\n"));
+        assert!(output.contains("    Acceptance Criteria: 1/2 (50%)\n"));
+        assert!(output.ends_with("2 documents, 1 of 2 checklist items checked (50%)\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ResultFormatter::format_progress_json(&documents).unwrap()).unwrap();
+        assert_eq!(json["documents"][0]["rollup"]["children_completed"], 1);
+        assert_eq!(json["documents"][1]["sections"][0]["checked"], 1);
+        assert_eq!(json["total"], 2);
+    }
+}
@@ -0,0 +1,565 @@
+use crate::document::{ForgepointDocument, GherkinBlock};
+use crate::error::{ForgepointError, Result};
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use std::collections::HashSet;
+
+const SCENARIO_KEYWORDS: &[&str] = &["Scenario Outline", "Scenario Template", "Scenario", "Example"];
+const EXAMPLES_KEYWORDS: &[&str] = &["Examples", "Scenarios"];
+const STEP_KEYWORDS: &[&str] = &["Given", "When", "Then", "And", "But", "*"];
+
+/// A Gherkin syntax error at a document line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GherkinError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// The `Feature:` header of a block
+#[derive(Debug, Clone)]
+pub struct FeatureHeader {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Free text below the `Feature:` line
+    pub description: Vec<String>,
+}
+
+/// A parsed Gherkin block
+#[derive(Debug, Clone)]
+pub struct ParsedBlock {
+    pub feature: Option<FeatureHeader>,
+    /// Index of the first line after the feature header and its description
+    pub body_start: usize,
+    pub errors: Vec<GherkinError>,
+}
+
+/// Parse a Gherkin block, reporting syntax errors at document lines
+pub fn parse_block(block: &GherkinBlock) -> ParsedBlock {
+    let mut parser = Parser::default();
+    for (index, (line_number, line)) in block.lines.iter().enumerate() {
+        parser.line(index, *line_number, line.trim());
+    }
+    if let Some((line, delimiter)) = parser.doc_string.take() {
+        parser.error(line, format!("Doc string opened with {} is never closed", delimiter));
+    }
+    parser.finish_scenario();
+    if !block.closed {
+        parser.error(block.line_number, "Gherkin block has no closing ---- delimiter".to_string());
+    }
+    parser.errors.sort_by_key(|error| error.line);
+
+    ParsedBlock {
+        feature: parser.feature,
+        body_start: parser.body_start.unwrap_or(block.lines.len()),
+        errors: parser.errors,
+    }
+}
+
+/// Report Gherkin syntax errors in a document's `[source,gherkin]` blocks
+pub fn check_gherkin(doc: &ForgepointDocument) -> Vec<ValidationError> {
+    doc.extract_gherkin_blocks()
+        .iter()
+        .flat_map(|block| parse_block(block).errors)
+        .map(|error| ValidationError {
+            error_type: ErrorType::Format,
+            severity: Severity::Error,
+            message: format!("Gherkin syntax error: {}", error.message),
+            location: Some(Location {
+                line: Some(error.line),
+                column: None,
+                section: None,
+            }),
+            rule: Some("gherkin-syntax".to_string()),
+            suggestion: None,
+        })
+        .collect()
+}
+
+/// Render a document's Gherkin blocks as one `.feature` file, tagged with the
+/// document ID and its `tags` attribute
+pub fn render_feature(doc: &ForgepointDocument) -> Option<String> {
+    let blocks = doc.extract_gherkin_blocks();
+    if blocks.is_empty() {
+        return None;
+    }
+    let parsed: Vec<ParsedBlock> = blocks.iter().map(parse_block).collect();
+    let feature = parsed.iter().find_map(|block| block.feature.as_ref());
+    let file_path = doc.file_path.to_string_lossy();
+
+    let mut tags: Vec<String> = Vec::new();
+    let mut candidates: Vec<&str> = doc.document_id().map(String::as_str).into_iter().collect();
+    candidates.extend(doc.attributes.get("tags").map(|value| value.split(',')).into_iter().flatten());
+    candidates.extend(parsed.iter().filter_map(|block| block.feature.as_ref()).flat_map(|feature| feature.tags.iter().map(String::as_str)));
+    for candidate in candidates {
+        let words: Vec<&str> = candidate.trim().trim_start_matches('@').split_whitespace().collect();
+        let tag = format!("@{}", words.join("-"));
+        if !words.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let name = feature
+        .map(|feature| feature.name.clone())
+        .or_else(|| doc.title.clone())
+        .unwrap_or_default();
+    let mut output = format!("# Generated from {}; edit the document rather than this file\n", file_path);
+    output.push_str(&format!("{}\nFeature: {}\n", tags.join(" "), name));
+    for line in feature.map(|feature| feature.description.as_slice()).unwrap_or_default() {
+        output.push_str(&format!("  {}\n", line));
+    }
+
+    for (block, parsed) in blocks.iter().zip(&parsed) {
+        let body = &block.lines[parsed.body_start..];
+        let Some((first_line, _)) = body.iter().find(|(_, line)| !line.trim().is_empty()) else {
+            continue;
+        };
+        output.push_str(&format!("\n  # {}:{}\n", file_path, first_line));
+        let lines: Vec<&str> = body.iter().map(|(_, line)| line.as_str()).skip_while(|line| line.trim().is_empty()).collect();
+        output.push_str(lines.join("\n").trim_end());
+        output.push('\n');
+    }
+
+    Some(output)
+}
+
+#[derive(Debug, Default)]
+struct Scenario {
+    keyword: &'static str,
+    name: String,
+    line: usize,
+    steps: Vec<String>,
+    /// Step keywords used, with And and But resolved to the step they follow
+    kinds: HashSet<&'static str>,
+    /// Header cells of the Examples tables
+    columns: Vec<String>,
+    examples: usize,
+}
+
+impl Scenario {
+    fn is_background(&self) -> bool {
+        self.keyword == "Background"
+    }
+
+    fn is_outline(&self) -> bool {
+        self.keyword.starts_with("Scenario ")
+    }
+}
+
+#[derive(Debug)]
+struct Table {
+    line: usize,
+    examples: bool,
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Context {
+    /// Free text is allowed until the first step or row
+    Description,
+    Steps,
+    Examples,
+}
+
+#[derive(Default)]
+struct Parser {
+    feature: Option<FeatureHeader>,
+    body_start: Option<usize>,
+    errors: Vec<GherkinError>,
+    /// Tags seen before the Feature line, and the index of the first of them
+    feature_tags: Vec<String>,
+    feature_tags_start: Option<usize>,
+    scenario: Option<Scenario>,
+    table: Option<Table>,
+    /// Whether the current Background has a Given step
+    background_given: bool,
+    last_kind: Option<&'static str>,
+    context: Option<Context>,
+    doc_string: Option<(usize, &'static str)>,
+}
+
+impl Parser {
+    fn error(&mut self, line: usize, message: String) {
+        self.errors.push(GherkinError { line, message });
+    }
+
+    fn line(&mut self, index: usize, line_number: usize, line: &str) {
+        if let Some((_, delimiter)) = self.doc_string {
+            if line.starts_with(delimiter) {
+                self.doc_string = None;
+            }
+            return;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            return;
+        }
+        if line.starts_with('|') {
+            self.table_row(line_number, line);
+            return;
+        }
+        self.finish_table();
+
+        // Free text below the Feature line is its description
+        let description = self.context == Some(Context::Description) && self.feature.is_some() && self.body_start.is_none();
+        let keyword = line.starts_with('@')
+            || line.starts_with("\"\"\"")
+            || line.starts_with("```")
+            || step_keyword(line).is_some()
+            || line.split_once(':').is_some_and(|(keyword, _)| is_keyword(keyword));
+        if description && !keyword {
+            if let Some(feature) = &mut self.feature {
+                feature.description.push(line.to_string());
+            }
+            return;
+        }
+
+        // Tags before the Feature line belong to the feature header
+        let header = line.starts_with('@') && self.feature.is_none() && self.scenario.is_none();
+        if !header && keyword_line(line, &["Feature"]).is_none() {
+            // Without a Feature line, leading tags belong to the first scenario
+            let start = match self.feature {
+                Some(_) => index,
+                None => self.feature_tags_start.unwrap_or(index),
+            };
+            self.body_start.get_or_insert(start);
+        }
+
+        if line.starts_with('@') {
+            if header {
+                self.feature_tags_start.get_or_insert(index);
+                self.feature_tags.extend(line.split_whitespace().map(str::to_string));
+            }
+        } else if let Some(name) = keyword_line(line, &["Feature"]) {
+            if self.feature.is_some() || self.body_start.is_some() {
+                self.error(line_number, "Feature must be the first keyword of the block and appear once".to_string());
+                return;
+            }
+            self.feature = Some(FeatureHeader {
+                name: name.to_string(),
+                tags: std::mem::take(&mut self.feature_tags),
+                description: Vec::new(),
+            });
+            self.context = Some(Context::Description);
+        } else if keyword_line(line, &["Rule"]).is_some() {
+            self.finish_scenario();
+            self.background_given = false;
+            self.context = Some(Context::Description);
+        } else if let Some(keyword) = std::iter::once(&"Background")
+            .chain(SCENARIO_KEYWORDS)
+            .find(|keyword| keyword_line(line, &[**keyword]).is_some())
+        {
+            let keyword = *keyword;
+            self.finish_scenario();
+            if keyword == "Background" {
+                self.background_given = false;
+            }
+            self.scenario = Some(Scenario {
+                keyword,
+                name: keyword_line(line, &[keyword]).unwrap_or_default().to_string(),
+                line: line_number,
+                ..Scenario::default()
+            });
+            self.last_kind = None;
+            self.context = Some(Context::Description);
+        } else if keyword_line(line, EXAMPLES_KEYWORDS).is_some() {
+            match &mut self.scenario {
+                Some(scenario) if scenario.is_outline() => {
+                    scenario.examples += 1;
+                    self.table = Some(Table {
+                        line: line_number,
+                        examples: true,
+                        rows: Vec::new(),
+                    });
+                    self.context = Some(Context::Examples);
+                }
+                _ => self.error(line_number, "Examples must belong to a Scenario Outline".to_string()),
+            }
+        } else if let Some(keyword) = step_keyword(line) {
+            self.step(line_number, keyword, line);
+        } else if line.starts_with("\"\"\"") || line.starts_with("```") {
+            if self.context != Some(Context::Steps) {
+                self.error(line_number, "Doc string must follow a step".to_string());
+            }
+            self.doc_string = Some((line_number, if line.starts_with("```") { "```" } else { "\"\"\"" }));
+        } else if self.context != Some(Context::Description) {
+            self.error(
+                line_number,
+                format!("Unexpected line '{}', expected a step, a table row or a keyword", line),
+            );
+        }
+    }
+
+    fn step(&mut self, line_number: usize, keyword: &'static str, line: &str) {
+        let Some(scenario) = &mut self.scenario else {
+            self.error(line_number, "Step must belong to a Scenario or Background".to_string());
+            return;
+        };
+        if scenario.examples > 0 {
+            self.error(line_number, "Steps must come before the Examples".to_string());
+            return;
+        }
+
+        let kind = match keyword {
+            "Given" | "When" | "Then" => Some(keyword),
+            _ => self.last_kind,
+        };
+        scenario.steps.push(line.to_string());
+        match kind {
+            Some(kind) => {
+                scenario.kinds.insert(kind);
+                if scenario.is_background() && kind == "Given" {
+                    self.background_given = true;
+                }
+                self.last_kind = Some(kind);
+            }
+            None if keyword != "*" => {
+                self.error(line_number, format!("{} step must follow a Given, When or Then step", keyword));
+            }
+            None => {}
+        }
+        self.context = Some(Context::Steps);
+    }
+
+    fn table_row(&mut self, line_number: usize, line: &str) {
+        if line.len() < 2 || !line.ends_with('|') {
+            self.error(line_number, "Table row must end with |".to_string());
+            return;
+        }
+        let cells: Vec<String> = line[1..line.len() - 1].split('|').map(|cell| cell.trim().to_string()).collect();
+
+        match (&mut self.table, self.context) {
+            (Some(table), _) => table.rows.push((line_number, cells)),
+            (None, Some(Context::Steps)) => {
+                self.table = Some(Table {
+                    line: line_number,
+                    examples: false,
+                    rows: vec![(line_number, cells)],
+                })
+            }
+            _ => self.error(line_number, "Table row must follow a step or Examples".to_string()),
+        }
+    }
+
+    fn finish_table(&mut self) {
+        let Some(table) = self.table.take() else {
+            return;
+        };
+
+        if let Some((_, header)) = table.rows.first() {
+            for (line, cells) in &table.rows[1..] {
+                if cells.len() != header.len() {
+                    self.error(
+                        *line,
+                        format!("Table row has {} cells but its header has {}", cells.len(), header.len()),
+                    );
+                }
+            }
+        }
+        if table.examples {
+            match table.rows.len() {
+                0 => self.error(table.line, "Examples table has no header row".to_string()),
+                1 => self.error(table.line, "Examples table has no data rows".to_string()),
+                _ => {}
+            }
+            if let (Some(scenario), Some((_, header))) = (&mut self.scenario, table.rows.first()) {
+                scenario.columns.extend(header.iter().cloned());
+            }
+        }
+    }
+
+    fn finish_scenario(&mut self) {
+        self.finish_table();
+        let Some(scenario) = self.scenario.take() else {
+            return;
+        };
+        if scenario.is_background() {
+            return;
+        }
+
+        // A Background's Given steps count for every scenario after it
+        let background_given = self.background_given;
+        let missing: Vec<&str> = ["Given", "When", "Then"]
+            .into_iter()
+            .filter(|kind| !(scenario.kinds.contains(kind) || *kind == "Given" && background_given))
+            .collect();
+        if let Some((last, rest)) = missing.split_last() {
+            let missing = match rest {
+                [] => last.to_string(),
+                rest => format!("{} or {}", rest.join(", "), last),
+            };
+            self.error(scenario.line, format!("{} '{}' has no {} step", scenario.keyword, scenario.name, missing));
+        }
+
+        if scenario.is_outline() {
+            if scenario.examples == 0 {
+                self.error(scenario.line, format!("{} '{}' has no Examples", scenario.keyword, scenario.name));
+            } else {
+                let placeholder_regex = regex::Regex::new(r"<([^<>]+)>").unwrap();
+                let mut unknown: Vec<String> = Vec::new();
+                for step in &scenario.steps {
+                    for cap in placeholder_regex.captures_iter(step) {
+                        if !scenario.columns.contains(&cap[1].to_string()) && !unknown.contains(&cap[1].to_string()) {
+                            unknown.push(cap[1].to_string());
+                        }
+                    }
+                }
+                for placeholder in unknown {
+                    self.error(
+                        scenario.line,
+                        format!(
+                            "Placeholder <{}> in {} '{}' isn't an Examples column",
+                            placeholder, scenario.keyword, scenario.name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["Feature", "Rule", "Background"]
+        .iter()
+        .chain(SCENARIO_KEYWORDS)
+        .chain(EXAMPLES_KEYWORDS)
+        .any(|keyword| *keyword == word)
+}
+
+/// The text after `Keyword:` if the line starts with one of the keywords
+fn keyword_line<'a>(line: &'a str, keywords: &[&str]) -> Option<&'a str> {
+    keywords.iter().find_map(|keyword| {
+        line.strip_prefix(keyword)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(str::trim)
+    })
+}
+
+fn step_keyword(line: &str) -> Option<&'static str> {
+    STEP_KEYWORDS
+        .iter()
+        .find(|keyword| line.strip_prefix(**keyword).is_some_and(|rest| rest.starts_with(' ')))
+        .copied()
+}
+
+/// The `.feature` file for each document with Gherkin blocks, by document ID.
+/// Any syntax error fails the export, listing every error found
+pub fn exported_features(docs: &[ForgepointDocument]) -> Result<Vec<(String, String)>> {
+    let mut features = Vec::new();
+    let mut failures = Vec::new();
+    for doc in docs {
+        let (Some(id), Some(feature)) = (doc.document_id(), render_feature(doc)) else {
+            continue;
+        };
+        let errors = check_gherkin(doc);
+        if errors.is_empty() {
+            features.push((id.clone(), feature));
+            continue;
+        }
+        for error in errors {
+            let line = error.location.and_then(|location| location.line).unwrap_or_default();
+            failures.push(format!("{}:{}: {}", doc.file_path.display(), line, error.message));
+        }
+    }
+    if !failures.is_empty() {
+        return Err(ForgepointError::Validation(format!(
+            "Gherkin syntax errors, nothing was exported:\n{}",
+            failures.join("\n")
+        )));
+    }
+    Ok(features)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn parse(content: &str) -> ForgepointDocument {
+        DocumentParser::new()
+            .parse_content(content, PathBuf::from("login.adoc"))
+            .unwrap()
+    }
+
+    fn scenario(gherkin: &str) -> ForgepointDocument {
+        parse(&format!(
+            "= Login\n:forgepoint-type: scenario\n:id: login\n:status: draft\n:tags: smoke, @auth\n:schema-version: 1.0\n\n== Scenarios\n\n[source,gherkin]\n----\n{}----\n",
+            gherkin
+        ))
+    }
+
+    fn errors(doc: &ForgepointDocument) -> Vec<(usize, String)> {
+        check_gherkin(doc)
+            .into_iter()
+            .map(|error| (error.location.unwrap().line.unwrap(), error.message))
+            .collect()
+    }
+
+    const VALID: &str = "@web\nFeature: Login\n  Users sign in\n\n  Background:\n    Given a registered user\n\n  @happy\n  Scenario: Sign in\n    When they enter their password\n      | field    | value  |\n      | password | secret |\n    Then they see the dashboard\n\n  Scenario Outline: Lockout\n    Given <attempts> failed attempts\n    When they sign in\n    Then they see \"<message>\"\n\n    Examples:\n      | attempts | message |\n      | 5        | Locked  |\n";
+
+    #[test]
+    fn test_parse_block() {
+        let doc = scenario(VALID);
+        assert!(errors(&doc).is_empty());
+
+        let blocks = doc.extract_gherkin_blocks();
+        let parsed = parse_block(&blocks[0]);
+        let feature = parsed.feature.unwrap();
+        assert_eq!(feature.name, "Login");
+        assert_eq!(feature.tags, vec!["@web"]);
+        assert_eq!(feature.description, vec!["Users sign in"]);
+        assert_eq!(blocks[0].lines[parsed.body_start].1, "  Background:");
+    }
+
+    #[test]
+    fn test_gherkin_errors() {
+        let doc = scenario("Feature: Login\n\n  Scenario: Sign in\n    And a user\n    Given a user\n    Then they see the dashboard\n\n  Scenario Outline: Lockout\n    Given <attempts> failed attempts\n    When they sign in\n    Then they see <message>\n\n    Examples:\n      | attempts |\n      | 5        | Locked |\n      | 6\n\n  Scenario: Sign out\n    Given a user\n    they sign out\n");
+
+        assert_eq!(
+            errors(&doc),
+            vec![
+                (14, "Gherkin syntax error: Scenario 'Sign in' has no When step".to_string()),
+                (15, "Gherkin syntax error: And step must follow a Given, When or Then step".to_string()),
+                (19, "Gherkin syntax error: Placeholder <message> in Scenario Outline 'Lockout' isn't an Examples column".to_string()),
+                (26, "Gherkin syntax error: Table row has 2 cells but its header has 1".to_string()),
+                (27, "Gherkin syntax error: Table row must end with |".to_string()),
+                (29, "Gherkin syntax error: Scenario 'Sign out' has no When or Then step".to_string()),
+                (31, "Gherkin syntax error: Unexpected line 'they sign out', expected a step, a table row or a keyword".to_string()),
+            ]
+        );
+
+        let doc = scenario("Scenario Outline: Lockout\n  Given a user\n  When they sign in\n  Then they are locked out\n\n  Examples:\n    | attempts |\n");
+        assert_eq!(
+            errors(&doc),
+            vec![(17, "Gherkin syntax error: Examples table has no data rows".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_render_feature() {
+        let doc = scenario(VALID);
+        let feature = render_feature(&doc).unwrap();
+        let lines: Vec<&str> = feature.lines().collect();
+
+        assert_eq!(lines[1], "@login @smoke @auth @web");
+        assert_eq!(lines[2], "Feature: Login");
+        assert_eq!(lines[3], "  Users sign in");
+        assert_eq!(lines[5], "  # login.adoc:16");
+        assert_eq!(lines[6], "  Background:");
+        assert_eq!(lines.last(), Some(&"      | 5        | Locked  |"));
+
+        let untitled = scenario("Scenario: Sign in\n  Given a user\n  When they sign in\n  Then they see the dashboard\n");
+        let feature = render_feature(&untitled).unwrap();
+        assert!(feature.contains("@login @smoke @auth\nFeature: Login\n\n  # login.adoc:12\nScenario: Sign in\n"));
+    }
+
+    #[test]
+    fn test_exported_features() {
+        let features = exported_features(&[scenario(VALID)]).unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].0, "login");
+
+        let invalid = scenario("Scenario: Sign in\n  Given a user\n  they sign in\n");
+        let error = exported_features(&[scenario(VALID), invalid]).unwrap_err().to_string();
+        assert!(error.contains("nothing was exported"));
+        assert!(error.contains("login.adoc:14: Gherkin syntax error: Unexpected line 'they sign in'"));
+    }
+}
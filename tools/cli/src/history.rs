@@ -0,0 +1,122 @@
+use crate::error::{ForgepointError, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Reads files as they were at a git revision
+pub struct GitRevision {
+    revision: String,
+    commit: String,
+}
+
+impl GitRevision {
+    /// Resolve a revision such as a branch, tag or commit in the repository
+    /// containing `directory`
+    pub fn resolve(revision: &str, directory: &Path) -> Result<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", revision)])
+            .output()
+            .map_err(|e| ForgepointError::Git(format!("Failed to run git: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(ForgepointError::Git(format!("Unknown revision '{}'", revision)));
+        }
+
+        Ok(Self {
+            revision: revision.to_string(),
+            commit: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        })
+    }
+
+    /// The revision as given by the user
+    pub fn name(&self) -> &str {
+        &self.revision
+    }
+
+    /// A file's content at the revision, or `None` if it didn't exist then.
+    /// Fails if git can't read the revision, e.g. outside a repository
+    pub fn read_file(&self, path: &Path) -> Result<Option<String>> {
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let Some(file_name) = path.file_name() else {
+            return Ok(None);
+        };
+
+        // Paths are relative to the file's directory; listing the file first
+        // tells a file that didn't exist apart from git failing
+        let listed = run_git(directory, &["ls-tree", "--name-only", &self.commit, "--", &file_name.to_string_lossy()])?;
+        if listed.trim().is_empty() {
+            return Ok(None);
+        }
+        let content = run_git(directory, &["show", &format!("{}:./{}", self.commit, file_name.to_string_lossy())])?;
+        Ok(Some(content))
+    }
+}
+
+/// Run git in `directory` and return its output, failing with its error message
+fn run_git(directory: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .map_err(|e| ForgepointError::Git(format!("Failed to run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(ForgepointError::Git(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(directory: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_read_file_at_revision() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        fs::create_dir(repo.join("docs")).unwrap();
+        git(repo, &["init", "-q"]);
+        fs::write(repo.join("docs/prd.adoc"), ":status: draft\n").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "Add PRD"]);
+        fs::write(repo.join("docs/prd.adoc"), ":status: review\n").unwrap();
+        fs::write(repo.join("docs/new.adoc"), ":status: draft\n").unwrap();
+
+        let revision = GitRevision::resolve("HEAD", repo).unwrap();
+        assert_eq!(revision.name(), "HEAD");
+        assert_eq!(
+            revision.read_file(&repo.join("docs/prd.adoc")).unwrap().as_deref(),
+            Some(":status: draft\n")
+        );
+        assert_eq!(revision.read_file(&repo.join("docs/new.adoc")).unwrap(), None);
+
+        assert!(GitRevision::resolve("no-such-branch", repo).is_err());
+
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("prd.adoc"), ":status: draft\n").unwrap();
+        assert!(revision.read_file(&outside.path().join("prd.adoc")).is_err());
+    }
+}
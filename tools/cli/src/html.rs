@@ -0,0 +1,43 @@
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; line-height: 1.5; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+pre { background: #f6f8fa; padding: 1em; overflow-x: auto; }
+.bar { display: inline-block; width: 8em; height: 0.8em; background: #eee; vertical-align: middle; }
+.bar > span { display: block; height: 100%; background: #2da44e; }
+.track { display: block; position: relative; min-width: 16em; height: 0.8em; background: #eee; }
+.track > span { position: absolute; height: 100%; min-width: 0.4em; background: #0969da; }";
+
+/// Wrap a page body in a complete HTML document
+pub fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+/// A horizontal bar filled to `ratio`, between 0 and 1
+pub fn progress_bar(ratio: f64) -> String {
+    format!(
+        "<span class=\"bar\"><span style=\"width: {:.0}%\"></span></span>",
+        ratio.clamp(0.0, 1.0) * 100.0
+    )
+}
+
+/// A span of a timeline track, with `start` and `end` between 0 and 1
+pub fn timeline_bar(start: f64, end: f64) -> String {
+    let start = start.clamp(0.0, 1.0);
+    format!(
+        "<span class=\"track\"><span style=\"left: {:.1}%; width: {:.1}%\"></span></span>",
+        start * 100.0,
+        (end.clamp(start, 1.0) - start) * 100.0
+    )
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
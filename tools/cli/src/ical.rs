@@ -0,0 +1,245 @@
+use crate::document::ForgepointDocument;
+use crate::schema::parse_date;
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use std::fmt::Write;
+
+/// The product identifier written to exported calendars
+pub const PRODUCT_ID: &str = "-//Forgepoint//Planning Calendar//EN";
+
+/// iCalendar lines longer than this many octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// When an event happens: a whole day, or a moment in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventTime {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl EventTime {
+    /// Parse a `date` or `date-time` attribute value
+    pub fn parse(value: &str) -> Option<Self> {
+        match NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d") {
+            Ok(date) => Some(Self::Date(date)),
+            Err(_) => parse_date(value).map(Self::DateTime),
+        }
+    }
+
+    fn start(&self) -> NaiveDateTime {
+        match self {
+            Self::Date(date) => date.and_hms_opt(0, 0, 0).unwrap_or_default(),
+            Self::DateTime(moment) => *moment,
+        }
+    }
+
+    /// The property for this time, e.g. `DTSTART;VALUE=DATE:20241001`
+    fn property(&self, name: &str) -> String {
+        match self {
+            Self::Date(date) => format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")),
+            Self::DateTime(moment) => format!("{}:{}", name, moment.format("%Y%m%dT%H%M%SZ")),
+        }
+    }
+}
+
+/// A planning date taken from a document
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Derived from the document ID and the kind of date, so re-exports
+    /// update events rather than duplicating them
+    pub uid: String,
+    pub summary: String,
+    pub start: EventTime,
+    /// The last day or the end moment, if the event spans more than its start
+    pub end: Option<EventTime>,
+    pub document_type: String,
+    pub file_path: String,
+}
+
+/// The events a document contributes to the calendar
+pub fn document_events(doc: &ForgepointDocument) -> Vec<CalendarEvent> {
+    let (Some(doc_type), Some(id)) = (doc.document_type(), doc.document_id()) else {
+        return Vec::new();
+    };
+    let name = doc.title.clone().unwrap_or_else(|| id.clone());
+    let date = |attribute: &str| doc.attributes.get(attribute).and_then(|value| EventTime::parse(value));
+    let event = |kind: &str, summary: String, start: EventTime, end: Option<EventTime>| CalendarEvent {
+        uid: format!("{}-{}@forgepoint", id, kind),
+        summary,
+        start,
+        end,
+        document_type: doc_type.clone(),
+        file_path: doc.file_path.to_string_lossy().to_string(),
+    };
+
+    let mut events = Vec::new();
+    match doc_type.as_str() {
+        "sprint-plan" => {
+            if let Some(start) = date("start-date") {
+                events.push(event("sprint", format!("Sprint: {}", name), start, date("end-date")));
+            }
+        }
+        "release-notes" => {
+            if let Some(released) = date("release-date") {
+                let summary = match doc.attributes.get("version") {
+                    Some(version) => format!("Release {}: {}", version.trim(), name),
+                    None => format!("Release: {}", name),
+                };
+                events.push(event("release", summary, released, None));
+            }
+        }
+        "deployment-plan" => {
+            if let Some(scheduled) = date("scheduled-date") {
+                events.push(event("deployment", format!("Deployment: {}", name), scheduled, None));
+            }
+            if let Some((start, end)) = doc.attributes.get("maintenance-window").and_then(|value| maintenance_window(value)) {
+                events.push(event("maintenance-window", format!("Maintenance window: {}", name), start, Some(end)));
+            }
+        }
+        "risk-register" => {
+            if let Some(review) = date("next-review") {
+                events.push(event("review", format!("Risk review: {}", name), review, None));
+            }
+        }
+        "feature-flag" => {
+            let removed = doc.attributes.get("status").is_some_and(|status| status == "removed");
+            if let Some(expiry) = date("expiry-date").filter(|_| !removed) {
+                let key = doc.attributes.get("flag-key").map(|key| key.trim().to_string()).unwrap_or_else(|| name.clone());
+                events.push(event("expiry", format!("Flag expiry: {}", key), expiry, None));
+            }
+        }
+        _ => {}
+    }
+    events
+}
+
+/// Parse a `start=…, end=…` maintenance window
+fn maintenance_window(value: &str) -> Option<(EventTime, EventTime)> {
+    let mut start = None;
+    let mut end = None;
+    for pair in value.split(',') {
+        match pair.split_once('=').map(|(key, value)| (key.trim(), value)) {
+            Some(("start", value)) => start = EventTime::parse(value),
+            Some(("end", value)) => end = EventTime::parse(value),
+            _ => {}
+        }
+    }
+    Some((start?, end?))
+}
+
+/// Render events as an iCalendar file, stamped with the time of export
+pub fn render_calendar(events: &[CalendarEvent], base_url: Option<&str>, stamp: NaiveDateTime) -> String {
+    let mut events: Vec<&CalendarEvent> = events.iter().collect();
+    events.sort_by(|a, b| a.start.start().cmp(&b.start.start()).then(a.uid.cmp(&b.uid)));
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Forgepoint planning".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")));
+        lines.push(event.start.property("DTSTART"));
+        match (event.start, event.end) {
+            // All-day events end on the day after their last day
+            (EventTime::Date(_), Some(EventTime::Date(last))) => {
+                lines.push(EventTime::Date(last.checked_add_days(Days::new(1)).unwrap_or(last)).property("DTEND"))
+            }
+            (_, Some(end)) if end.start() > event.start.start() => lines.push(end.property("DTEND")),
+            _ => {}
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&format!("Document: {}", event.file_path))));
+        lines.push(format!("CATEGORIES:{}", escape_text(&event.document_type)));
+        if let Some(base_url) = base_url {
+            lines.push(format!("URL:{}/{}", base_url.trim_end_matches('/'), event.file_path.trim_start_matches("./")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = String::new();
+    for line in lines {
+        let _ = write!(calendar, "{}\r\n", fold_line(&line));
+    }
+    calendar
+}
+
+/// Escape a TEXT value
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, without splitting a character
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+    for ch in line.chars() {
+        if octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(ch);
+        octets += ch.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn events(content: &str) -> Vec<CalendarEvent> {
+        DocumentParser::new()
+            .parse_content_documents(content, PathBuf::from("docs/plan.adoc"))
+            .unwrap()
+            .iter()
+            .flat_map(document_events)
+            .collect()
+    }
+
+    #[test]
+    fn test_document_events() {
+        let sprint = events("= Core S3\n:forgepoint-type: sprint-plan\n:id: core-s3\n:start-date: 2024-10-07\n:end-date: 2024-10-18\n");
+        assert_eq!(sprint.len(), 1);
+        assert_eq!(sprint[0].uid, "core-s3-sprint@forgepoint");
+        assert_eq!(sprint[0].summary, "Sprint: Core S3");
+        assert_eq!(sprint[0].end, EventTime::parse("2024-10-18"));
+
+        let deployment = events(
+            "= Cutover\n:forgepoint-type: deployment-plan\n:id: cutover\n:scheduled-date: 2024-11-02T22:00:00+01:00\n:maintenance-window: start=2024-11-02T21:00:00Z, end=2024-11-03T01:00:00Z\n",
+        );
+        let uids: Vec<&str> = deployment.iter().map(|event| event.uid.as_str()).collect();
+        assert_eq!(uids, vec!["cutover-deployment@forgepoint", "cutover-maintenance-window@forgepoint"]);
+        assert_eq!(deployment[0].start.property("DTSTART"), "DTSTART:20241102T210000Z");
+
+        let removed = events("= Old\n:forgepoint-type: feature-flag\n:id: old\n:status: removed\n:expiry-date: 2024-12-01\n");
+        assert!(removed.is_empty());
+        assert!(events("= Untyped\n:start-date: 2024-10-07\n").is_empty());
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let mut all = events("= Checkout, v2\n:forgepoint-type: release-notes\n:id: checkout-v2\n:version: 2.0.0\n:release-date: 2024-11-04\n");
+        all.extend(events("= Core S3\n:forgepoint-type: sprint-plan\n:id: core-s3\n:start-date: 2024-10-07\n:end-date: 2024-10-18\n"));
+        let stamp = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+
+        let calendar = render_calendar(&all, Some("https://git.example.com/plans/blob/main/"), stamp);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.find("UID:core-s3-sprint").unwrap() < calendar.find("UID:checkout-v2-release").unwrap());
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20241007\r\nDTEND;VALUE=DATE:20241019\r\n"));
+        assert!(calendar.contains("SUMMARY:Release 2.0.0: Checkout\\, v2\r\n"));
+        assert!(calendar.contains("DTSTAMP:20241001T093000Z\r\n"));
+        assert!(calendar.contains("URL:https://git.example.com/plans/blob/main/docs/plan.adoc\r\n"));
+        assert!(calendar.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+    }
+}
@@ -0,0 +1,528 @@
+use crate::document::ForgepointDocument;
+use crate::error::{ForgepointError, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Sections of a test-results document that an import writes
+pub const GENERATED_SECTIONS: &[&str] = &["Executive Summary", "Test Results Summary", "Detailed Results", "Defects Found"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Passed,
+    /// A failure or an error
+    Failed,
+    Skipped,
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Passed => "Passed",
+            Outcome::Failed => "Failed",
+            Outcome::Skipped => "Skipped",
+        }
+    }
+}
+
+/// A `<testcase>` of a JUnit report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseResult {
+    pub name: String,
+    pub classname: Option<String>,
+    pub time: Option<f64>,
+    pub outcome: Outcome,
+    /// The failure, error or skip message
+    pub message: Option<String>,
+    /// Values of the test case's `<property>` elements, such as a test-case ID or tags
+    pub properties: Vec<String>,
+}
+
+impl TestCaseResult {
+    pub fn full_name(&self) -> String {
+        match &self.classname {
+            Some(classname) if !classname.is_empty() => format!("{}.{}", classname, self.name),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+/// A JUnit or xUnit XML report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JunitReport {
+    pub name: Option<String>,
+    /// Date of the first suite's `timestamp`
+    pub timestamp: Option<NaiveDate>,
+    pub cases: Vec<TestCaseResult>,
+}
+
+impl JunitReport {
+    pub fn parse(xml: &str) -> Result<Self> {
+        let document = roxmltree::Document::parse(xml)
+            .map_err(|e| ForgepointError::Parsing(format!("Invalid JUnit XML: {}", e)))?;
+        let root = document.root_element();
+        if !matches!(root.tag_name().name(), "testsuites" | "testsuite") {
+            return Err(ForgepointError::Parsing(format!(
+                "Expected a <testsuites> or <testsuite> root element, found <{}>",
+                root.tag_name().name()
+            )));
+        }
+
+        let suites = || root.descendants().filter(|node| node.has_tag_name("testsuite"));
+        let cases = root
+            .descendants()
+            .filter(|node| node.has_tag_name("testcase"))
+            .map(|case| {
+                let child = |tag: &str| case.children().find(|node| node.has_tag_name(tag));
+                let (outcome, message) = match (child("failure").or_else(|| child("error")), child("skipped")) {
+                    (Some(failure), _) => (Outcome::Failed, message_of(failure)),
+                    (None, Some(skipped)) => (Outcome::Skipped, message_of(skipped)),
+                    (None, None) => (Outcome::Passed, None),
+                };
+
+                TestCaseResult {
+                    name: case.attribute("name").unwrap_or_default().to_string(),
+                    classname: case.attribute("classname").map(str::to_string),
+                    time: case.attribute("time").and_then(|time| time.trim().parse().ok()),
+                    outcome,
+                    message,
+                    properties: case
+                        .descendants()
+                        .filter(|node| node.has_tag_name("property"))
+                        .filter_map(|property| property.attribute("value").or_else(|| property.text()))
+                        .map(|value| value.trim().to_string())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            name: root
+                .attribute("name")
+                .or_else(|| suites().find_map(|suite| suite.attribute("name")))
+                .map(str::to_string),
+            timestamp: std::iter::once(root)
+                .chain(suites())
+                .filter_map(|node| node.attribute("timestamp"))
+                .find_map(|timestamp| NaiveDate::parse_from_str(timestamp.get(..10)?, "%Y-%m-%d").ok()),
+            cases,
+        })
+    }
+}
+
+/// The `message` attribute of a failure, error or skip, or else the first line of its text
+fn message_of(node: roxmltree::Node) -> Option<String> {
+    node.attribute("message")
+        .or_else(|| node.text().and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty())))
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+}
+
+/// Test counts by outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultsSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+impl ResultsSummary {
+    pub fn of(cases: &[TestCaseResult]) -> Self {
+        let count = |outcome| cases.iter().filter(|case| case.outcome == outcome).count();
+        Self {
+            total: cases.len(),
+            passed: count(Outcome::Passed),
+            failed: count(Outcome::Failed),
+            skipped: count(Outcome::Skipped),
+        }
+    }
+
+    /// Passed tests as a percentage of the tests that ran, to one decimal place
+    pub fn pass_rate(&self) -> Option<f64> {
+        let ran = self.passed + self.failed;
+        (ran > 0).then(|| (self.passed as f64 * 1000.0 / ran as f64).round() / 10.0)
+    }
+
+    /// The `summary` attribute value
+    pub fn attribute(&self) -> String {
+        format!(
+            "total={}, passed={}, failed={}, skipped={}",
+            self.total, self.passed, self.failed, self.skipped
+        )
+    }
+}
+
+/// A test-case document that report test cases can be mapped to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCaseDocument {
+    pub document_id: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl TestCaseDocument {
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        if doc.document_type().map(String::as_str) != Some("test-case") {
+            return None;
+        }
+
+        Some(Self {
+            document_id: doc.document_id()?.clone(),
+            title: doc.title.clone(),
+            tags: doc
+                .attributes
+                .get("tags")
+                .map(|tags| tags.split(',').map(normalize).filter(|tag| !tag.is_empty()).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Find the test-case document a test case exercises: the longest test-case
+/// ID in its name, class name or properties, or else a tag that only one
+/// test-case document carries
+pub fn match_test_case<'a>(case: &TestCaseResult, documents: &'a [TestCaseDocument]) -> Option<&'a TestCaseDocument> {
+    let texts: Vec<String> = std::iter::once(&case.name)
+        .chain(&case.classname)
+        .chain(&case.properties)
+        .map(|text| normalize(text))
+        .collect();
+    let mentions = |token: &str| texts.iter().any(|text| contains_token(text, token));
+
+    documents
+        .iter()
+        .filter(|doc| mentions(&doc.document_id))
+        .max_by_key(|doc| doc.document_id.len())
+        .or_else(|| {
+            documents.iter().find(|doc| {
+                doc.tags.iter().any(|tag| {
+                    mentions(tag) && documents.iter().filter(|other| other.tags.contains(tag)).count() == 1
+                })
+            })
+        })
+}
+
+/// Lowercase text with every run of other characters than letters and digits
+/// turned into a single hyphen, so `LoginTest.test_valid_login` reads
+/// `logintest-test-valid-login`
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Whether `token` appears in `text` between hyphens or the ends of the text
+fn contains_token(text: &str, token: &str) -> bool {
+    let token = normalize(token);
+    !token.is_empty()
+        && text.match_indices(&token).any(|(start, _)| {
+            let end = start + token.len();
+            (start == 0 || text[..start].ends_with('-')) && (end == text.len() || text[end..].starts_with('-'))
+        })
+}
+
+/// The attributes and sections of a test-results document generated from a report
+#[derive(Debug, Clone)]
+pub struct TestResultsContent {
+    pub attributes: Vec<(String, String)>,
+    pub sections: Vec<(String, String)>,
+}
+
+impl TestResultsContent {
+    /// Generate content from a report, with each test case's matching
+    /// test-case document; `attributes` are set after `test-plan`
+    pub fn from_report(
+        report: &JunitReport,
+        source: &str,
+        test_plan: &str,
+        matches: &[Option<&TestCaseDocument>],
+        attributes: Vec<(String, String)>,
+        today: NaiveDate,
+    ) -> Self {
+        let summary = ResultsSummary::of(&report.cases);
+        let execution_date = report.timestamp.unwrap_or(today);
+        let pass_rate = summary.pass_rate();
+
+        let mut all_attributes = vec![("test-plan".to_string(), test_plan.to_string())];
+        all_attributes.extend(attributes);
+        all_attributes.push(("execution-date".to_string(), execution_date.to_string()));
+        all_attributes.push(("summary".to_string(), summary.attribute()));
+        if let Some(pass_rate) = pass_rate {
+            all_attributes.push(("pass-rate".to_string(), pass_rate.to_string()));
+        }
+        all_attributes.push(("defects-found".to_string(), summary.failed.to_string()));
+        all_attributes.push(("updated".to_string(), today.to_string()));
+
+        let test_case = |index: usize| match matches.get(index).copied().flatten() {
+            Some(doc) => format!("xref:test-case:{}[]", doc.document_id),
+            None => "-".to_string(),
+        };
+
+        let mut executive = format!(
+            "The {} report{} ran {} tests on {}: {} passed, {} failed and {} were skipped",
+            source,
+            report.name.as_ref().map(|name| format!(" for {}", name)).unwrap_or_default(),
+            summary.total,
+            execution_date,
+            summary.passed,
+            summary.failed,
+            summary.skipped
+        );
+        match pass_rate {
+            Some(pass_rate) => executive.push_str(&format!(", a pass rate of {}%.", pass_rate)),
+            None => executive.push('.'),
+        }
+        if summary.failed > 0 {
+            executive.push_str(" The failures are listed under Defects Found.");
+        }
+        let unmatched = report.cases.len() - matches.iter().filter(|doc| doc.is_some()).count();
+        if unmatched > 0 {
+            executive.push_str(&format!(
+                "\n\n{} test{} didn't map to a test-case document.",
+                unmatched,
+                if unmatched == 1 { "" } else { "s" }
+            ));
+        }
+
+        let mut results_summary = String::from("|===\n| Result | Tests\n\n");
+        for (label, count) in [
+            ("Passed", summary.passed),
+            ("Failed", summary.failed),
+            ("Skipped", summary.skipped),
+            ("Total", summary.total),
+        ] {
+            results_summary.push_str(&format!("| {} | {}\n", label, count));
+        }
+        results_summary.push_str("|===");
+
+        let mut detailed = String::from("|===\n| Test | Test Case | Result | Time (s)\n\n");
+        for (index, case) in report.cases.iter().enumerate() {
+            detailed.push_str(&format!(
+                "| {} | {} | {} | {}\n",
+                escape_cell(&case.full_name()),
+                test_case(index),
+                case.outcome.label(),
+                case.time.map(|time| format!("{:.3}", time)).unwrap_or_default()
+            ));
+        }
+        detailed.push_str("|===");
+
+        let defects: Vec<String> = report
+            .cases
+            .iter()
+            .enumerate()
+            .filter(|(_, case)| case.outcome == Outcome::Failed)
+            .map(|(index, case)| {
+                let mut defect = format!("* `{}`", case.full_name());
+                if matches.get(index).copied().flatten().is_some() {
+                    defect.push_str(&format!(" ({})", test_case(index)));
+                }
+                if let Some(message) = &case.message {
+                    defect.push_str(&format!(": {}", message));
+                }
+                defect
+            })
+            .collect();
+        let defects = match defects.is_empty() {
+            true => "No defects found.".to_string(),
+            false => defects.join("\n"),
+        };
+
+        Self {
+            attributes: all_attributes,
+            sections: GENERATED_SECTIONS
+                .iter()
+                .map(|title| title.to_string())
+                .zip([executive, results_summary, detailed, defects])
+                .collect(),
+        }
+    }
+
+    /// Render a new test-results document
+    pub fn render(&self, id: &str, title: &str, today: NaiveDate) -> String {
+        let mut output = format!("= {}\n:forgepoint-type: test-results\n:id: {}\n:status: completed\n", title, id);
+        for (name, value) in self.attributes.iter().filter(|(name, _)| name != "updated") {
+            output.push_str(&format!(":{}: {}\n", name, value));
+        }
+        output.push_str(&format!(":created: {}\n:schema-version: 1.0\n", today));
+
+        for (title, body) in &self.sections {
+            output.push_str(&format!("\n== {}\n\n{}\n", title, body));
+        }
+        output
+    }
+
+    /// Rewrite an existing test-results document, replacing the generated
+    /// attributes and sections and keeping everything else
+    pub fn apply(&self, content: &str) -> String {
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+        // The header runs from the title to the first blank line
+        let mut header_end = lines.iter().position(|line| line.trim().is_empty()).unwrap_or(lines.len());
+        for (name, value) in &self.attributes {
+            let prefix = format!(":{}:", name);
+            let line = format!("{} {}", prefix, value);
+            match lines[..header_end].iter().position(|existing| existing.starts_with(&prefix)) {
+                Some(index) => lines[index] = line,
+                None => {
+                    let index = lines[..header_end]
+                        .iter()
+                        .position(|existing| existing.starts_with(":schema-version:"))
+                        .unwrap_or(header_end);
+                    lines.insert(index, line);
+                    header_end += 1;
+                }
+            }
+        }
+
+        for (title, body) in &self.sections {
+            let heading = format!("== {}", title);
+            let body_lines = std::iter::once(String::new())
+                .chain(body.lines().map(str::to_string))
+                .chain(std::iter::once(String::new()));
+            match lines.iter().position(|line| line.trim_end() == heading) {
+                Some(start) => {
+                    let end = lines[start + 1..]
+                        .iter()
+                        .position(|line| is_heading(line))
+                        .map(|offset| start + 1 + offset)
+                        .unwrap_or(lines.len());
+                    lines.splice(start + 1..end, body_lines);
+                }
+                None => {
+                    if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                        lines.push(String::new());
+                    }
+                    lines.push(heading);
+                    lines.extend(body_lines);
+                }
+            }
+        }
+
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Whether a line is a level-1 or level-2 section heading
+fn is_heading(line: &str) -> bool {
+    line.starts_with("= ") || line.starts_with("== ")
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="checkout">
+  <testsuite name="LoginTest" timestamp="2024-05-02T10:15:00">
+    <testcase classname="LoginTest" name="test_login_valid_credentials" time="0.12"/>
+    <testcase classname="LoginTest" name="rejects wrong password" time="0.3">
+      <failure message="expected 401, got 500">stack trace</failure>
+    </testcase>
+    <testcase classname="LoginTest" name="locks account">
+      <properties><property name="tags" value="TC-42"/></properties>
+      <error>NullPointerException
+  at Login.java:12</error>
+    </testcase>
+    <testcase classname="SignupTest" name="sends email">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+    fn test_cases() -> Vec<TestCaseDocument> {
+        let doc = |id: &str, tags: &[&str]| TestCaseDocument {
+            document_id: id.to_string(),
+            title: None,
+            tags: tags.iter().map(|tag| normalize(tag)).collect(),
+        };
+        vec![
+            doc("login", &["smoke"]),
+            doc("login-valid-credentials", &["smoke"]),
+            doc("account-lockout", &["@TC-42"]),
+            doc("wrong-password", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let report = JunitReport::parse(REPORT).unwrap();
+
+        assert_eq!(report.name.as_deref(), Some("checkout"));
+        assert_eq!(report.timestamp, NaiveDate::from_ymd_opt(2024, 5, 2));
+        let outcomes: Vec<_> = report.cases.iter().map(|case| (case.outcome, case.message.as_deref())).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (Outcome::Passed, None),
+                (Outcome::Failed, Some("expected 401, got 500")),
+                (Outcome::Failed, Some("NullPointerException")),
+                (Outcome::Skipped, None),
+            ]
+        );
+        assert_eq!(report.cases[2].properties, vec!["TC-42"]);
+
+        let summary = ResultsSummary::of(&report.cases);
+        assert_eq!(summary.attribute(), "total=4, passed=1, failed=2, skipped=1");
+        assert_eq!(summary.pass_rate(), Some(33.3));
+
+        assert!(JunitReport::parse("<results/>").is_err());
+        assert!(JunitReport::parse("<testsuite>").is_err());
+    }
+
+    #[test]
+    fn test_match_test_case() {
+        let report = JunitReport::parse(REPORT).unwrap();
+        let documents = test_cases();
+        let matched: Vec<Option<&str>> = report
+            .cases
+            .iter()
+            .map(|case| match_test_case(case, &documents).map(|doc| doc.document_id.as_str()))
+            .collect();
+
+        assert_eq!(
+            matched,
+            vec![Some("login-valid-credentials"), Some("wrong-password"), Some("account-lockout"), None]
+        );
+    }
+
+    #[test]
+    fn test_generate_and_update() {
+        let report = JunitReport::parse(REPORT).unwrap();
+        let documents = test_cases();
+        let matches: Vec<_> = report.cases.iter().map(|case| match_test_case(case, &documents)).collect();
+        let today = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+        let content = TestResultsContent::from_report(
+            &report,
+            "results.xml",
+            "checkout-plan",
+            &matches,
+            vec![("executor".to_string(), "ci".to_string())],
+            today,
+        );
+
+        let document = content.render("checkout-run-1", "Checkout Test Results", today);
+        assert!(document.starts_with("= Checkout Test Results\n:forgepoint-type: test-results\n:id: checkout-run-1\n:status: completed\n:test-plan: checkout-plan\n:executor: ci\n:execution-date: 2024-05-02\n:summary: total=4, passed=1, failed=2, skipped=1\n:pass-rate: 33.3\n:defects-found: 2\n:created: 2024-05-03\n:schema-version: 1.0\n"));
+        assert!(document.contains("| LoginTest.test_login_valid_credentials | xref:test-case:login-valid-credentials[] | Passed | 0.120\n"));
+        assert!(document.contains("* `LoginTest.rejects wrong password` (xref:test-case:wrong-password[]): expected 401, got 500\n"));
+        assert!(document.contains("1 test didn't map to a test-case document."));
+
+        let existing = "= Checkout Test Results\n:forgepoint-type: test-results\n:id: checkout-run-1\n:status: in-progress\n:test-plan: checkout-plan\n:version: 2.1\n:defects-found: 0\n:schema-version: 1.0\n\n== Executive Summary\n\nOld summary\n\n=== Detail\n\nOld detail\n\n== Recommendations\n\nShip it\n";
+        let updated = content.apply(existing);
+        assert!(updated.starts_with("= Checkout Test Results\n:forgepoint-type: test-results\n:id: checkout-run-1\n:status: in-progress\n:test-plan: checkout-plan\n:version: 2.1\n:defects-found: 2\n:executor: ci\n:execution-date: 2024-05-02\n"));
+        assert!(updated.contains(":updated: 2024-05-03\n:schema-version: 1.0\n\n== Executive Summary\n\nThe results.xml report for checkout ran 4 tests"));
+        assert!(!updated.contains("Old"));
+        assert!(updated.contains("== Recommendations\n\nShip it\n\n== Test Results Summary\n"));
+        assert!(updated.ends_with("got 500\n* `LoginTest.locks account` (xref:test-case:account-lockout[]): NullPointerException\n"));
+    }
+}
@@ -17,7 +17,7 @@ pub struct ForgepointLinter {
 
 impl ForgepointLinter {
     pub fn new(config: ForgepointConfig) -> Self {
-        let schema_loader = SchemaLoader::new(&config.schema_path);
+        let schema_loader = SchemaLoader::from_source(config.schema_source());
         let parser = DocumentParser::new();
 
         Self {
@@ -39,19 +39,19 @@ impl ForgepointLinter {
             return Ok(Vec::new());
         }
 
-        let schema_loader_clone = SchemaLoader::new(&self.config.schema_path);
+        let schema_loader_clone = SchemaLoader::from_source(self.config.schema_source());
         let validator = Mutex::new(DocumentValidator::new(schema_loader_clone));
         
         // First pass: Parse and validate individual documents
         let results: Vec<ValidationResult> = files
             .par_iter()
-            .map(|file_path| {
-                match self.parser.parse_file(file_path) {
-                    Ok(doc) => {
+            .flat_map(|file_path| {
+                match self.parser.parse_file_documents(file_path) {
+                    Ok(docs) => {
                         let mut validator = validator.lock().unwrap();
-                        validator.validate_document(&doc)
+                        docs.iter().map(|doc| validator.validate_document(doc)).collect()
                     }
-                    Err(e) => self.create_parse_error_result(file_path, &e),
+                    Err(e) => vec![self.create_parse_error_result(file_path, &e)],
                 }
             })
             .collect();
@@ -81,7 +81,7 @@ impl ForgepointLinter {
     pub fn lint_file(&self, file_path: &PathBuf) -> Result<ValidationResult> {
         match self.parser.parse_file(file_path) {
             Ok(doc) => {
-                let schema_loader_clone = SchemaLoader::new(&self.config.schema_path);
+                let schema_loader_clone = SchemaLoader::from_source(self.config.schema_source());
                 let mut validator = DocumentValidator::new(schema_loader_clone);
                 Ok(validator.validate_document(&doc))
             }
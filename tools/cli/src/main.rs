@@ -5,10 +5,28 @@ use std::path::PathBuf;
 mod cli;
 mod config;
 mod document;
+mod due;
+mod embedded;
+mod flags;
+mod gherkin;
+mod history;
+mod html;
+mod ical;
+mod junit;
 mod linter;
+mod migration;
+mod okr;
+mod openapi;
 mod parser;
+mod progress;
 mod schema;
+mod schema_check;
+mod schema_docs;
+mod sprint;
+mod suggest;
+mod timeline;
 mod validator;
+mod wsjf;
 mod formatter;
 mod error;
 
@@ -21,8 +39,18 @@ use cli::*;
     version = "0.1.0",
     long_about = "Forgepoint CLI validates AsciiDoc documents against comprehensive schemas covering the entire SDLC - from PRFAQs to retrospectives."
 )]
+pub struct Arguments {
+    #[command(flatten)]
+    pub cli: Cli,
+
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+/// Options shared by every command
+#[derive(clap::Args)]
 pub struct Cli {
-    /// Path to schema directory
+    /// Path to a schema directory to use instead of the built-in schemas
     #[arg(long, global = true, env = "FORGEPOINT_SCHEMA_PATH")]
     pub schema_path: Option<PathBuf>,
 
@@ -33,9 +61,6 @@ pub struct Cli {
     /// Enable verbose output
     #[arg(short, long, global = true)]
     pub verbose: bool,
-
-    #[command(subcommand)]
-    pub command: Commands,
 }
 
 #[derive(Subcommand)]
@@ -69,6 +94,128 @@ pub enum Commands {
         /// Treat warnings as failures
         #[arg(long)]
         fail_on_warnings: bool,
+
+        /// Warn about overdue reviews, expired flags and stale documents
+        #[arg(long)]
+        due: bool,
+
+        /// Check status changes against the documents at this git revision
+        #[arg(long, value_name = "GIT_REV")]
+        against: Option<String>,
+    },
+
+    /// Report overdue reviews, expired flags and stale documents by owner
+    Due {
+        /// File patterns to check
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+
+        /// Only report documents with this owner
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Report what is due as of this date (default: today)
+        #[arg(long)]
+        as_of: Option<chrono::NaiveDate>,
+    },
+
+    /// Report checklist completion per document and section, rolled up to
+    /// parents such as epics
+    Progress {
+        /// File patterns to report on
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+
+        /// Only report documents of this type
+        #[arg(long = "type")]
+        document_type: Option<String>,
+    },
+
+    /// Report OKR progress from key-result metrics, rolled up by level and period
+    Okr {
+        /// File patterns to report on
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: OkrFormat,
+
+        /// Only report this period, e.g. 2024-Q1
+        #[arg(long)]
+        period: Option<String>,
+    },
+
+    /// Reconcile sprint plans with the stories and tasks assigned to them
+    Sprint {
+        /// File patterns to report on
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+
+        /// Only report the sprint plan with this ID
+        #[arg(long)]
+        sprint: Option<String>,
+    },
+
+    /// Print the prioritised backlog of a document type per Agile Release Train
+    Rank {
+        /// Document type to rank, e.g. safe-feature
+        document_type: String,
+
+        /// File patterns to rank
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Score to rank by
+        #[arg(long, value_enum, default_value = "wsjf")]
+        by: RankBy,
+
+        /// Only rank documents in this Program Increment
+        #[arg(long)]
+        pi: Option<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+
+    /// Render roadmaps, epics and pitches on a timeline
+    Timeline {
+        /// File patterns to include
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "mermaid")]
+        format: TimelineFormat,
+
+        /// Only include roadmaps for this product
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Only include items of this team
+        #[arg(long)]
+        team: Option<String>,
+
+        /// Only include items with this status
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// Create a new document from template
@@ -109,6 +256,43 @@ pub enum Commands {
         example: bool,
     },
 
+    /// Migrate documents to another schema version
+    Migrate {
+        /// File patterns to migrate
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Target schema version
+        #[arg(long)]
+        to: String,
+
+        /// Only migrate documents of this type
+        #[arg(long = "type")]
+        document_type: Option<String>,
+
+        /// Show the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Work with the schema pack
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+
+    /// Export documents to formats other tools consume
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+
+    /// Import reports from other tools into documents
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
     /// Show configuration
     Config {
         /// Show resolved configuration
@@ -117,6 +301,124 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum SchemaCommands {
+    /// Write the built-in schemas to a directory for customisation
+    Export {
+        /// Directory to write the schemas to
+        dir: PathBuf,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check the schema set itself for mistakes
+    Check {
+        /// Schema directory to check (default: the configured schemas)
+        dir: Option<PathBuf>,
+    },
+
+    /// Generate reference documentation for every document type
+    Docs {
+        /// Output format
+        #[arg(long, value_enum, default_value = "md")]
+        format: DocsFormat,
+
+        /// Directory to write the pages to
+        #[arg(short, long, default_value = "schema-docs")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Write the Gherkin blocks of each document to a .feature file
+    Gherkin {
+        /// Directory to write the feature files to
+        dir: PathBuf,
+
+        /// File patterns to export
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Write feature-flag documents as flag configuration
+    Flags {
+        /// File patterns to export
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// Configuration format
+        #[arg(short, long, value_enum, default_value = "flagd")]
+        format: FlagFormat,
+
+        /// Only export flags active in this environment
+        #[arg(long)]
+        environment: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Write sprint, release, deployment, review and flag expiry dates as an iCalendar file
+    Ical {
+        /// File patterns to export
+        #[arg(default_values = &["**/*.adoc"])]
+        patterns: Vec<String>,
+
+        /// URL that document paths are appended to for each event's link
+        #[arg(long)]
+        base_url: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ImportCommands {
+    /// Generate or update a test-results document from a JUnit XML report
+    Junit {
+        /// JUnit or xUnit XML report
+        report: PathBuf,
+
+        /// ID of the test plan the results belong to
+        #[arg(long)]
+        test_plan: String,
+
+        /// ID of the test-results document to generate or update
+        #[arg(long)]
+        id: String,
+
+        /// Document title (default: "<test plan> Test Results")
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Version tested
+        #[arg(long = "tested-version")]
+        version: Option<String>,
+
+        /// Test environment
+        #[arg(long)]
+        environment: Option<String>,
+
+        /// Test executor
+        #[arg(long)]
+        executor: Option<String>,
+
+        /// Output file for a new document (default: <id>.adoc)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(clap::ValueEnum, Clone, Copy)]
 pub enum OutputFormat {
     Text,
@@ -124,9 +426,53 @@ pub enum OutputFormat {
     Junit,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum RankBy {
+    /// Weighted Shortest Job First
+    Wsjf,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum FlagFormat {
+    /// OpenFeature flagd flag definitions
+    Flagd,
+    /// The flag definitions as JSON
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum TimelineFormat {
+    /// A Mermaid gantt chart
+    Mermaid,
+    /// A standalone HTML page
+    Html,
+    /// The timeline as JSON
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum OkrFormat {
+    Text,
+    Json,
+    Html,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum DocsFormat {
+    Adoc,
+    Md,
+    Html,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let Arguments { cli, command } = Arguments::parse();
     
     // Set up logging based on verbosity
     if cli.verbose {
@@ -135,7 +481,7 @@ async fn main() -> Result<()> {
             .init();
     }
 
-    match cli.command {
+    match command {
         Commands::Lint {
             patterns,
             format,
@@ -144,6 +490,8 @@ async fn main() -> Result<()> {
             no_check_ids,
             no_check_refs,
             fail_on_warnings,
+            due,
+            against,
         } => {
             lint_command(LintArgs {
                 cli,
@@ -154,6 +502,64 @@ async fn main() -> Result<()> {
                 no_check_ids,
                 no_check_refs,
                 fail_on_warnings,
+                due,
+                against,
+            })
+            .await
+        }
+        Commands::Due {
+            patterns,
+            format,
+            owner,
+            as_of,
+        } => {
+            due_command(DueArgs {
+                cli,
+                patterns,
+                format,
+                owner,
+                as_of,
+            })
+            .await
+        }
+        Commands::Progress {
+            patterns,
+            format,
+            document_type,
+        } => progress_command(cli, patterns, format, document_type).await,
+        Commands::Okr {
+            patterns,
+            format,
+            period,
+        } => okr_command(cli, patterns, format, period).await,
+        Commands::Sprint {
+            patterns,
+            format,
+            sprint,
+        } => sprint_command(cli, patterns, format, sprint).await,
+        Commands::Rank {
+            document_type,
+            patterns,
+            by,
+            pi,
+            format,
+        } => rank_command(cli, document_type, patterns, by, pi, format).await,
+        Commands::Timeline {
+            patterns,
+            format,
+            product,
+            team,
+            status,
+            output,
+        } => {
+            timeline_command(TimelineArgs {
+                cli,
+                patterns,
+                format,
+                product,
+                team,
+                status,
+                output,
             })
             .await
         }
@@ -177,6 +583,65 @@ async fn main() -> Result<()> {
         Commands::ListTypes => list_types_command(cli).await,
         Commands::Check { file } => check_command(cli, file).await,
         Commands::Init { example } => init_command(cli, example).await,
+        Commands::Migrate {
+            patterns,
+            to,
+            document_type,
+            dry_run,
+        } => {
+            migrate_command(MigrateArgs {
+                cli,
+                patterns,
+                to,
+                document_type,
+                dry_run,
+            })
+            .await
+        }
+        Commands::Schema { command } => match command {
+            SchemaCommands::Export { dir, force } => schema_export_command(dir, force).await,
+            SchemaCommands::Check { dir } => schema_check_command(cli, dir).await,
+            SchemaCommands::Docs { format, output } => schema_docs_command(cli, format, output).await,
+        },
+        Commands::Export { command } => match command {
+            ExportCommands::Gherkin { dir, patterns, force } => export_gherkin_command(cli, dir, patterns, force).await,
+            ExportCommands::Flags {
+                patterns,
+                format,
+                environment,
+                output,
+            } => export_flags_command(cli, patterns, format, environment, output).await,
+            ExportCommands::Ical {
+                patterns,
+                base_url,
+                output,
+            } => export_ical_command(cli, patterns, base_url, output).await,
+        },
+        Commands::Import { command } => match command {
+            ImportCommands::Junit {
+                report,
+                test_plan,
+                id,
+                title,
+                version,
+                environment,
+                executor,
+                output,
+            } => {
+                import_junit_command(ImportJunitArgs {
+                    cli,
+                    report,
+                    test_plan,
+                    id,
+                    title,
+                    version,
+                    environment,
+                    executor,
+                    output,
+                })
+                .await
+            }
+        },
         Commands::Config { show } => config_command(cli, show).await,
     }
 }
\ No newline at end of file
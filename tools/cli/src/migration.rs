@@ -0,0 +1,384 @@
+use crate::document::{AttributeLocation, ForgepointDocument};
+use crate::error::{ForgepointError, Result};
+use crate::schema::SchemaSource;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// A declarative migration of one document type between two schema versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Migration {
+    #[serde(rename = "documentType")]
+    pub document_type: String,
+    pub from: String,
+    pub to: String,
+    pub operations: Vec<MigrationOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum MigrationOperation {
+    /// Rename a header attribute, keeping its value
+    RenameAttribute { from: String, to: String },
+    /// Replace attribute values according to a mapping
+    MapValues {
+        attribute: String,
+        values: HashMap<String, String>,
+    },
+    /// Rename a section heading
+    RenameSection { from: String, to: String },
+}
+
+/// A line rewritten by a migration
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChange {
+    pub line: usize,
+    pub old: String,
+    pub new: String,
+}
+
+/// The migrations found in a schema directory's `migrations` folder
+#[derive(Debug, Default)]
+pub struct MigrationSet {
+    migrations: Vec<Migration>,
+}
+
+impl MigrationSet {
+    /// Load every migration file from the schema directory's `migrations` folder
+    pub fn load(source: &SchemaSource) -> Result<Self> {
+        let mut migrations = Vec::new();
+        for path in source.list(Path::new("migrations")) {
+            let content = source.read(&path)?;
+            let migration: Migration = serde_json::from_str(&content).map_err(|e| {
+                ForgepointError::Schema(format!("Invalid migration {}: {}", source.display(&path), e))
+            })?;
+            migrations.push(migration);
+        }
+
+        Ok(Self { migrations })
+    }
+
+    /// Find the shortest chain of migrations taking a document type from one
+    /// schema version to another
+    pub fn plan(&self, doc_type: &str, from: &str, to: &str) -> Option<Vec<&Migration>> {
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, Vec::new())]);
+
+        while let Some((version, steps)) = queue.pop_front() {
+            if version == to {
+                return Some(steps);
+            }
+
+            for migration in self
+                .migrations
+                .iter()
+                .filter(|m| m.document_type == doc_type && m.from == version)
+            {
+                if visited.insert(migration.to.as_str()) {
+                    let mut next_steps = steps.clone();
+                    next_steps.push(migration);
+                    queue.push_back((migration.to.as_str(), next_steps));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Apply a chain of migrations to a parsed document, rewriting `lines` (the
+/// file the document came from) in place and returning the changed lines
+pub fn migrate_document(doc: &ForgepointDocument, lines: &mut [String], steps: &[&Migration]) -> Vec<LineChange> {
+    // Attributes a bundle child inherits are declared on the bundle's lines, which aren't the child's to edit
+    let mut attributes: HashMap<String, (String, Option<AttributeLocation>)> = doc
+        .attributes
+        .iter()
+        .map(|(name, value)| {
+            let location = doc.attribute_location(name).filter(|location| declares_at(doc, location.line));
+            (name.clone(), (value.clone(), location))
+        })
+        .collect();
+    let inherited_version = attributes.get("schema-version").is_some_and(|(_, location)| location.is_none())
+        && doc.attribute_location("schema-version").is_some();
+    let mut sections: Vec<(String, Option<usize>)> = doc
+        .sections
+        .iter()
+        .filter(|s| s.level > 0)
+        .map(|s| (s.title.clone(), s.line_number))
+        .collect();
+    let mut changes = Vec::new();
+
+    for step in steps {
+        for operation in &step.operations {
+            match operation {
+                MigrationOperation::RenameAttribute { from, to } => {
+                    if let Some((value, location)) = attributes.remove(from) {
+                        if let Some(location) = location {
+                            edit_line(lines, location.line, &mut changes, |line| rename_attribute(line, from, to));
+                        }
+                        attributes.insert(to.clone(), (value, location));
+                    }
+                }
+                MigrationOperation::MapValues { attribute, values } => {
+                    if let Some((value, location)) = attributes.get_mut(attribute) {
+                        if let Some(new_value) = values.get(value.as_str()) {
+                            if let Some(location) = location {
+                                edit_line(lines, location.line, &mut changes, |line| {
+                                    replace_value(line, attribute, new_value)
+                                });
+                            }
+                            *value = new_value.clone();
+                        }
+                    }
+                }
+                MigrationOperation::RenameSection { from, to } => {
+                    for (title, line_number) in sections.iter_mut().filter(|(title, _)| title == from) {
+                        if let Some(line_number) = line_number {
+                            edit_line(lines, *line_number, &mut changes, |line| {
+                                let markers = line.len() - line.trim_start_matches('=').len();
+                                (markers > 0).then(|| format!("{} {}", &line[..markers], to))
+                            });
+                        }
+                        *title = to.clone();
+                    }
+                }
+            }
+        }
+
+        if let Some((value, location)) = attributes.get_mut("schema-version") {
+            if let Some(location) = location {
+                edit_line(lines, location.line, &mut changes, |line| {
+                    replace_value(line, "schema-version", &step.to)
+                });
+            }
+            *value = step.to.clone();
+        }
+    }
+
+    if let (true, Some(last)) = (inherited_version, steps.last()) {
+        declare_attribute(doc, lines, &mut changes, "schema-version", &last.to);
+    }
+
+    changes
+}
+
+/// The first line of a document's own content. A bundle child's content is
+/// the whole bundle with everything outside the child blanked out
+fn first_content_line(doc: &ForgepointDocument) -> usize {
+    doc.content.lines().position(|line| !line.trim().is_empty()).unwrap_or(0) + 1
+}
+
+/// The line of a bundle child's block attribute list, which sits just above
+/// its heading and declares its type
+fn block_attribute_line(doc: &ForgepointDocument) -> Option<usize> {
+    let line = first_content_line(doc).checked_sub(1).filter(|line| *line > 0)?;
+    doc.attribute_location("forgepoint-type").filter(|location| location.line == line).map(|_| line)
+}
+
+/// Whether the document declares an attribute on `line` itself, rather than
+/// inheriting it from its bundle's header
+fn declares_at(doc: &ForgepointDocument, line: usize) -> bool {
+    line >= first_content_line(doc) || block_attribute_line(doc) == Some(line)
+}
+
+/// Add an attribute to the document's own declarations: its block attribute
+/// list if it has one, otherwise a header line after its last attribute
+fn declare_attribute(doc: &ForgepointDocument, lines: &mut [String], changes: &mut Vec<LineChange>, name: &str, value: &str) {
+    if let Some(line_number) = block_attribute_line(doc) {
+        edit_line(lines, line_number, changes, |line| {
+            let entries = line.trim_end().strip_suffix(']')?;
+            Some(format!("{},{}={}]", entries, name, value))
+        });
+        return;
+    }
+
+    let first_line = first_content_line(doc);
+    let last_attribute = doc
+        .attribute_locations
+        .values()
+        .map(|location| location.line)
+        .filter(|line| *line >= first_line)
+        .max()
+        .unwrap_or(first_line);
+    edit_line(lines, last_attribute, changes, |line| Some(format!("{}\n:{}: {}", line, name, value)));
+}
+
+/// Rewrite a 1-based line, merging the change into any earlier change to it
+fn edit_line<F>(lines: &mut [String], line_number: usize, changes: &mut Vec<LineChange>, edit: F)
+where
+    F: FnOnce(&str) -> Option<String>,
+{
+    let Some(line) = line_number.checked_sub(1).and_then(|index| lines.get_mut(index)) else {
+        return;
+    };
+    let Some(new_line) = edit(line).filter(|new_line| new_line != line) else {
+        return;
+    };
+
+    match changes.iter_mut().find(|c| c.line == line_number) {
+        Some(change) => change.new = new_line.clone(),
+        None => changes.push(LineChange {
+            line: line_number,
+            old: line.clone(),
+            new: new_line.clone(),
+        }),
+    }
+    *line = new_line;
+}
+
+/// Split a `[name=value,...]` block attribute list into the byte ranges of
+/// its entries, ignoring commas inside quotes
+fn attribute_list_entries(line: &str) -> Vec<(usize, usize)> {
+    let trimmed = line.trim_end();
+    if !trimmed.starts_with('[') || !trimmed.ends_with(']') || trimmed.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut entries = Vec::new();
+    let mut entry_start = 1;
+    let mut in_quotes = false;
+    for (offset, ch) in trimmed.char_indices().skip(1) {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                entries.push((entry_start, offset));
+                entry_start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push((entry_start, trimmed.len() - 1));
+    entries
+}
+
+/// Find the byte ranges of an attribute's name and value, in either a
+/// `:name: value` header line or a `[name=value]` block attribute list
+fn find_attribute(line: &str, name: &str) -> Option<((usize, usize), (usize, usize))> {
+    let header_name = format!(":{}:", name);
+    if line.starts_with(&header_name) {
+        let rest = &line[header_name.len()..];
+        let value_start = header_name.len() + (rest.len() - rest.trim_start().len());
+        return Some(((1, 1 + name.len()), (value_start, line.trim_end().len().max(value_start))));
+    }
+
+    attribute_list_entries(line).into_iter().find_map(|(start, end)| {
+        let entry = &line[start..end];
+        let equals = entry.find('=')?;
+        let key = &entry[..equals];
+        if key.trim() != name {
+            return None;
+        }
+
+        let name_start = start + (key.len() - key.trim_start().len());
+        let raw_value = &entry[equals + 1..];
+        let mut value_start = start + equals + 1 + (raw_value.len() - raw_value.trim_start().len());
+        let mut value_end = start + equals + 1 + raw_value.trim_end().len();
+        if line[value_start..value_end].len() >= 2
+            && line[value_start..value_end].starts_with('"')
+            && line[value_start..value_end].ends_with('"')
+        {
+            value_start += 1;
+            value_end -= 1;
+        }
+        Some(((name_start, name_start + name.len()), (value_start, value_end)))
+    })
+}
+
+/// Rename an attribute declared on the line
+fn rename_attribute(line: &str, from: &str, to: &str) -> Option<String> {
+    let ((start, end), _) = find_attribute(line, from)?;
+    Some(format!("{}{}{}", &line[..start], to, &line[end..]))
+}
+
+/// Replace the value of an attribute declared on the line, keeping any quotes
+fn replace_value(line: &str, name: &str, new: &str) -> Option<String> {
+    let (_, (start, end)) = find_attribute(line, name)?;
+    Some(format!("{}{}{}", &line[..start], new, &line[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn story_migration() -> Migration {
+        serde_json::from_str(
+            r#"{
+                "documentType": "story",
+                "from": "1.0",
+                "to": "2.0",
+                "operations": [
+                    { "op": "renameAttribute", "from": "owner", "to": "assignee" },
+                    { "op": "mapValues", "attribute": "status", "values": { "in-progress": "active" } },
+                    { "op": "renameSection", "from": "Acceptance Criteria", "to": "Acceptance Tests" }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_migrations() {
+        let mut second = story_migration();
+        second.from = "2.0".to_string();
+        second.to = "3.0".to_string();
+        let set = MigrationSet {
+            migrations: vec![story_migration(), second],
+        };
+
+        assert_eq!(set.plan("story", "1.0", "3.0").map(|steps| steps.len()), Some(2));
+        assert_eq!(set.plan("story", "2.0", "2.0").map(|steps| steps.len()), Some(0));
+        assert!(set.plan("story", "3.0", "1.0").is_none());
+        assert!(set.plan("epic", "1.0", "2.0").is_none());
+    }
+
+    #[test]
+    fn test_migrate_document() {
+        let content = "= Login\n:forgepoint-type: story\n:id: auth-login\n:status: in-progress\n:owner: alice\n:schema-version: 1.0\n\n== Acceptance Criteria\n\n* [ ] Works\n";
+        let doc = DocumentParser::new()
+            .parse_content(content, PathBuf::from("login.adoc"))
+            .unwrap();
+        let mut lines: Vec<String> = content.split('\n').map(String::from).collect();
+        let migration = story_migration();
+
+        let changes = migrate_document(&doc, &mut lines, &[&migration]);
+
+        assert_eq!(changes.len(), 4);
+        assert_eq!(
+            lines.join("\n"),
+            "= Login\n:forgepoint-type: story\n:id: auth-login\n:status: active\n:assignee: alice\n:schema-version: 2.0\n\n== Acceptance Tests\n\n* [ ] Works\n"
+        );
+    }
+
+    #[test]
+    fn test_migrate_bundle_children() {
+        let content = "= Checkout\n:forgepoint-type: epic\n:id: checkout\n:schema-version: 1.0\n:forgepoint-bundle:\n\n[forgepoint-type=story,id=pay-by-card]\n== Pay by card\n\n=== Acceptance Criteria\n\n== Order confirmation\n:forgepoint-type: story\n:id: order-confirmation\n:status: in-progress\n\n== Refunds\n:forgepoint-type: story\n:id: refunds\n:schema-version: 1.0\n";
+        let docs = DocumentParser::new()
+            .parse_content_documents(content, PathBuf::from("checkout.adoc"))
+            .unwrap();
+        let mut lines: Vec<String> = content.split('\n').map(String::from).collect();
+        let migration = story_migration();
+
+        for doc in &docs[1..] {
+            migrate_document(doc, &mut lines, &[&migration]);
+        }
+
+        assert_eq!(
+            lines.join("\n"),
+            "= Checkout\n:forgepoint-type: epic\n:id: checkout\n:schema-version: 1.0\n:forgepoint-bundle:\n\n[forgepoint-type=story,id=pay-by-card,schema-version=2.0]\n== Pay by card\n\n=== Acceptance Tests\n\n== Order confirmation\n:forgepoint-type: story\n:id: order-confirmation\n:status: active\n:schema-version: 2.0\n\n== Refunds\n:forgepoint-type: story\n:id: refunds\n:schema-version: 2.0\n"
+        );
+    }
+
+    #[test]
+    fn test_migrate_block_attributes() {
+        let mut lines = vec!["[forgepoint-type=story,id=auth-login,status=\"in-progress\",owner=alice]".to_string()];
+        let mut changes = Vec::new();
+
+        edit_line(&mut lines, 1, &mut changes, |line| replace_value(line, "status", "active"));
+        edit_line(&mut lines, 1, &mut changes, |line| rename_attribute(line, "owner", "assignee"));
+
+        assert_eq!(lines[0], "[forgepoint-type=story,id=auth-login,status=\"active\",assignee=alice]");
+        assert_eq!(changes.len(), 1);
+    }
+}
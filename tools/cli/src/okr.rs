@@ -0,0 +1,594 @@
+use crate::document::ForgepointDocument;
+use crate::progress::{check_progress_attribute, PROGRESS_SECTION};
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// OKR levels from the top down
+pub const LEVELS: &[&str] = &["company", "team", "individual"];
+
+/// How far `progress` may differ from the progress of the key results
+pub const PROGRESS_TOLERANCE: f64 = 0.15;
+
+/// How far progress may trail the elapsed share of the period before high
+/// confidence is questioned
+pub const CONFIDENCE_MARGIN: f64 = 0.25;
+
+/// A number parsed from a key result, such as `60%`, `$1.2M` or `300ms`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Metric {
+    pub raw: String,
+    pub value: f64,
+}
+
+impl Metric {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let regex = Regex::new(r"^[^\d+\-.]*([+\-]?\d[\d,]*(?:\.\d+)?|[+\-]?\.\d+)\s*([a-zA-Z%]*)").unwrap();
+        let cap = regex.captures(raw.trim())?;
+        let value: f64 = cap[1].replace(',', "").parse().ok()?;
+        let multiplier = match &cap[2] {
+            "k" | "K" => 1e3,
+            "m" | "M" => 1e6,
+            "b" | "B" => 1e9,
+            _ => 1.0,
+        };
+
+        Some(Self {
+            raw: raw.trim().to_string(),
+            value: value * multiplier,
+        })
+    }
+}
+
+/// A key result checklist item with the metrics written after it, e.g.
+/// `Increase engagement by 25% (current: 60%, target: 75%)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyResult {
+    pub text: String,
+    pub line: usize,
+    pub checked: bool,
+    pub baseline: Option<Metric>,
+    pub current: Option<Metric>,
+    pub target: Option<Metric>,
+    /// Share of the key result achieved, between 0 and 1
+    pub progress: f64,
+    /// Whether the progress comes from metrics rather than the checkbox
+    pub measured: bool,
+}
+
+impl KeyResult {
+    fn new(text: &str, line: usize, checked: bool) -> Self {
+        let metrics_regex = Regex::new(r"\(([^()]*\btarget:[^()]*)\)\s*$").unwrap();
+        let (description, mut metrics) = match metrics_regex.captures(text) {
+            Some(cap) => (
+                text[..cap.get(0).unwrap().start()].trim(),
+                cap[1]
+                    .split(", ")
+                    .filter_map(|pair| pair.split_once(':'))
+                    .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            None => (text.trim(), BTreeMap::new()),
+        };
+
+        let mut metric = |keys: &[&str]| keys.iter().find_map(|key| metrics.remove(*key)).and_then(|v| Metric::parse(&v));
+        let baseline = metric(&["baseline", "start"]);
+        let current = metric(&["current"]);
+        let target = metric(&["target"]);
+
+        let measured_progress = match (&baseline, &current, &target) {
+            (_, Some(current), Some(target)) => {
+                let start = baseline.as_ref().map(|b| b.value).unwrap_or(0.0);
+                // Without a baseline only growth towards the target can be measured
+                let span = target.value - start;
+                (span != 0.0 && (baseline.is_some() || target.value >= current.value))
+                    .then(|| ((current.value - start) / span).clamp(0.0, 1.0))
+            }
+            _ => None,
+        };
+
+        Self {
+            text: description.to_string(),
+            line,
+            checked,
+            progress: match (checked, measured_progress) {
+                (true, _) => 1.0,
+                (false, Some(progress)) => progress,
+                (false, None) => 0.0,
+            },
+            measured: measured_progress.is_some(),
+            baseline,
+            current,
+            target,
+        }
+    }
+}
+
+/// An OKR document with the progress of its key results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    pub file_path: String,
+    pub document_id: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub period: Option<String>,
+    pub level: Option<String>,
+    pub team: Option<String>,
+    pub owner: Option<String>,
+    /// ID of the OKR this one contributes to
+    pub parent: Option<String>,
+    pub confidence: Option<String>,
+    /// The `progress` attribute as written
+    pub progress_attribute: Option<f64>,
+    pub key_results: Vec<KeyResult>,
+    /// Mean progress of the key results
+    pub progress: Option<f64>,
+}
+
+impl Objective {
+    pub fn from_document(doc: &ForgepointDocument) -> Self {
+        let attribute = |name: &str| doc.attributes.get(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let items = match doc
+            .sections
+            .iter()
+            .position(|section| section.level == 2 && section.title == PROGRESS_SECTION)
+        {
+            Some(index) => doc.section_checklist_items(index),
+            None => Vec::new(),
+        };
+        let key_results: Vec<KeyResult> = items
+            .iter()
+            .map(|item| KeyResult::new(&item.text, item.line_number, item.checked))
+            .collect();
+        let progress = mean(key_results.iter().map(|kr| kr.progress));
+
+        Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_id: doc.document_id().cloned(),
+            title: doc.title.clone(),
+            status: attribute("status"),
+            period: attribute("period"),
+            level: attribute("level"),
+            team: attribute("team"),
+            owner: attribute("owner"),
+            parent: attribute("parent"),
+            confidence: attribute("confidence"),
+            progress_attribute: attribute("progress").and_then(|v| v.parse().ok()),
+            key_results,
+            progress,
+        }
+    }
+
+    /// Whether any key result is measured by its metrics
+    pub fn is_measured(&self) -> bool {
+        self.key_results.iter().any(|kr| kr.measured)
+    }
+}
+
+/// An objective with the objectives that contribute to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveNode {
+    #[serde(flatten)]
+    pub objective: Objective,
+    /// Mean rolled-up progress of the contributing objectives, or the
+    /// objective's own progress when nothing contributes to it
+    pub rolled_up: Option<f64>,
+    pub children: Vec<ObjectiveNode>,
+}
+
+/// Progress of all objectives at one level in a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelSummary {
+    pub level: String,
+    pub objectives: usize,
+    pub progress: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodReport {
+    pub period: String,
+    pub levels: Vec<LevelSummary>,
+    pub objectives: Vec<ObjectiveNode>,
+}
+
+impl PeriodReport {
+    /// Objectives in the report, parents before the objectives contributing to them
+    pub fn flatten(&self) -> Vec<(usize, &ObjectiveNode)> {
+        fn walk<'a>(node: &'a ObjectiveNode, depth: usize, out: &mut Vec<(usize, &'a ObjectiveNode)>) {
+            out.push((depth, node));
+            for child in &node.children {
+                walk(child, depth + 1, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        for node in &self.objectives {
+            walk(node, 0, &mut out);
+        }
+        out
+    }
+}
+
+/// IDs of the objectives whose `parent` chain leads back to themselves, sorted
+pub fn parent_cycles(objectives: &[Objective]) -> Vec<String> {
+    let parents: HashMap<&str, &str> = objectives
+        .iter()
+        .filter_map(|o| Some((o.document_id.as_deref()?, o.parent.as_deref()?)))
+        .collect();
+
+    let mut cycles: Vec<String> = parents
+        .keys()
+        .filter(|id| {
+            let mut seen = HashSet::new();
+            let mut current = **id;
+            while let Some(parent) = parents.get(current) {
+                if parent == *id {
+                    return true;
+                }
+                if !seen.insert(*parent) {
+                    return false;
+                }
+                current = parent;
+            }
+            false
+        })
+        .map(|id| id.to_string())
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+/// Group objectives by period, summarise each level and nest each objective
+/// under the objective named by its `parent`. Objectives whose parents form
+/// a cycle are reported at the top level
+pub fn build_report(objectives: Vec<Objective>) -> Vec<PeriodReport> {
+    let mut periods: BTreeMap<String, Vec<Objective>> = BTreeMap::new();
+    for objective in objectives {
+        let period = objective.period.clone().unwrap_or_else(|| "unscheduled".to_string());
+        periods.entry(period).or_default().push(objective);
+    }
+
+    periods
+        .into_iter()
+        .map(|(period, mut objectives)| {
+            objectives.sort_by(|a, b| {
+                level_rank(a.level.as_deref())
+                    .cmp(&level_rank(b.level.as_deref()))
+                    .then(a.document_id.cmp(&b.document_id))
+            });
+
+            let mut levels: Vec<LevelSummary> = Vec::new();
+            for objective in &objectives {
+                let level = objective.level.clone().unwrap_or_else(|| "unspecified".to_string());
+                if levels.last().map(|l| &l.level) != Some(&level) {
+                    levels.push(LevelSummary {
+                        level: level.clone(),
+                        objectives: 0,
+                        progress: mean(
+                            objectives
+                                .iter()
+                                .filter(|o| o.level.as_deref().unwrap_or("unspecified") == level)
+                                .filter_map(|o| o.progress),
+                        ),
+                    });
+                }
+                levels.last_mut().unwrap().objectives += 1;
+            }
+
+            let ids: Vec<Option<String>> = objectives.iter().map(|o| o.document_id.clone()).collect();
+            let cycles = parent_cycles(&objectives);
+            let (roots, mut rest): (Vec<Objective>, Vec<Objective>) = objectives.into_iter().partition(|o| {
+                o.parent.as_ref().is_none_or(|parent| !ids.contains(&Some(parent.clone())))
+                    || o.document_id.as_ref().is_some_and(|id| cycles.contains(id))
+            });
+
+            let objectives = roots.into_iter().map(|root| build_node(root, &mut rest)).collect();
+            PeriodReport {
+                period,
+                levels,
+                objectives,
+            }
+        })
+        .collect()
+}
+
+fn build_node(objective: Objective, rest: &mut Vec<Objective>) -> ObjectiveNode {
+    let mut children = Vec::new();
+    while let Some(index) = rest
+        .iter()
+        .position(|o| o.parent.is_some() && o.parent == objective.document_id)
+    {
+        let child = rest.remove(index);
+        children.push(build_node(child, rest));
+    }
+
+    let rolled_up = match children.is_empty() {
+        true => objective.progress,
+        false => mean(children.iter().filter_map(|child| child.rolled_up)),
+    };
+
+    ObjectiveNode {
+        objective,
+        rolled_up,
+        children,
+    }
+}
+
+fn level_rank(level: Option<&str>) -> usize {
+    level
+        .and_then(|level| LEVELS.iter().position(|l| *l == level))
+        .unwrap_or(LEVELS.len())
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let values: Vec<f64> = values.collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// The first and last day of a period such as `2024-Q1`, `2024-H2` or `2024-annual`
+pub fn period_range(period: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let (year, part) = period.split_once('-')?;
+    let year: i32 = year.parse().ok()?;
+    let (first_month, months) = match part {
+        "Q1" => (1, 3),
+        "Q2" => (4, 3),
+        "Q3" => (7, 3),
+        "Q4" => (10, 3),
+        "H1" => (1, 6),
+        "H2" => (7, 6),
+        "annual" => (1, 12),
+        _ => return None,
+    };
+
+    let start = NaiveDate::from_ymd_opt(year, first_month, 1)?;
+    let next = match first_month + months {
+        13 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+        month => NaiveDate::from_ymd_opt(year, month, 1)?,
+    };
+    Some((start, next.pred_opt()?))
+}
+
+/// The share of a period that has passed by `today`
+pub fn elapsed(period: &str, today: NaiveDate) -> Option<f64> {
+    let (start, end) = period_range(period)?;
+    let days = (end - start).num_days() + 1;
+    let passed = (today - start).num_days() + 1;
+    Some((passed as f64 / days as f64).clamp(0.0, 1.0))
+}
+
+/// Warn when an OKR's `progress` or `confidence` doesn't match its key
+/// results. Without measured key results, `progress` is checked against the
+/// checked key results instead.
+pub fn check_okr(doc: &ForgepointDocument, today: NaiveDate) -> Vec<ValidationError> {
+    let objective = Objective::from_document(doc);
+    let mut warnings = Vec::new();
+
+    if !objective.is_measured() {
+        warnings.extend(check_progress_attribute(doc));
+    } else if let (Some(written), Some(progress)) = (objective.progress_attribute, objective.progress) {
+        if (written - progress).abs() > PROGRESS_TOLERANCE {
+            warnings.push(warning(
+                doc,
+                "progress",
+                format!(":progress: is {} but the key results add up to {:.2}", written, progress),
+                format!("Set :progress: to {:.2} or update the key results' current values", progress),
+            ));
+        }
+    }
+
+    let progress = match objective.is_measured() {
+        true => objective.progress,
+        false => objective.progress_attribute.or(objective.progress),
+    };
+    if let (Some("active"), Some(confidence), Some(period), Some(progress)) = (
+        objective.status.as_deref(),
+        objective.confidence.as_deref(),
+        objective.period.as_deref(),
+        progress,
+    ) {
+        if let Some(expected) = elapsed(period, today) {
+            let message = match confidence {
+                "high" if progress < expected - CONFIDENCE_MARGIN => Some(format!(
+                    ":confidence: is high but progress {:.2} trails the {:.0}% of {} that has passed",
+                    progress,
+                    expected * 100.0,
+                    period
+                )),
+                "low" if progress >= expected => Some(format!(
+                    ":confidence: is low but progress {:.2} is ahead of the {:.0}% of {} that has passed",
+                    progress,
+                    expected * 100.0,
+                    period
+                )),
+                _ => None,
+            };
+            if let Some(message) = message {
+                warnings.push(warning(
+                    doc,
+                    "confidence",
+                    message,
+                    "Revisit :confidence: at the next check-in".to_string(),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+fn warning(doc: &ForgepointDocument, attribute: &str, message: String, suggestion: String) -> ValidationError {
+    ValidationError {
+        error_type: ErrorType::Schema,
+        severity: Severity::Warning,
+        message,
+        location: doc.attribute_location(attribute).map(|location| Location {
+            line: Some(location.line),
+            column: Some(location.column),
+            section: Some("attributes".to_string()),
+        }),
+        rule: Some("okr-consistency".to_string()),
+        suggestion: Some(suggestion),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn okr(id: &str, level: &str, parent: Option<&str>, extra: &str, key_results: &str) -> ForgepointDocument {
+        let parent = parent.map(|p| format!(":parent: {}\n", p)).unwrap_or_default();
+        let content = format!(
+            "= Objective {}\n:forgepoint-type: okr\n:id: {}\n:status: active\n:period: 2024-Q1\n:level: {}\n{}{}:schema-version: 1.0\n\n== Key Results\n\n{}",
+            id, id, level, parent, extra, key_results
+        );
+        DocumentParser::new()
+            .parse_content(&content, PathBuf::from(format!("{}.adoc", id)))
+            .unwrap()
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_key_results() {
+        let kr = KeyResult::new("Increase engagement by 25% (current: 60%, target: 75%)", 9, false);
+        assert_eq!(kr.text, "Increase engagement by 25%");
+        assert_eq!(kr.current.as_ref().unwrap().value, 60.0);
+        assert_eq!(kr.target.as_ref().unwrap().raw, "75%");
+        assert!(kr.measured);
+        assert!((kr.progress - 0.8).abs() < 1e-9);
+
+        let kr = KeyResult::new("Grow revenue (baseline: $1.2M, current: 1,500k, target: $2M)", 10, false);
+        assert!((kr.progress - 0.375).abs() < 1e-9);
+
+        // Lower is better only with a baseline
+        let kr = KeyResult::new("Cut p95 latency (baseline: 400ms, current: 300ms, target: 200ms)", 11, false);
+        assert!((kr.progress - 0.5).abs() < 1e-9);
+        let kr = KeyResult::new("Cut p95 latency (current: 300ms, target: 200ms)", 11, false);
+        assert!(!kr.measured);
+        assert_eq!(kr.progress, 0.0);
+
+        let kr = KeyResult::new("Launch in Germany", 12, true);
+        assert!(!kr.measured);
+        assert_eq!(kr.progress, 1.0);
+    }
+
+    #[test]
+    fn test_build_report() {
+        let objectives: Vec<Objective> = [
+            okr("team-a", "team", Some("company"), "", "* [ ] A (current: 50, target: 100)\n"),
+            okr("company", "company", None, "", "* [ ] Revenue (current: 20%, target: 100%)\n"),
+            okr("alice", "individual", Some("team-b"), "", "* [x] Done\n* [ ] Open\n"),
+            okr("team-b", "team", Some("company"), "", "* [x] B\n"),
+        ]
+        .iter()
+        .map(Objective::from_document)
+        .collect();
+
+        let report = build_report(objectives);
+        assert_eq!(report.len(), 1);
+        let levels: Vec<_> = report[0].levels.iter().map(|l| (l.level.as_str(), l.objectives)).collect();
+        assert_eq!(levels, vec![("company", 1), ("team", 2), ("individual", 1)]);
+        assert_eq!(report[0].levels[1].progress, Some(0.75));
+
+        let company = &report[0].objectives[0];
+        assert_eq!(report[0].objectives.len(), 1);
+        assert_eq!(company.objective.progress, Some(0.2));
+        // team-a at 0.5, and team-b rolled up from alice at 0.5
+        assert_eq!(company.rolled_up, Some(0.5));
+        let flat: Vec<_> = report[0]
+            .flatten()
+            .iter()
+            .map(|(depth, node)| (*depth, node.objective.document_id.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            flat,
+            vec![
+                (0, "company".to_string()),
+                (1, "team-a".to_string()),
+                (1, "team-b".to_string()),
+                (2, "alice".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_report_with_parent_cycles() {
+        let objectives: Vec<Objective> = [
+            okr("team-a", "team", Some("team-b"), "", "* [x] A\n"),
+            okr("team-b", "team", Some("team-a"), "", "* [ ] B\n"),
+            okr("team-c", "team", Some("team-c"), "", "* [x] C\n"),
+            okr("alice", "individual", Some("team-a"), "", "* [x] Done\n"),
+        ]
+        .iter()
+        .map(Objective::from_document)
+        .collect();
+
+        assert_eq!(parent_cycles(&objectives), vec!["team-a", "team-b", "team-c"]);
+
+        let report = build_report(objectives);
+        let flat: Vec<_> = report[0]
+            .flatten()
+            .iter()
+            .map(|(depth, node)| (*depth, node.objective.document_id.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            flat,
+            vec![
+                (0, "team-a".to_string()),
+                (1, "alice".to_string()),
+                (0, "team-b".to_string()),
+                (0, "team-c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_period_range() {
+        assert_eq!(period_range("2024-Q1"), Some((date("2024-01-01"), date("2024-03-31"))));
+        assert_eq!(period_range("2024-H2"), Some((date("2024-07-01"), date("2024-12-31"))));
+        assert_eq!(period_range("2024-annual"), Some((date("2024-01-01"), date("2024-12-31"))));
+        assert_eq!(period_range("2024-Q5"), None);
+        assert_eq!(elapsed("2024-Q1", date("2023-12-01")), Some(0.0));
+        assert_eq!(elapsed("2024-Q1", date("2024-06-01")), Some(1.0));
+    }
+
+    #[test]
+    fn test_check_okr() {
+        let messages = |doc: &ForgepointDocument, today: &str| {
+            check_okr(doc, date(today)).into_iter().map(|w| w.message).collect::<Vec<_>>()
+        };
+        let key_results = "* [ ] A (current: 20, target: 100)\n* [ ] B (current: 40, target: 100)\n";
+
+        let doc = okr("growth", "team", None, ":progress: 0.3\n:confidence: medium\n", key_results);
+        assert!(messages(&doc, "2024-02-15").is_empty());
+
+        let doc = okr("growth", "team", None, ":progress: 0.8\n:confidence: high\n", key_results);
+        assert_eq!(
+            messages(&doc, "2024-03-20"),
+            vec![
+                ":progress: is 0.8 but the key results add up to 0.30",
+                ":confidence: is high but progress 0.30 trails the 88% of 2024-Q1 that has passed",
+            ]
+        );
+
+        let doc = okr("growth", "team", None, ":confidence: low\n", key_results);
+        assert_eq!(
+            messages(&doc, "2024-01-10"),
+            vec![":confidence: is low but progress 0.30 is ahead of the 11% of 2024-Q1 that has passed"]
+        );
+
+        // Unmeasured key results fall back to the checked-items rule
+        let doc = okr("growth", "team", None, ":progress: 1.0\n", "* [x] A\n* [ ] B\n");
+        assert_eq!(
+            messages(&doc, "2024-01-10"),
+            vec![":progress: is 1.0 but 1 of 2 key results is unchecked"]
+        );
+    }
+}
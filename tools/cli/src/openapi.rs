@@ -0,0 +1,378 @@
+use crate::document::ForgepointDocument;
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+
+/// The api-spec section documenting each endpoint
+pub const ENDPOINTS_SECTION: &str = "Endpoints";
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// An HTTP method and path
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Endpoint {
+    pub method: String,
+    pub path: String,
+}
+
+impl Endpoint {
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+        }
+    }
+
+    /// The endpoint with path parameters blanked and any trailing slash
+    /// removed, so `/users/{id}`, `/users/{userId}` and `/users/:id/` compare equal
+    fn key(&self, prefix: &str) -> Option<(String, String)> {
+        let path = self.path.strip_prefix(prefix)?;
+        let segments: Vec<&str> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match segment.starts_with('{') || segment.starts_with(':') {
+                true => "{}",
+                false => segment,
+            })
+            .collect();
+        Some((self.method.clone(), format!("/{}", segments.join("/"))))
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+/// The endpoints mentioned in a document's Endpoints section, with the line
+/// and 1-based column each is first mentioned at
+pub fn documented_endpoints(doc: &ForgepointDocument) -> Vec<(Endpoint, (usize, usize))> {
+    let Some(index) = doc
+        .sections
+        .iter()
+        .position(|section| section.level == 2 && section.title == ENDPOINTS_SECTION)
+    else {
+        return Vec::new();
+    };
+
+    let endpoint_regex =
+        regex::Regex::new(r"\b(GET|PUT|POST|DELETE|OPTIONS|HEAD|PATCH|TRACE)\s+`?(/[^\s`|,;?)\]]*)").unwrap();
+    let mut endpoints: Vec<(Endpoint, (usize, usize))> = Vec::new();
+    for (line_number, line) in doc.section_lines(index) {
+        for cap in endpoint_regex.captures_iter(line) {
+            let endpoint = Endpoint::new(&cap[1], cap[2].trim_end_matches('.'));
+            if !endpoints.iter().any(|(existing, _)| existing.key("") == endpoint.key("")) {
+                let column = line[..cap.get(1).unwrap().start()].chars().count() + 1;
+                endpoints.push((endpoint, (line_number, column)));
+            }
+        }
+    }
+    endpoints
+}
+
+/// Check an api-spec document against the specification file its `spec-file`
+/// links to: the file must exist, and an OpenAPI file must agree with the
+/// document's `api-version`, endpoints and `authentication`
+pub fn check_api_spec(doc: &ForgepointDocument) -> Vec<ValidationError> {
+    let Some(spec_file) = doc.attributes.get("spec-file") else {
+        return Vec::new();
+    };
+    let path = doc.file_path.parent().unwrap_or(std::path::Path::new("")).join(spec_file.trim());
+    let at = |name: &str| doc.attribute_location(name).map(|location| (location.line, location.column));
+
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            return vec![error(
+                format!("Spec file {} can't be read: {}", path.display(), e),
+                at("spec-file"),
+                "Point :spec-file: at the specification, relative to this document",
+            )]
+        }
+    };
+    let format = doc.attributes.get("spec-format").map(String::as_str).unwrap_or_default();
+    let Some(format_version) = format.strip_prefix("openapi-") else {
+        return Vec::new();
+    };
+
+    let spec: Value = match serde_yaml::from_str(&text) {
+        Ok(spec) => spec,
+        Err(e) => {
+            return vec![error(
+                format!("Spec file {} isn't valid YAML or JSON: {}", path.display(), e),
+                at("spec-file"),
+                "Fix the syntax of the specification file",
+            )]
+        }
+    };
+    let Some(openapi) = spec.get("openapi").and_then(Value::as_str) else {
+        return vec![error(
+            format!("Spec file {} isn't an OpenAPI 3 document", path.display()),
+            at("spec-file"),
+            "Link an OpenAPI 3 file, or change :spec-format:",
+        )];
+    };
+
+    let mut errors = Vec::new();
+    if !openapi.starts_with(&format!("{}.", format_version)) && openapi != format_version {
+        errors.push(error(
+            format!(":spec-format: is {} but the spec is OpenAPI {}", format, openapi),
+            at("spec-format"),
+            &format!("Set :spec-format: to openapi-{}", openapi.split('.').take(2).collect::<Vec<_>>().join(".")),
+        ));
+    }
+    errors.extend(check_version(doc, &spec, at("api-version")));
+    errors.extend(check_endpoints(doc, &spec));
+    errors.extend(check_authentication(doc, &spec, at("authentication")));
+    errors
+}
+
+fn check_version(doc: &ForgepointDocument, spec: &Value, location: Option<(usize, usize)>) -> Option<ValidationError> {
+    let api_version = doc.attributes.get("api-version")?;
+    let info_version = spec.pointer("/info/version").and_then(Value::as_str)?;
+
+    // v2.1 matches 2.1 and 2.1.3, but not 2.2.0
+    let components = |version: &str| -> Vec<String> {
+        version.trim().trim_start_matches('v').split('.').map(str::to_string).collect()
+    };
+    let (api, info) = (components(api_version), components(info_version));
+    if api.len() <= info.len() && api.iter().zip(&info).all(|(a, b)| a == b) {
+        return None;
+    }
+
+    Some(error(
+        format!(":api-version: is {} but the spec's info.version is {}", api_version, info_version),
+        location,
+        "Update :api-version: or info.version so they agree",
+    ))
+}
+
+fn check_endpoints(doc: &ForgepointDocument, spec: &Value) -> Vec<ValidationError> {
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let spec_endpoints: Vec<Endpoint> = paths
+        .iter()
+        .flat_map(|(path, item)| {
+            HTTP_METHODS
+                .iter()
+                .filter(|method| item.get(**method).is_some())
+                .map(move |method| Endpoint::new(method, path))
+        })
+        .collect();
+    let spec_keys: BTreeSet<(String, String)> = spec_endpoints.iter().filter_map(|endpoint| endpoint.key("")).collect();
+
+    // The document may include the base path of a server URL, like /v1/users
+    let prefixes: Vec<String> = std::iter::once(String::new())
+        .chain(
+            spec.get("servers")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|server| server.get("url").and_then(Value::as_str))
+                .filter_map(|url| {
+                    let path = url::Url::parse(url).map(|url| url.path().to_string()).unwrap_or_else(|_| url.to_string());
+                    let path = path.trim_end_matches('/');
+                    path.starts_with('/').then(|| path.to_string())
+                }),
+        )
+        .collect();
+    let in_spec = |endpoint: &Endpoint| {
+        prefixes
+            .iter()
+            .filter_map(|prefix| endpoint.key(prefix))
+            .any(|key| spec_keys.contains(&key))
+    };
+
+    let documented = documented_endpoints(doc);
+    let mut errors: Vec<ValidationError> = documented
+        .iter()
+        .filter(|(endpoint, _)| !in_spec(endpoint))
+        .map(|(endpoint, location)| {
+            error(
+                format!("Endpoint {} isn't in the OpenAPI spec", endpoint),
+                Some(*location),
+                "Add the operation to the spec, or fix the method and path",
+            )
+        })
+        .collect();
+
+    let documented_keys: BTreeSet<(String, String)> = documented
+        .iter()
+        .flat_map(|(endpoint, _)| prefixes.iter().filter_map(|prefix| endpoint.key(prefix)))
+        .collect();
+    let section_line = doc
+        .sections
+        .iter()
+        .find(|section| section.level == 2 && section.title == ENDPOINTS_SECTION)
+        .and_then(|section| section.line_number);
+    for endpoint in spec_endpoints {
+        if endpoint.key("").is_some_and(|key| !documented_keys.contains(&key)) {
+            errors.push(error(
+                format!("Endpoint {} in the OpenAPI spec isn't documented in {}", endpoint, ENDPOINTS_SECTION),
+                section_line.map(|line| (line, 1)),
+                &format!("Describe {} in the {} section", endpoint, ENDPOINTS_SECTION),
+            ));
+        }
+    }
+    errors
+}
+
+/// The `authentication` value each security scheme corresponds to
+fn authentication_of(scheme: &Value) -> Option<&'static str> {
+    let scheme_name = scheme.get("scheme").and_then(Value::as_str).map(str::to_lowercase);
+    match (scheme.get("type").and_then(Value::as_str)?, scheme_name.as_deref()) {
+        ("apiKey", _) => Some("api-key"),
+        ("http", Some("bearer")) => Some("bearer-token"),
+        ("http", Some("basic")) => Some("basic-auth"),
+        ("oauth2" | "openIdConnect", _) => Some("oauth2"),
+        _ => None,
+    }
+}
+
+fn check_authentication(
+    doc: &ForgepointDocument,
+    spec: &Value,
+    location: Option<(usize, usize)>,
+) -> Option<ValidationError> {
+    let authentication = doc.attributes.get("authentication")?.trim();
+    let schemes: BTreeSet<&str> = spec
+        .pointer("/components/securitySchemes")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|schemes| schemes.values())
+        .filter_map(authentication_of)
+        .collect();
+    let required = spec
+        .get("security")
+        .and_then(Value::as_array)
+        .is_some_and(|requirements| requirements.iter().any(|r| r.as_object().is_some_and(|r| !r.is_empty())));
+
+    let message = match authentication {
+        "none" if required => format!(
+            ":authentication: is none but the spec requires security ({})",
+            schemes.iter().copied().collect::<Vec<_>>().join(", ")
+        ),
+        "none" => return None,
+        declared if schemes.contains(declared) => return None,
+        declared if schemes.is_empty() => {
+            format!(":authentication: is {} but the spec defines no security schemes", declared)
+        }
+        declared => format!(
+            ":authentication: is {} but the spec's security schemes are {}",
+            declared,
+            schemes.iter().copied().collect::<Vec<_>>().join(", ")
+        ),
+    };
+
+    Some(error(message, location, "Make :authentication: match components.securitySchemes"))
+}
+
+fn error(message: String, location: Option<(usize, usize)>, suggestion: &str) -> ValidationError {
+    ValidationError {
+        error_type: ErrorType::Reference,
+        severity: Severity::Error,
+        message,
+        location: location.map(|(line, column)| Location {
+            line: Some(line),
+            column: Some(column),
+            section: None,
+        }),
+        rule: Some("openapi-consistency".to_string()),
+        suggestion: Some(suggestion.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+
+    const SPEC: &str = r#"openapi: 3.0.3
+info:
+  title: Users
+  version: 1.4.0
+servers:
+  - url: https://api.example.com/v1
+paths:
+  /users:
+    get:
+      summary: List users
+    post:
+      summary: Create a user
+  /users/{userId}:
+    get:
+      summary: Get a user
+components:
+  securitySchemes:
+    token:
+      type: http
+      scheme: bearer
+security:
+  - token: []
+"#;
+
+    fn api_spec(dir: &std::path::Path, attributes: &str, endpoints: &str) -> ForgepointDocument {
+        let content = format!(
+            "= Users API\n:forgepoint-type: api-spec\n:id: users-api\n:status: draft\n:spec-format: openapi-3.0\n:spec-file: openapi.yaml\n{}:schema-version: 1.0\n\n== Endpoints\n\n{}\n",
+            attributes, endpoints
+        );
+        DocumentParser::new()
+            .parse_content(&content, dir.join("users-api.adoc"))
+            .unwrap()
+    }
+
+    fn messages(doc: &ForgepointDocument) -> Vec<String> {
+        check_api_spec(doc).into_iter().map(|error| error.message).collect()
+    }
+
+    #[test]
+    fn test_consistent_api_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("openapi.yaml"), SPEC).unwrap();
+
+        let doc = api_spec(
+            dir.path(),
+            ":api-version: v1\n:authentication: bearer-token\n",
+            "=== GET /users\n\nLists users.\n\n* `POST /v1/users` creates one\n\n=== GET /users/:id\n",
+        );
+        assert!(messages(&doc).is_empty(), "{:?}", messages(&doc));
+    }
+
+    #[test]
+    fn test_inconsistent_api_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("openapi.yaml"), SPEC.replace("openapi: 3.0.3", "openapi: 3.1.0")).unwrap();
+
+        let doc = api_spec(
+            dir.path(),
+            ":api-version: v2\n:authentication: api-key\n",
+            "| GET /users | List\n| DELETE /users/{id} | Remove\n",
+        );
+        let errors = check_api_spec(&doc);
+        let found: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                ":spec-format: is openapi-3.0 but the spec is OpenAPI 3.1.0",
+                ":api-version: is v2 but the spec's info.version is 1.4.0",
+                "Endpoint DELETE /users/{id} isn't in the OpenAPI spec",
+                "Endpoint POST /users in the OpenAPI spec isn't documented in Endpoints",
+                "Endpoint GET /users/{userId} in the OpenAPI spec isn't documented in Endpoints",
+                ":authentication: is api-key but the spec's security schemes are bearer-token",
+            ]
+        );
+        assert_eq!(errors[2].location.as_ref().unwrap().line, Some(14));
+        assert_eq!(errors[2].location.as_ref().unwrap().column, Some(3));
+        assert_eq!(errors[3].location.as_ref().unwrap().line, Some(11));
+        assert_eq!(errors[3].location.as_ref().unwrap().column, Some(1));
+
+        fs::remove_file(dir.path().join("openapi.yaml")).unwrap();
+        let unreadable = messages(&api_spec(dir.path(), "", ""));
+        assert_eq!(unreadable.len(), 1);
+        assert!(unreadable[0].contains("openapi.yaml can't be read"));
+    }
+}
@@ -1,9 +1,9 @@
-use crate::document::{ForgepointDocument, Section};
+use crate::document::{AttributeLocation, ForgepointDocument, Section};
 use crate::error::{ForgepointError, Result};
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct DocumentParser {
     // Pre-compiled regexes for better performance
@@ -34,11 +34,218 @@ impl DocumentParser {
         self.parse_content(&content, path.to_path_buf())
     }
 
+    /// Parse an AsciiDoc file into one or more Forgepoint documents, splitting
+    /// bundles into their child documents
+    pub fn parse_file_documents<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<ForgepointDocument>> {
+        let path = file_path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            ForgepointError::Parsing(format!("Failed to read file '{}': {}", path.display(), e))
+        })?;
+
+        self.parse_content_documents(&content, path.to_path_buf())
+    }
+
+    /// Parse AsciiDoc content into one or more Forgepoint documents.
+    ///
+    /// A document that declares `:forgepoint-bundle:` is split into the parent
+    /// and one document per level-1 section that declares its own
+    /// `forgepoint-type`, either in a block attribute list above the heading
+    /// (`[forgepoint-type=story,id=auth-login]`) or in a nested header of
+    /// `:key: value` lines directly below it. Each document keeps the full
+    /// line layout of the file, with the other documents blanked out, so line
+    /// numbers in validation results point into the original file.
+    pub fn parse_content_documents(&self, content: &str, file_path: PathBuf) -> Result<Vec<ForgepointDocument>> {
+        let document = self.parse_content(content, file_path.clone())?;
+        if !document.is_bundle() {
+            return Ok(vec![document]);
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        let children = self.find_bundle_children(&document, &lines);
+        if children.is_empty() {
+            return Ok(vec![document]);
+        }
+
+        let mut documents = Vec::new();
+
+        // A bundle without its own type is only a container for its children
+        if document.document_type().is_some() {
+            let parent_content = Self::mask_lines(&lines, |line_number, line| {
+                if children.iter().any(|child| child.contains(line_number)) {
+                    None
+                } else {
+                    Some(line.to_string())
+                }
+            });
+            documents.push(self.parse_content(&parent_content, file_path.clone())?);
+        }
+
+        for child in &children {
+            let child_content = Self::mask_lines(&lines, |line_number, line| {
+                if line_number == child.heading_line {
+                    Some(format!("= {}", child.title))
+                } else if line_number > child.heading_line && line_number <= child.end_line {
+                    // Promote nested sections so the child's sections start at level 2
+                    Some(line.strip_prefix('=').filter(|l| l.starts_with('=')).unwrap_or(line).to_string())
+                } else {
+                    None
+                }
+            });
+
+            let mut child_document = self.parse_content(&child_content, file_path.clone())?;
+
+            for (key, value, location) in &child.block_attributes {
+                if !child_document.attributes.contains_key(key) {
+                    child_document.attributes.insert(key.clone(), value.clone());
+                    child_document.attribute_locations.insert(key.clone(), *location);
+                }
+            }
+
+            // Children share the bundle's schema version unless they declare their own
+            if let Some(version) = document.schema_version() {
+                if !child_document.attributes.contains_key("schema-version") {
+                    child_document.attributes.insert("schema-version".to_string(), version.clone());
+                    if let Some(location) = document.attribute_location("schema-version") {
+                        child_document.attribute_locations.insert("schema-version".to_string(), location);
+                    }
+                }
+            }
+
+            documents.push(child_document);
+        }
+
+        Ok(documents)
+    }
+
+    /// Find the level-1 sections of a bundle that declare a child document
+    fn find_bundle_children(&self, document: &ForgepointDocument, lines: &[&str]) -> Vec<BundleChild> {
+        let mut children: Vec<BundleChild> = Vec::new();
+
+        for (index, section) in document.sections.iter().enumerate() {
+            let heading_line = match section.line_number {
+                Some(line) if section.level == 2 => line,
+                _ => continue,
+            };
+
+            let end_line = document.sections[index + 1..]
+                .iter()
+                .find(|next| next.level <= 2)
+                .and_then(|next| next.line_number)
+                .map(|line| line - 1)
+                .unwrap_or(lines.len());
+
+            // Block attribute list directly above the heading
+            let block_attributes = heading_line
+                .checked_sub(2)
+                .and_then(|index| lines.get(index))
+                .map(|line| Self::parse_attribute_list(line, heading_line - 1))
+                .unwrap_or_default();
+
+            // Nested header directly below the heading
+            let nested_header_type = lines
+                .iter()
+                .skip(heading_line)
+                .map_while(|line| self.attribute_regex.captures(line))
+                .any(|cap| cap[1].trim() == "forgepoint-type");
+
+            let has_block_type = block_attributes.iter().any(|(key, _, _)| key == "forgepoint-type");
+            if !has_block_type && !nested_header_type {
+                continue;
+            }
+
+            let start_line = if has_block_type { heading_line - 1 } else { heading_line };
+
+            // The previous child ends before this child's block attribute list
+            if let Some(previous) = children.last_mut() {
+                if previous.end_line >= start_line {
+                    previous.end_line = start_line - 1;
+                }
+            }
+
+            children.push(BundleChild {
+                title: section.title.clone(),
+                start_line,
+                heading_line,
+                end_line,
+                block_attributes: if has_block_type { block_attributes } else { Vec::new() },
+            });
+        }
+
+        children
+    }
+
+    /// Parse an AsciiDoc block attribute list such as
+    /// `[forgepoint-type=story,id=auth-login,status="in-progress"]`
+    fn parse_attribute_list(line: &str, line_number: usize) -> Vec<(String, String, AttributeLocation)> {
+        let trimmed = line.trim_end();
+        if !trimmed.starts_with('[') || !trimmed.ends_with(']') || trimmed.len() < 2 {
+            return Vec::new();
+        }
+
+        // Split on commas outside quotes, keeping each entry's byte offset
+        let inner = &trimmed[1..trimmed.len() - 1];
+        let mut entries = Vec::new();
+        let mut entry_start = 0;
+        let mut in_quotes = false;
+        for (offset, ch) in inner.char_indices() {
+            match ch {
+                '"' => in_quotes = !in_quotes,
+                ',' if !in_quotes => {
+                    entries.push((entry_start, &inner[entry_start..offset]));
+                    entry_start = offset + 1;
+                }
+                _ => {}
+            }
+        }
+        entries.push((entry_start, &inner[entry_start..]));
+
+        entries
+            .into_iter()
+            .filter_map(|(entry_offset, entry)| {
+                let equals = entry.find('=')?;
+                let key = entry[..equals].trim();
+                if key.is_empty() {
+                    return None;
+                }
+
+                // Byte offset of the value within the line, past the opening bracket
+                let raw_value = &entry[equals + 1..];
+                let value_offset = 1 + entry_offset + equals + 1 + (raw_value.len() - raw_value.trim_start().len());
+                let value = raw_value.trim().trim_matches('"').to_string();
+
+                Some((
+                    key.to_string(),
+                    value,
+                    AttributeLocation {
+                        line: line_number,
+                        column: line[..value_offset].chars().count() + 1,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Rebuild content line by line, blanking the lines the mapper drops
+    fn mask_lines<F>(lines: &[&str], mut mapper: F) -> String
+    where
+        F: FnMut(usize, &str) -> Option<String>,
+    {
+        let mut masked = String::new();
+        for (line_no, line) in lines.iter().enumerate() {
+            if let Some(mapped) = mapper(line_no + 1, line) {
+                masked.push_str(&mapped);
+            }
+            masked.push('\n');
+        }
+        masked
+    }
+
     /// Parse AsciiDoc content into a Forgepoint document
-    pub fn parse_content(&self, content: &str, file_path: std::path::PathBuf) -> Result<ForgepointDocument> {
+    pub fn parse_content(&self, content: &str, file_path: PathBuf) -> Result<ForgepointDocument> {
         let lines: Vec<&str> = content.lines().collect();
         let mut title = None;
         let mut attributes = HashMap::new();
+        let mut attribute_locations = HashMap::new();
         let mut sections = Vec::new();
 
         let mut current_section: Option<Section> = None;
@@ -65,6 +272,14 @@ impl DocumentParser {
                 if let Some(cap) = self.attribute_regex.captures(line) {
                     let key = cap[1].trim().to_string();
                     let value = cap[2].trim().to_string();
+                    let value_start = cap.get(2).map(|m| m.start()).unwrap_or(0);
+                    attribute_locations.insert(
+                        key.clone(),
+                        AttributeLocation {
+                            line: line_number,
+                            column: line[..value_start].chars().count() + 1,
+                        },
+                    );
                     attributes.insert(key, value);
                     continue;
                 }
@@ -122,6 +337,7 @@ impl DocumentParser {
             attributes,
             content: content.to_string(),
             sections,
+            attribute_locations,
         })
     }
 
@@ -162,6 +378,22 @@ impl DocumentParser {
     }
 }
 
+/// A child document declared inside a bundle
+struct BundleChild {
+    title: String,
+    /// First line of the child, including any block attribute list
+    start_line: usize,
+    heading_line: usize,
+    end_line: usize,
+    block_attributes: Vec<(String, String, AttributeLocation)>,
+}
+
+impl BundleChild {
+    fn contains(&self, line_number: usize) -> bool {
+        line_number >= self.start_line && line_number <= self.end_line
+    }
+}
+
 impl Default for DocumentParser {
     fn default() -> Self {
         Self::new()
@@ -204,6 +436,93 @@ More content here.
         assert_eq!(doc.sections[1].title, "Section Two");
     }
 
+    #[test]
+    fn test_attribute_locations() {
+        let content = "= Test\n:forgepoint-type: story\n:id:   test-story\n:schema-version: 1.0\n";
+
+        let parser = DocumentParser::new();
+        let doc = parser.parse_content(content, "test.adoc".into()).unwrap();
+
+        assert_eq!(doc.attribute_location("forgepoint-type"), Some(AttributeLocation { line: 2, column: 19 }));
+        assert_eq!(doc.attribute_location("id"), Some(AttributeLocation { line: 3, column: 8 }));
+        assert_eq!(doc.attribute_location("status"), None);
+    }
+
+    #[test]
+    fn test_extract_gherkin_blocks() {
+        let content = "= Login\n:forgepoint-type: scenario\n:id: login\n:schema-version: 1.0\n\n== Scenarios\n\n[source,gherkin]\n.Happy path\n----\nScenario: Log in\n  Given a user\n----\n\n[source,ruby]\n----\nputs 1\n----\n\n[source, gherkin]\n------\nScenario: Open\n";
+
+        let parser = DocumentParser::new();
+        let doc = parser.parse_content(content, "test.adoc".into()).unwrap();
+        let blocks = doc.extract_gherkin_blocks();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].line_number, 8);
+        assert_eq!(
+            blocks[0].lines,
+            vec![(11, "Scenario: Log in".to_string()), (12, "  Given a user".to_string())]
+        );
+        assert!(blocks[0].closed);
+        assert_eq!(blocks[1].lines, vec![(22, "Scenario: Open".to_string())]);
+        assert!(!blocks[1].closed);
+    }
+
+    #[test]
+    fn test_parse_bundle() {
+        let content = r#"= Checkout Epic
+:forgepoint-type: epic
+:id: checkout
+:schema-version: 1.0
+:forgepoint-bundle:
+
+== Overview
+
+Checkout covers payment and confirmation.
+
+[forgepoint-type=story,id=pay-by-card,status="in-progress"]
+== Pay by card
+
+=== Acceptance Criteria
+
+* [ ] Card is charged
+
+== Order confirmation
+:forgepoint-type: story
+:id: order-confirmation
+:status: draft
+
+=== Acceptance Criteria
+
+* [ ] Email is sent
+"#;
+
+        let parser = DocumentParser::new();
+        let docs = parser.parse_content_documents(content, "checkout.adoc".into()).unwrap();
+        assert_eq!(docs.len(), 3);
+
+        let epic = &docs[0];
+        assert_eq!(epic.document_id(), Some(&"checkout".to_string()));
+        assert_eq!(epic.sections.len(), 1);
+        assert_eq!(epic.sections[0].title, "Overview");
+
+        let card = &docs[1];
+        assert_eq!(card.title, Some("Pay by card".to_string()));
+        assert_eq!(card.document_type(), Some(&"story".to_string()));
+        assert_eq!(card.attributes.get("status"), Some(&"in-progress".to_string()));
+        assert_eq!(card.schema_version(), Some(&"1.0".to_string()));
+        assert_eq!(card.attribute_location("id"), Some(AttributeLocation { line: 11, column: 27 }));
+        assert_eq!(card.sections.len(), 1);
+        assert_eq!(card.sections[0].level, 2);
+        assert_eq!(card.sections[0].line_number, Some(14));
+        assert_eq!(card.extract_checklist_items()[0].line_number, 16);
+
+        let confirmation = &docs[2];
+        assert_eq!(confirmation.document_id(), Some(&"order-confirmation".to_string()));
+        assert_eq!(confirmation.attribute_location("status"), Some(AttributeLocation { line: 21, column: 10 }));
+        assert_eq!(confirmation.sections[0].title, "Acceptance Criteria");
+        assert_eq!(confirmation.extract_checklist_items().len(), 1);
+    }
+
     #[test]
     fn test_parse_file() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -0,0 +1,345 @@
+use crate::document::{ChecklistItem, ForgepointDocument};
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Statuses in which a document counts as finished
+pub const COMPLETED_STATUSES: &[&str] = &["done", "completed"];
+
+/// Attributes naming the document a document belongs to, such as a story's epic
+pub const PARENT_ATTRIBUTES: &[&str] = &["epic", "parent"];
+
+/// The section whose checklist a `progress` attribute is measured against
+pub const PROGRESS_SECTION: &str = "Key Results";
+
+/// Checked items out of all checklist items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Completion {
+    pub checked: usize,
+    pub total: usize,
+}
+
+impl Completion {
+    pub fn of(items: &[ChecklistItem]) -> Self {
+        Self {
+            checked: items.iter().filter(|item| item.checked).count(),
+            total: items.len(),
+        }
+    }
+
+    /// The share of items checked, if there are any items
+    pub fn ratio(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.checked as f64 / self.total as f64)
+    }
+
+    pub fn add(&mut self, other: Completion) {
+        self.checked += other.checked;
+        self.total += other.total;
+    }
+}
+
+/// Checklist completion of one level-2 section, including its subsections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionProgress {
+    pub section: String,
+    pub line: Option<usize>,
+    #[serde(flatten)]
+    pub completion: Completion,
+}
+
+/// Completion of the documents below a document, such as an epic's stories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Rollup {
+    pub children: usize,
+    pub children_completed: usize,
+    /// Checklist items of all descendants
+    #[serde(flatten)]
+    pub completion: Completion,
+}
+
+/// Checklist completion of a document and its sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentProgress {
+    pub file_path: String,
+    pub document_type: Option<String>,
+    pub document_id: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    /// ID of the document this one belongs to
+    pub parent: Option<String>,
+    #[serde(flatten)]
+    pub completion: Completion,
+    pub sections: Vec<SectionProgress>,
+    pub rollup: Option<Rollup>,
+}
+
+impl DocumentProgress {
+    pub fn is_completed(&self) -> bool {
+        self.status.as_deref().is_some_and(|status| COMPLETED_STATUSES.contains(&status))
+    }
+}
+
+/// Compute checklist completion for a document and each of its sections
+pub fn document_progress(doc: &ForgepointDocument) -> DocumentProgress {
+    let sections = doc
+        .sections
+        .iter()
+        .enumerate()
+        .filter(|(_, section)| section.level == 2)
+        .map(|(index, section)| SectionProgress {
+            section: section.title.clone(),
+            line: section.line_number,
+            completion: Completion::of(&doc.section_checklist_items(index)),
+        })
+        .filter(|section| section.completion.total > 0)
+        .collect();
+
+    DocumentProgress {
+        file_path: doc.file_path.to_string_lossy().to_string(),
+        document_type: doc.document_type().cloned(),
+        document_id: doc.document_id().cloned(),
+        title: doc.title.clone(),
+        status: doc.attributes.get("status").cloned(),
+        parent: PARENT_ATTRIBUTES
+            .iter()
+            .filter_map(|name| doc.attributes.get(*name))
+            .map(|value| value.trim().to_string())
+            .find(|value| !value.is_empty()),
+        completion: Completion::of(&doc.extract_checklist_items()),
+        sections,
+        rollup: None,
+    }
+}
+
+/// Roll checklist completion up from documents to the documents they belong
+/// to, e.g. an epic's completion from its stories and their tasks
+pub fn roll_up(documents: &mut [DocumentProgress]) {
+    let rollups: Vec<Option<Rollup>> = documents
+        .iter()
+        .map(|doc| {
+            let id = doc.document_id.as_deref()?;
+            let children: Vec<&DocumentProgress> = children_of(documents, id).collect();
+            if children.is_empty() {
+                return None;
+            }
+
+            let mut visited = HashSet::from([id]);
+            let mut completion = Completion::default();
+            descendant_completion(documents, id, &mut visited, &mut completion);
+
+            Some(Rollup {
+                children: children.len(),
+                children_completed: children.iter().filter(|child| child.is_completed()).count(),
+                completion,
+            })
+        })
+        .collect();
+
+    for (doc, rollup) in documents.iter_mut().zip(rollups) {
+        doc.rollup = rollup;
+    }
+}
+
+fn children_of<'a>(documents: &'a [DocumentProgress], id: &'a str) -> impl Iterator<Item = &'a DocumentProgress> {
+    documents.iter().filter(move |doc| doc.parent.as_deref() == Some(id))
+}
+
+fn descendant_completion<'a>(
+    documents: &'a [DocumentProgress],
+    id: &'a str,
+    visited: &mut HashSet<&'a str>,
+    completion: &mut Completion,
+) {
+    for child in children_of(documents, id) {
+        completion.add(child.completion);
+        if let Some(child_id) = child.document_id.as_deref() {
+            if visited.insert(child_id) {
+                descendant_completion(documents, child_id, visited, completion);
+            }
+        }
+    }
+}
+
+/// Warn about a finished document with unchecked items, leaving out the
+/// lines in `reported`
+pub fn check_unchecked_items(doc: &ForgepointDocument, reported: &[usize]) -> Option<ValidationError> {
+    let status = doc.attributes.get("status")?;
+    if !COMPLETED_STATUSES.contains(&status.as_str()) {
+        return None;
+    }
+
+    let items = doc.extract_checklist_items();
+    let unchecked: Vec<&ChecklistItem> = items
+        .iter()
+        .filter(|item| !item.checked && !reported.contains(&item.line_number))
+        .collect();
+    let first = unchecked.first()?;
+
+    Some(ValidationError {
+        error_type: ErrorType::Structure,
+        severity: Severity::Warning,
+        message: format!(
+            "Document is {} but {} of {} checklist items {} unchecked",
+            status,
+            unchecked.len(),
+            items.len(),
+            if unchecked.len() == 1 { "is" } else { "are" }
+        ),
+        location: Some(Location {
+            line: Some(first.line_number),
+            column: None,
+            section: None,
+        }),
+        rule: Some("unchecked-items".to_string()),
+        suggestion: Some(format!("Check the remaining items or change :status: from {}", status)),
+    })
+}
+
+/// Warn when a `progress` attribute disagrees with the checked key results:
+/// it can't be below the share already checked, and can't be complete while
+/// any are unchecked
+pub fn check_progress_attribute(doc: &ForgepointDocument) -> Option<ValidationError> {
+    let value = doc.attributes.get("progress")?;
+    let progress: f64 = value.trim().parse().ok()?;
+
+    let index = doc
+        .sections
+        .iter()
+        .position(|section| section.level == 2 && section.title == PROGRESS_SECTION);
+    let (items, subject) = match index {
+        Some(index) => (doc.section_checklist_items(index), "key results"),
+        None => (doc.extract_checklist_items(), "checklist items"),
+    };
+    let completion = Completion::of(&items);
+    let ratio = completion.ratio()?;
+
+    let (message, suggestion) = if progress < ratio {
+        (
+            format!(
+                ":progress: is {} but {} of {} {} are checked ({:.2})",
+                value, completion.checked, completion.total, subject, ratio
+            ),
+            format!("Set :progress: to at least {:.2}, or uncheck the {} that aren't met", ratio, subject),
+        )
+    } else if progress >= 1.0 && completion.checked < completion.total {
+        let unchecked = completion.total - completion.checked;
+        (
+            format!(
+                ":progress: is {} but {} of {} {} {} unchecked",
+                value,
+                unchecked,
+                completion.total,
+                subject,
+                if unchecked == 1 { "is" } else { "are" }
+            ),
+            format!("Check the remaining {}, or set :progress: below 1", subject),
+        )
+    } else {
+        return None;
+    };
+
+    Some(ValidationError {
+        error_type: ErrorType::Schema,
+        severity: Severity::Warning,
+        message,
+        location: doc.attribute_location("progress").map(|location| Location {
+            line: Some(location.line),
+            column: Some(location.column),
+            section: Some("attributes".to_string()),
+        }),
+        rule: Some("progress-mismatch".to_string()),
+        suggestion: Some(suggestion),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn parse(content: &str) -> ForgepointDocument {
+        DocumentParser::new()
+            .parse_content(content, PathBuf::from("doc.adoc"))
+            .unwrap()
+    }
+
+    fn story(id: &str, status: &str, epic: &str, items: &str) -> ForgepointDocument {
+        parse(&format!(
+            "= Story\n:forgepoint-type: story\n:id: {}\n:status: {}\n:epic: {}\n:schema-version: 1.0\n\n== Acceptance Criteria\n\n{}",
+            id, status, epic, items
+        ))
+    }
+
+    #[test]
+    fn test_document_progress() {
+        let doc = parse("= Payments\n:forgepoint-type: okr\n:id: payments\n:status: active\n:schema-version: 1.0\n\n== Key Results\n\n* [x] One\n* [ ] Two\n\n=== Stretch\n\n* [x] Three\n\n== Context\n\nText\n\n== Updates\n\n* [ ] Four\n");
+        let progress = document_progress(&doc);
+
+        assert_eq!(progress.completion, Completion { checked: 2, total: 4 });
+        let sections: Vec<_> = progress
+            .sections
+            .iter()
+            .map(|s| (s.section.as_str(), s.completion.checked, s.completion.total))
+            .collect();
+        assert_eq!(sections, vec![("Key Results", 2, 3), ("Updates", 0, 1)]);
+    }
+
+    #[test]
+    fn test_roll_up() {
+        let epic = parse("= Payments\n:forgepoint-type: epic\n:id: payments\n:status: in-progress\n:schema-version: 1.0\n");
+        let task = parse("= Task\n:forgepoint-type: task\n:id: card-task\n:status: todo\n:parent: card\n:schema-version: 1.0\n\n* [ ] Write tests\n");
+        let mut documents: Vec<DocumentProgress> = [
+            epic,
+            story("card", "done", "payments", "* [x] Pay by card\n"),
+            story("invoice", "in-progress", "payments", "* [x] Send invoice\n* [ ] Pay invoice\n"),
+            task,
+        ]
+        .iter()
+        .map(document_progress)
+        .collect();
+        roll_up(&mut documents);
+
+        let rollup = documents[0].rollup.as_ref().unwrap();
+        assert_eq!((rollup.children, rollup.children_completed), (2, 1));
+        assert_eq!(rollup.completion, Completion { checked: 2, total: 4 });
+        assert_eq!(documents[1].rollup.as_ref().unwrap().completion, Completion { checked: 0, total: 1 });
+        assert!(documents[2].rollup.is_none());
+    }
+
+    #[test]
+    fn test_unchecked_items() {
+        let done = story("card", "done", "payments", "* [x] Pay by card\n* [ ] Refund\n* [ ] Void\n");
+        let warning = check_unchecked_items(&done, &[]).unwrap();
+        assert_eq!(warning.message, "Document is done but 2 of 3 checklist items are unchecked");
+        assert_eq!(warning.location.unwrap().line, Some(11));
+        assert_eq!(
+            check_unchecked_items(&done, &[11]).unwrap().message,
+            "Document is done but 1 of 3 checklist items is unchecked"
+        );
+        assert!(check_unchecked_items(&done, &[11, 12]).is_none());
+
+        let open = story("card", "in-progress", "payments", "* [ ] Refund\n");
+        assert!(check_unchecked_items(&open, &[]).is_none());
+    }
+
+    #[test]
+    fn test_progress_attribute() {
+        let okr = |progress: &str| {
+            parse(&format!(
+                "= Payments\n:forgepoint-type: okr\n:id: payments\n:status: active\n:progress: {}\n:schema-version: 1.0\n\n== Key Results\n\n* [x] One\n* [x] Two\n* [ ] Three\n* [ ] Four\n",
+                progress
+            ))
+        };
+
+        assert!(check_progress_attribute(&okr("0.5")).is_none());
+        assert!(check_progress_attribute(&okr("0.7")).is_none());
+        let warning = check_progress_attribute(&okr("0.3")).unwrap();
+        assert_eq!(warning.message, ":progress: is 0.3 but 2 of 4 key results are checked (0.50)");
+        assert_eq!(warning.location.unwrap().line, Some(5));
+        assert_eq!(
+            check_progress_attribute(&okr("1.0")).unwrap().message,
+            ":progress: is 1.0 but 2 of 4 key results are unchecked"
+        );
+    }
+}
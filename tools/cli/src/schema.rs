@@ -1,15 +1,27 @@
+use crate::embedded::{embedded_schema, EMBEDDED_SCHEMAS};
 use crate::error::{ForgepointError, Result};
-use jsonschema::{Draft, JSONSchema};
+use chrono::{DateTime, Days, Months, NaiveDate, NaiveDateTime};
+use jsonschema::error::{TypeKind, ValidationErrorKind};
+use jsonschema::{Draft, JSONSchema, SchemaResolver, SchemaResolverError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
+
+/// Header attributes interpreted by the tool itself, never validated by schemas
+const TOOL_ATTRIBUTES: &[&str] = &["forgepoint-bundle"];
+
+/// Document type categories of the built-in schemas
+pub const BUILTIN_CATEGORIES: &[&str] = &["discovery", "design", "development", "testing", "release"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaRegistry {
-    #[serde(rename = "schemaVersion")]
+    #[serde(rename = "schemaVersion", default)]
     pub schema_version: String,
+    #[serde(default)]
     pub schemas: HashMap<String, SchemaRef>,
     #[serde(rename = "documentTypes")]
     pub document_types: Vec<DocumentTypeDefinition>,
@@ -29,13 +41,132 @@ pub struct DocumentTypeDefinition {
     pub description: String,
     pub category: String,
     pub schema: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<SchemaVersionDefinition>,
+    /// Set by a repository-local type that replaces a built-in one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub overrides: bool,
+    /// Where the type's schema files are resolved from
+    #[serde(skip)]
+    pub source: SchemaSource,
 }
 
-#[derive(Debug, Clone)]
+/// Where a set of schema files is read from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SchemaSource {
+    /// The built-in schema pack compiled into the binary
+    #[default]
+    Embedded,
+    /// A schema directory on disk
+    Directory(PathBuf),
+}
+
+impl SchemaSource {
+    /// Read a file given relative to the schema directory
+    pub fn read(&self, file: &Path) -> Result<String> {
+        match self {
+            SchemaSource::Embedded => embedded_schema(&Self::embedded_name(file))
+                .map(String::from)
+                .ok_or_else(|| ForgepointError::FileNotFound(self.display(file))),
+            SchemaSource::Directory(dir) => Ok(fs::read_to_string(dir.join(file))?),
+        }
+    }
+
+    /// Check whether a file exists in the schema directory
+    pub fn exists(&self, file: &Path) -> bool {
+        match self {
+            SchemaSource::Embedded => embedded_schema(&Self::embedded_name(file)).is_some(),
+            SchemaSource::Directory(dir) => dir.join(file).exists(),
+        }
+    }
+
+    /// List the JSON files in a folder of the schema directory, relative to it
+    pub fn list(&self, folder: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = match self {
+            SchemaSource::Embedded => {
+                let prefix = Self::embedded_name(folder);
+                EMBEDDED_SCHEMAS
+                    .iter()
+                    .map(|(name, _)| PathBuf::from(name))
+                    .filter(|name| name.parent().map(Self::embedded_name) == Some(prefix.clone()))
+                    .collect()
+            }
+            SchemaSource::Directory(dir) => fs::read_dir(dir.join(folder))
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .map(|entry| folder.join(entry.file_name()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        files.retain(|file| file.extension().is_some_and(|ext| ext == "json"));
+        files.sort();
+        files
+    }
+
+    /// Describe where a file lives, for messages
+    pub fn display(&self, file: &Path) -> String {
+        match self {
+            SchemaSource::Embedded => format!("built-in {}", Self::embedded_name(file)),
+            SchemaSource::Directory(dir) => dir.join(file).display().to_string(),
+        }
+    }
+
+    /// Normalise a relative path into the name it's embedded under
+    fn embedded_name(file: &Path) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        for component in file.components() {
+            match component {
+                Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+                Component::ParentDir => {
+                    parts.pop();
+                }
+                _ => {}
+            }
+        }
+        parts.join("/")
+    }
+}
+
+/// A schema version kept alongside the current one in `schema`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaVersionDefinition {
+    pub version: String,
+    pub schema: String,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+#[derive(Debug)]
 pub struct CompiledSchema {
     pub definition: DocumentTypeDefinition,
     pub json_schema: JSONSchema,
+    pub schema_json: Value,
     pub structural_requirements: StructuralRequirements,
+    /// The `schema-version` the schema pins, if any
+    pub version: Option<String>,
+    pub deprecated: bool,
+    pub date_rules: Vec<DateRule>,
+    pub due_dates: Vec<DueDateRule>,
+    pub lifecycle: Option<Lifecycle>,
+}
+
+/// A schema violation found in a document's attributes
+#[derive(Debug, Clone)]
+pub struct AttributeError {
+    /// Attribute the violation applies to, if it applies to a single attribute
+    pub attribute: Option<String>,
+    /// JSON Schema keyword that failed, e.g. `enum`, `pattern` or `required`
+    pub keyword: String,
+    /// What the keyword expected: allowed values, the pattern, the limit, etc.
+    pub expected: Vec<String>,
+    /// Raw message from the JSON Schema validator
+    pub message: String,
+    /// When the violation comes from an `if`/`then` rule, the attribute values
+    /// the rule applies to, e.g. `:status: is published`
+    pub condition: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +175,43 @@ pub struct StructuralRequirements {
     pub sections: Option<SectionRequirements>,
     #[serde(rename = "abstract")]
     pub abstract_req: Option<AbstractRequirement>,
+    pub conditions: Option<Vec<StructuralCondition>>,
+}
+
+/// Structural requirements that only apply while attributes have certain
+/// values, the counterpart of JSON Schema's `if`/`then` for sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuralCondition {
+    /// The values each attribute must have for the requirements to apply
+    #[serde(rename = "if")]
+    pub when: HashMap<String, Vec<String>>,
+    pub then: ConditionalRequirements,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalRequirements {
+    pub sections: Option<SectionRequirements>,
+}
+
+impl StructuralCondition {
+    /// Whether a document's attributes meet the condition
+    pub fn applies(&self, attributes: &HashMap<String, String>) -> bool {
+        self.when
+            .iter()
+            .all(|(attribute, values)| attributes.get(attribute).is_some_and(|value| values.contains(value)))
+    }
+
+    /// The condition in words, e.g. `:status: is done`
+    pub fn describe(&self) -> String {
+        let mut attributes: Vec<_> = self.when.iter().collect();
+        attributes.sort();
+        attributes
+            .into_iter()
+            .map(|(attribute, values)| format!(":{}: is {}", attribute, values.join(" or ")))
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,66 +225,446 @@ pub struct TitleRequirement {
 pub struct SectionRequirements {
     pub required: Option<Vec<String>>,
     pub optional: Option<Vec<String>>,
+    pub content: Option<HashMap<String, SectionContentRequirement>>,
+    pub ordered: Option<bool>,
+    #[serde(rename = "additionalSections")]
+    pub additional_sections: Option<AdditionalSections>,
     pub description: Option<String>,
 }
 
+/// Policy for sections that are neither required nor optional: `true` allows
+/// them, `false` rejects them and `"warn"` reports them as warnings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdditionalSections {
+    Allowed(bool),
+    Mode(AdditionalSectionsMode),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdditionalSectionsMode {
+    Warn,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionContentRequirement {
+    pub checklist: Option<ChecklistRequirement>,
+    pub table: Option<TableRequirement>,
+    #[serde(rename = "sourceBlock")]
+    pub source_block: Option<SourceBlockRequirement>,
+    pub subsections: Option<SectionRequirements>,
+    #[serde(rename = "minWords")]
+    pub min_words: Option<usize>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistRequirement {
+    #[serde(rename = "minItems")]
+    pub min_items: Option<usize>,
+    #[serde(rename = "maxItems")]
+    pub max_items: Option<usize>,
+    /// Every item must be checked
+    #[serde(rename = "allChecked")]
+    pub all_checked: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRequirement {
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceBlockRequirement {
+    pub language: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AbstractRequirement {
     pub required: Option<bool>,
     pub description: Option<String>,
 }
 
+/// An ordering constraint between two date attributes, checked when both are set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateRule {
+    /// The attribute the rule constrains
+    pub attribute: String,
+    /// The attribute must be strictly later than this one
+    pub after: Option<String>,
+    /// The attribute must be the same as or later than this one
+    #[serde(rename = "onOrAfter")]
+    pub on_or_after: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A date by which a document needs attention, such as its next review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueDateRule {
+    /// Date attribute holding the due date, or the date an interval counts from
+    pub attribute: String,
+    /// Attribute naming the interval, e.g. `monthly`, added to `attribute`
+    pub every: Option<String>,
+    /// Statuses in which the date applies; any status when unset
+    pub status: Option<Vec<String>>,
+    /// What falls due, e.g. "Risk review". When several rules share a
+    /// description, the first one that applies to a document is used.
+    pub description: Option<String>,
+}
+
+/// The states a document moves through and the changes allowed between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lifecycle {
+    /// The attribute holding the state
+    #[serde(default = "default_lifecycle_attribute")]
+    pub attribute: String,
+    /// The states each state may move to; states missing from the map are terminal
+    pub transitions: HashMap<String, Vec<String>>,
+}
+
+fn default_lifecycle_attribute() -> String {
+    "status".to_string()
+}
+
+impl Lifecycle {
+    /// Whether the state appears in the lifecycle, as a key or as a target
+    pub fn knows(&self, state: &str) -> bool {
+        self.transitions.contains_key(state) || self.transitions.values().flatten().any(|target| target == state)
+    }
+
+    /// Whether a document may move from one state to another
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        from == to || self.transitions.get(from).is_some_and(|next| next.iter().any(|state| state == to))
+    }
+
+    /// The shortest chain of allowed transitions between two states,
+    /// including both ends
+    pub fn path<'a>(&'a self, from: &'a str, to: &str) -> Option<Vec<&'a str>> {
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(state) = queue.pop_front() {
+            if state == to {
+                let mut path = vec![state];
+                while let Some(before) = previous.get(path[path.len() - 1]) {
+                    path.push(before);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for next in self.transitions.get(state).into_iter().flatten() {
+                if next != from && !previous.contains_key(next.as_str()) {
+                    previous.insert(next, state);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl DateRule {
+    /// The attribute compared against, and whether equal dates are allowed
+    pub fn reference(&self) -> Option<(&str, bool)> {
+        match (&self.after, &self.on_or_after) {
+            (Some(after), None) => Some((after, false)),
+            (None, Some(on_or_after)) => Some((on_or_after, true)),
+            _ => None,
+        }
+    }
+}
+
 pub struct SchemaLoader {
-    schema_path: PathBuf,
+    schema_source: SchemaSource,
+    /// Repository-local schema directories merged into the built-in registry
+    additional_schema_paths: Vec<PathBuf>,
     registry: Option<SchemaRegistry>,
     compiled_schemas: HashMap<String, CompiledSchema>,
+    /// Non-current schema versions, by document type and version
+    versioned_schemas: HashMap<String, HashMap<String, CompiledSchema>>,
+}
+
+impl CompiledSchema {
+    /// The schema of an attribute's property, with its `$ref`s resolved
+    pub fn attribute_schema(&self, name: &str) -> Option<Value> {
+        let property = self.schema_json.pointer(&format!("/properties/{}", name))?;
+        let dir = Path::new(&self.definition.schema).parent().unwrap_or(Path::new(""));
+        Some(dereference(&self.definition.source, dir, &self.schema_json, property, 0))
+    }
+
+    /// Validate document attributes against this schema
+    pub fn validate_attributes(&self, attributes: &HashMap<String, String>) -> Result<Vec<AttributeError>> {
+        // Convert attributes to JSON for validation, leaving out attributes that
+        // control the tool rather than describe the document
+        let attributes_json: serde_json::Map<String, Value> = attributes
+            .iter()
+            .filter(|(name, _)| !TOOL_ATTRIBUTES.contains(&name.as_str()))
+            .map(|(name, value)| {
+                let value = match self.attribute_schema(name) {
+                    Some(property) => coerce_attribute(value, &property),
+                    None => Value::String(value.clone()),
+                };
+                (name.clone(), value)
+            })
+            .collect();
+        let attributes_json = Value::Object(attributes_json);
+
+        let validation_result = self.json_schema.validate(&attributes_json);
+        
+        let mut errors = Vec::new();
+        if let Err(validation_errors) = validation_result {
+            for error in validation_errors {
+                let attribute = error.instance_path.clone().into_vec().into_iter().next();
+                let keyword = error
+                    .schema_path
+                    .clone()
+                    .into_vec()
+                    .pop()
+                    .unwrap_or_default();
+                let message = error.to_string();
+                let condition = self.condition_of(&error.schema_path.clone().into_vec());
+
+                match error.kind {
+                    ValidationErrorKind::Required { property } => errors.push(AttributeError {
+                        attribute: property.as_str().map(String::from),
+                        keyword,
+                        expected: Vec::new(),
+                        message,
+                        condition,
+                    }),
+                    ValidationErrorKind::AdditionalProperties { unexpected } => {
+                        let mut known: Vec<String> = self
+                            .schema_json
+                            .get("properties")
+                            .and_then(|p| p.as_object())
+                            .map(|p| p.keys().cloned().collect())
+                            .unwrap_or_default();
+                        known.sort();
+
+                        for name in unexpected {
+                            errors.push(AttributeError {
+                                attribute: Some(name),
+                                keyword: keyword.clone(),
+                                expected: known.clone(),
+                                message: message.clone(),
+                                condition: condition.clone(),
+                            });
+                        }
+                    }
+                    kind => errors.push(AttributeError {
+                        attribute,
+                        keyword,
+                        expected: Self::expected_values(&kind),
+                        message,
+                        condition,
+                    }),
+                }
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// Describe the `if` that guards the `then` a schema path runs through
+    fn condition_of(&self, schema_path: &[String]) -> Option<String> {
+        let then = schema_path.iter().rposition(|chunk| chunk == "then")?;
+        let pointer: String = schema_path[..then].iter().map(|chunk| format!("/{}", chunk)).collect();
+        self.schema_json
+            .pointer(&format!("{}/if", pointer))
+            .and_then(describe_condition)
+    }
+
+    /// Describe what a failed JSON Schema keyword expected
+    fn expected_values(kind: &ValidationErrorKind) -> Vec<String> {
+        let value_to_string = |value: &Value| match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+
+        match kind {
+            ValidationErrorKind::Enum { options } => options
+                .as_array()
+                .map(|values| values.iter().map(value_to_string).collect())
+                .unwrap_or_default(),
+            ValidationErrorKind::Constant { expected_value } => vec![value_to_string(expected_value)],
+            ValidationErrorKind::Pattern { pattern } => vec![pattern.clone()],
+            ValidationErrorKind::Format { format } => vec![format.clone()],
+            ValidationErrorKind::Type { kind } => match kind {
+                TypeKind::Single(primitive) => vec![primitive.to_string()],
+                TypeKind::Multiple(primitives) => (*primitives).into_iter().map(|p| p.to_string()).collect(),
+            },
+            ValidationErrorKind::Minimum { limit }
+            | ValidationErrorKind::Maximum { limit }
+            | ValidationErrorKind::ExclusiveMinimum { limit }
+            | ValidationErrorKind::ExclusiveMaximum { limit } => vec![value_to_string(limit)],
+            ValidationErrorKind::MinLength { limit } | ValidationErrorKind::MaxLength { limit } => {
+                vec![limit.to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the required sections
+    pub fn required_sections(&self) -> Vec<String> {
+        self.section_requirements()
+            .and_then(|s| s.required.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the optional sections
+    pub fn optional_sections(&self) -> Vec<String> {
+        self.section_requirements()
+            .and_then(|s| s.optional.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the section requirements
+    pub fn section_requirements(&self) -> Option<&SectionRequirements> {
+        self.structural_requirements.sections.as_ref()
+    }
+
+    /// Get the structural requirements that depend on attribute values
+    pub fn structural_conditions(&self) -> &[StructuralCondition] {
+        self.structural_requirements.conditions.as_deref().unwrap_or_default()
+    }
+
+    /// Check if an abstract is required
+    pub fn is_abstract_required(&self) -> bool {
+        self.structural_requirements
+            .abstract_req
+            .as_ref()
+            .and_then(|a| a.required)
+            .unwrap_or(false)
+    }
+
+    /// Get the title format requirement
+    pub fn title_format(&self) -> Option<String> {
+        self.structural_requirements
+            .title
+            .as_ref()
+            .and_then(|t| t.format.clone())
+    }
 }
 
 impl SchemaLoader {
     pub fn new<P: AsRef<Path>>(schema_path: P) -> Self {
+        Self::from_source(SchemaSource::Directory(schema_path.as_ref().to_path_buf()))
+    }
+
+    /// Create a loader for the schema pack built into the binary
+    pub fn embedded() -> Self {
+        Self::from_source(SchemaSource::Embedded)
+    }
+
+    /// Create a loader reading schemas from the given source
+    pub fn from_source(schema_source: SchemaSource) -> Self {
         Self {
-            schema_path: schema_path.as_ref().to_path_buf(),
+            schema_source,
+            additional_schema_paths: Vec::new(),
             registry: None,
             compiled_schemas: HashMap::new(),
+            versioned_schemas: HashMap::new(),
         }
     }
 
+    /// Add a repository-local schema directory whose `index.json` is merged
+    /// into the built-in registry
+    pub fn add_schema_path<P: AsRef<Path>>(&mut self, schema_path: P) {
+        self.additional_schema_paths.push(schema_path.as_ref().to_path_buf());
+    }
+
     /// Load and compile all schemas
     pub fn load_schemas(&mut self) -> Result<()> {
-        // Load the schema registry index
-        let index_path = self.schema_path.join("index.json");
-        if !index_path.exists() {
-            return Err(ForgepointError::FileNotFound(format!(
-                "Schema index not found at {}",
-                index_path.display()
-            )));
-        }
+        let mut registry = Self::read_registry(&self.schema_source)?;
 
-        let index_content = fs::read_to_string(&index_path)?;
-        let registry: SchemaRegistry = serde_json::from_str(&index_content)?;
+        // Merge repository-local types, which may only replace an existing type
+        // when they say so explicitly
+        for schema_path in &self.additional_schema_paths {
+            let local_registry = Self::read_registry(&SchemaSource::Directory(schema_path.clone()))?;
 
-        // Load and compile each schema
-        for doc_type in &registry.document_types {
-            self.load_schema(&doc_type.doc_type, &doc_type.schema)?;
+            for definition in local_registry.document_types {
+                match registry.document_types.iter().position(|dt| dt.doc_type == definition.doc_type) {
+                    Some(_) if !definition.overrides => {
+                        return Err(ForgepointError::Schema(format!(
+                            "Document type '{}' in {} conflicts with an existing type; set \"overrides\": true to replace it",
+                            definition.doc_type,
+                            schema_path.join("index.json").display()
+                        )));
+                    }
+                    Some(index) => registry.document_types[index] = definition,
+                    None => registry.document_types.push(definition),
+                }
+            }
+            registry.schemas.extend(local_registry.schemas);
         }
 
+        // Register the index first so each schema can find its type definition
+        let document_types = registry.document_types.clone();
         self.registry = Some(registry);
+
+        // Load and compile each schema, plus any other versions kept alongside it
+        for doc_type in &document_types {
+            if let Some(mut compiled_schema) = self.load_schema(doc_type, &doc_type.schema)? {
+                compiled_schema.deprecated = doc_type
+                    .versions
+                    .iter()
+                    .any(|v| v.deprecated && (v.schema == doc_type.schema || Some(&v.version) == compiled_schema.version.as_ref()));
+                self.compiled_schemas.insert(doc_type.doc_type.clone(), compiled_schema);
+            }
+
+            for version in &doc_type.versions {
+                if version.schema == doc_type.schema {
+                    continue;
+                }
+
+                if let Some(mut compiled_schema) = self.load_schema(doc_type, &version.schema)? {
+                    compiled_schema.version = Some(version.version.clone());
+                    compiled_schema.deprecated = version.deprecated;
+                    self.versioned_schemas
+                        .entry(doc_type.doc_type.clone())
+                        .or_default()
+                        .insert(version.version.clone(), compiled_schema);
+                }
+            }
+        }
         
-        println!("Loaded {} schemas", self.compiled_schemas.len());
+        eprintln!("Loaded {} schemas", self.compiled_schemas.len());
         Ok(())
     }
 
-    /// Load a specific schema file
-    fn load_schema(&mut self, doc_type: &str, schema_file: &str) -> Result<()> {
-        let schema_path = self.schema_path.join(schema_file);
+    /// Read a schema directory's `index.json`
+    pub fn read_registry(source: &SchemaSource) -> Result<SchemaRegistry> {
+        let index_path = Path::new("index.json");
+        if !source.exists(index_path) {
+            return Err(ForgepointError::FileNotFound(format!(
+                "Schema index not found at {}",
+                source.display(index_path)
+            )));
+        }
+
+        let index_content = source.read(index_path)?;
+        let mut registry: SchemaRegistry = serde_json::from_str(&index_content)?;
+        for definition in &mut registry.document_types {
+            definition.source = source.clone();
+        }
+
+        Ok(registry)
+    }
+
+    /// Load and compile a specific schema file
+    fn load_schema(&self, definition: &DocumentTypeDefinition, schema_file: &str) -> Result<Option<CompiledSchema>> {
+        let doc_type = &definition.doc_type;
+        let schema_path = Path::new(schema_file);
         
-        if !schema_path.exists() {
-            eprintln!("Warning: Schema file not found: {}", schema_path.display());
-            return Ok(());
+        if !definition.source.exists(schema_path) {
+            eprintln!("Warning: Schema file not found: {}", definition.source.display(schema_path));
+            return Ok(None);
         }
 
-        let schema_content = fs::read_to_string(&schema_path)?;
-        let schema_json: Value = serde_json::from_str(&schema_content)?;
+        let schema_json = Self::read_schema(&definition.source, schema_path, &mut Vec::new())?;
 
         // Extract structural requirements
         let structural_requirements = schema_json
@@ -126,30 +674,86 @@ impl SchemaLoader {
                 title: None,
                 sections: None,
                 abstract_req: None,
+                conditions: None,
             });
 
         // Compile the JSON schema
-        let json_schema = JSONSchema::options()
-            .with_draft(Draft::Draft7)
-            .compile(&schema_json)
+        let json_schema = Self::compile_json_schema(&definition.source, &schema_json)
             .map_err(|e| ForgepointError::Schema(format!("Failed to compile schema for {}: {}", doc_type, e)))?;
 
-        // Find the document type definition
-        let definition = self
-            .registry
-            .as_ref()
-            .and_then(|r| r.document_types.iter().find(|dt| dt.doc_type == doc_type))
-            .cloned()
-            .ok_or_else(|| ForgepointError::Schema(format!("Document type definition not found: {}", doc_type)))?;
+        let version = schema_json
+            .pointer("/properties/schema-version/const")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let date_rules = schema_json
+            .get("dateRules")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
 
-        let compiled_schema = CompiledSchema {
-            definition,
+        let due_dates = schema_json
+            .get("dueDates")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
+        let lifecycle = schema_json
+            .get("lifecycle")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        Ok(Some(CompiledSchema {
+            definition: definition.clone(),
             json_schema,
+            schema_json,
             structural_requirements,
-        };
+            version,
+            deprecated: false,
+            date_rules,
+            due_dates,
+            lifecycle,
+        }))
+    }
 
-        self.compiled_schemas.insert(doc_type.to_string(), compiled_schema);
-        Ok(())
+    /// Compile a JSON schema as Draft 7, resolving `$ref`s against its source
+    pub fn compile_json_schema(source: &SchemaSource, schema_json: &Value) -> std::result::Result<JSONSchema, String> {
+        JSONSchema::options()
+            .with_draft(Draft::Draft7)
+            .should_validate_formats(true)
+            .with_resolver(SchemaFileResolver { source: source.clone() })
+            .compile(schema_json)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Read a schema file, merging in the chain of base schemas it `extends`
+    pub fn read_schema(source: &SchemaSource, schema_path: &Path, visited: &mut Vec<PathBuf>) -> Result<Value> {
+        if visited.iter().any(|path| path == schema_path) {
+            return Err(ForgepointError::Schema(format!(
+                "Schema {} extends itself",
+                source.display(schema_path)
+            )));
+        }
+        visited.push(schema_path.to_path_buf());
+
+        let schema_content = source.read(schema_path).map_err(|e| {
+            ForgepointError::Schema(format!("Failed to read schema {}: {}", source.display(schema_path), e))
+        })?;
+        let mut schema_json: Value = serde_json::from_str(&schema_content)?;
+
+        let base = schema_json
+            .as_object_mut()
+            .and_then(|schema| schema.remove("extends"));
+        match base {
+            Some(Value::String(base)) => {
+                let base_path = schema_path.parent().unwrap_or(Path::new("")).join(base);
+                let base_json = Self::read_schema(source, &base_path, visited)?;
+                Ok(merge_schemas(base_json, schema_json))
+            }
+            Some(other) => Err(ForgepointError::Schema(format!(
+                "Invalid extends in {}: expected a schema file name, found {}",
+                source.display(schema_path),
+                other
+            ))),
+            None => Ok(schema_json),
+        }
     }
 
     /// Get compiled schema for a document type
@@ -157,6 +761,36 @@ impl SchemaLoader {
         self.compiled_schemas.get(doc_type)
     }
 
+    /// Get the compiled schema matching a document's schema version, falling
+    /// back to the current schema when the document doesn't declare one
+    pub fn get_schema_for_version(&self, doc_type: &str, version: Option<&str>) -> Option<&CompiledSchema> {
+        let current = self.compiled_schemas.get(doc_type)?;
+
+        match version {
+            None => Some(current),
+            Some(version) if current.version.is_none() || current.version.as_deref() == Some(version) => {
+                Some(current)
+            }
+            Some(version) => self
+                .versioned_schemas
+                .get(doc_type)
+                .and_then(|versions| versions.get(version)),
+        }
+    }
+
+    /// Get every schema version available for a document type
+    pub fn get_schema_versions(&self, doc_type: &str) -> Vec<String> {
+        let mut versions: Vec<String> = self
+            .compiled_schemas
+            .get(doc_type)
+            .and_then(|s| s.version.clone())
+            .into_iter()
+            .chain(self.versioned_schemas.get(doc_type).into_iter().flat_map(|v| v.keys().cloned()))
+            .collect();
+        versions.sort();
+        versions
+    }
+
     /// Get all available document types
     pub fn get_document_types(&self) -> Vec<DocumentTypeDefinition> {
         self.registry
@@ -170,56 +804,231 @@ impl SchemaLoader {
         self.compiled_schemas.contains_key(doc_type)
     }
 
-    /// Validate document attributes against schema
-    pub fn validate_attributes(&self, doc_type: &str, attributes: &HashMap<String, String>) -> Result<Vec<String>> {
-        let schema = self
-            .get_schema(doc_type)
-            .ok_or_else(|| ForgepointError::InvalidDocumentType(doc_type.to_string()))?;
-
-        // Convert attributes to JSON for validation
-        let attributes_json = serde_json::to_value(attributes)?;
-
-        let validation_result = schema.json_schema.validate(&attributes_json);
-        
-        let mut errors = Vec::new();
-        if let Err(validation_errors) = validation_result {
-            for error in validation_errors {
-                errors.push(format!("Validation error at {}: {}", error.instance_path, error));
-            }
-        }
-
-        Ok(errors)
-    }
-
     /// Get required sections for a document type
     pub fn get_required_sections(&self, doc_type: &str) -> Vec<String> {
         self.get_schema(doc_type)
-            .and_then(|s| s.structural_requirements.sections.as_ref())
-            .and_then(|s| s.required.clone())
-            .unwrap_or_default()
-    }
-
-    /// Get optional sections for a document type
-    pub fn get_optional_sections(&self, doc_type: &str) -> Vec<String> {
-        self.get_schema(doc_type)
-            .and_then(|s| s.structural_requirements.sections.as_ref())
-            .and_then(|s| s.optional.clone())
+            .map(|s| s.required_sections())
             .unwrap_or_default()
     }
 
     /// Check if abstract is required for a document type
     pub fn is_abstract_required(&self, doc_type: &str) -> bool {
         self.get_schema(doc_type)
-            .and_then(|s| s.structural_requirements.abstract_req.as_ref())
-            .and_then(|a| a.required)
+            .map(|s| s.is_abstract_required())
             .unwrap_or(false)
     }
+}
+
+/// Describe a JSON Schema `if` that tests attribute values with `const` or
+/// `enum`, e.g. `:status: is superseded`
+pub fn describe_condition(if_schema: &Value) -> Option<String> {
+    let value_to_string = |value: &Value| match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    let mut properties: Vec<_> = if_schema.get("properties")?.as_object()?.iter().collect();
+    properties.sort_by(|a, b| a.0.cmp(b.0));
+
+    let parts: Vec<String> = properties
+        .into_iter()
+        .filter_map(|(name, property)| {
+            let values: Vec<String> = match (property.get("const"), property.get("enum")) {
+                (Some(value), _) => vec![value_to_string(value)],
+                (None, Some(Value::Array(values))) => values.iter().map(value_to_string).collect(),
+                _ => return None,
+            };
+            Some(format!(":{}: is {}", name, values.join(" or ")))
+        })
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" and "))
+}
+
+/// Convert an attribute's text to the JSON type its schema declares, so that
+/// `:number: 12` validates against `"type": "integer"`. Arrays are written as
+/// comma-separated values and objects as `key=value` pairs. With a list of
+/// types the first one the text converts to wins; text that converts to none
+/// stays a string and fails validation with a type error.
+pub fn coerce_attribute(value: &str, schema: &Value) -> Value {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(type_name)) => vec![type_name.as_str()],
+        Some(Value::Array(type_names)) => type_names.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    types
+        .into_iter()
+        .find_map(|type_name| coerce_as(value, type_name, schema))
+        .unwrap_or_else(|| Value::String(value.to_string()))
+}
+
+fn coerce_as(value: &str, type_name: &str, schema: &Value) -> Option<Value> {
+    match type_name {
+        "string" => Some(Value::String(value.to_string())),
+        "integer" => value.trim().parse::<i64>().ok().map(Value::from),
+        "number" => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        "boolean" => match value.trim() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        "array" => {
+            let items = schema.get("items").unwrap_or(&Value::Null);
+            Some(Value::Array(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| coerce_attribute(item, items))
+                    .collect(),
+            ))
+        }
+        "object" => {
+            let mut object = serde_json::Map::new();
+            for pair in value.split(',') {
+                let (key, item) = pair.split_once('=')?;
+                let key = key.trim();
+                let item_schema = schema.pointer(&format!("/properties/{}", key)).unwrap_or(&Value::Null);
+                object.insert(key.to_string(), coerce_attribute(item.trim(), item_schema));
+            }
+            Some(Value::Object(object))
+        }
+        _ => None,
+    }
+}
 
-    /// Get title format requirement for a document type
-    pub fn get_title_format(&self, doc_type: &str) -> Option<String> {
-        self.get_schema(doc_type)
-            .and_then(|s| s.structural_requirements.title.as_ref())
-            .and_then(|t| t.format.clone())
+/// How many `$ref`s deep `dereference` follows before giving up on a cycle
+const MAX_REF_DEPTH: usize = 32;
+
+/// Replace the `$ref`s in a schema with the schemas they point at: `#/...` in
+/// `root`, or another file read from `source` relative to `dir`. References
+/// that can't be followed are left in place.
+fn dereference(source: &SchemaSource, dir: &Path, root: &Value, schema: &Value, depth: usize) -> Value {
+    if depth > MAX_REF_DEPTH {
+        return schema.clone();
+    }
+    match schema {
+        Value::Object(object) => {
+            let Some(reference) = object.get("$ref").and_then(|r| r.as_str()) else {
+                return Value::Object(
+                    object
+                        .iter()
+                        .map(|(key, value)| (key.clone(), dereference(source, dir, root, value, depth + 1)))
+                        .collect(),
+                );
+            };
+            let (file, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+            let (target_root, target_dir) = if file.is_empty() {
+                (root.clone(), dir.to_path_buf())
+            } else {
+                let path = dir.join(file);
+                match source.read(&path).ok().and_then(|content| serde_json::from_str::<Value>(&content).ok()) {
+                    Some(json) => (json, path.parent().unwrap_or(Path::new("")).to_path_buf()),
+                    None => return schema.clone(),
+                }
+            };
+            match target_root.pointer(pointer) {
+                Some(target) => dereference(source, &target_dir, &target_root, target, depth + 1),
+                None => schema.clone(),
+            }
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| dereference(source, dir, root, item, depth + 1))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Parse a `date` or `date-time` attribute value
+pub fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|dt| dt.naive_utc()))
+}
+
+/// Add a named interval (`daily`, `weekly`, `biweekly`, `monthly`, `quarterly`,
+/// `annually`) to a date
+pub fn add_interval(date: NaiveDate, interval: &str) -> Option<NaiveDate> {
+    match interval.trim() {
+        "daily" => date.checked_add_days(Days::new(1)),
+        "weekly" => date.checked_add_days(Days::new(7)),
+        "biweekly" => date.checked_add_days(Days::new(14)),
+        "monthly" => date.checked_add_months(Months::new(1)),
+        "quarterly" => date.checked_add_months(Months::new(3)),
+        "annually" | "yearly" => date.checked_add_months(Months::new(12)),
+        _ => None,
+    }
+}
+
+/// Merge a schema over the base schema it extends: objects are merged key by
+/// key, `required`, `optional`, `dateRules` and `dueDates` lists are combined,
+/// and any other value in the schema replaces the base one
+fn merge_schemas(base: Value, schema: Value) -> Value {
+    match (base, schema) {
+        (Value::Object(mut base), Value::Object(schema)) => {
+            for (key, value) in schema {
+                let merged = match (base.remove(&key), value) {
+                    (Some(Value::Array(mut base_items)), Value::Array(items))
+                        if matches!(
+                            key.as_str(),
+                            "required" | "optional" | "allOf" | "conditions" | "dateRules" | "dueDates"
+                        ) =>
+                    {
+                        for item in items {
+                            if !base_items.contains(&item) {
+                                base_items.push(item);
+                            }
+                        }
+                        Value::Array(base_items)
+                    }
+                    (Some(base_value), value) => merge_schemas(base_value, value),
+                    (None, value) => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, schema) => schema,
+    }
+}
+
+/// Resolves `$ref`s to other files in the schema directory, relative to the
+/// referencing schema's `$id` when it has one
+struct SchemaFileResolver {
+    source: SchemaSource,
+}
+
+impl SchemaResolver for SchemaFileResolver {
+    fn resolve(&self, root_schema: &Value, url: &Url, original_reference: &str) -> std::result::Result<Arc<Value>, SchemaResolverError> {
+        let base = root_schema
+            .get("$id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Url::parse(id).ok());
+
+        let relative = match url.scheme() {
+            "json-schema" => url.path().trim_start_matches('/').to_string(),
+            "file" => url.path().to_string(),
+            _ => base
+                .and_then(|base| base.make_relative(url))
+                .unwrap_or_else(|| original_reference.to_string()),
+        };
+        let relative = relative.split('#').next().unwrap_or_default();
+
+        let content = self
+            .source
+            .read(Path::new(relative))
+            .map_err(|e| anyhow::anyhow!("Failed to resolve {}: {}", url, e))?;
+        Ok(Arc::new(serde_json::from_str(&content)?))
     }
 }
 
@@ -229,26 +1038,30 @@ mod tests {
     use tempfile::TempDir;
     use std::fs;
 
-    #[test]
-    fn test_schema_loading() {
-        let temp_dir = TempDir::new().unwrap();
-        let schema_dir = temp_dir.path();
-
-        // Create a simple schema index
-        let index = r#"{
+    /// Write a schema index registering `doc_type` with `<doc_type>.json`,
+    /// and the schema files as (file name, content) pairs
+    fn write_schema_set(schema_dir: &Path, doc_type: &str, files: &[(&str, &str)]) {
+        let index = serde_json::json!({
             "schemaVersion": "1.0",
-            "schemas": {
-                "story": { "$ref": "story.json" }
-            },
+            "schemas": { doc_type: { "$ref": format!("{}.json", doc_type) } },
             "documentTypes": [{
-                "type": "story",
-                "name": "User Story", 
-                "description": "Test story",
+                "type": doc_type,
+                "name": doc_type,
+                "description": format!("Test {}", doc_type),
                 "category": "design",
-                "schema": "story.json"
+                "schema": format!("{}.json", doc_type),
             }]
-        }"#;
-        fs::write(schema_dir.join("index.json"), index).unwrap();
+        });
+        fs::write(schema_dir.join("index.json"), index.to_string()).unwrap();
+        for (file, content) in files {
+            fs::write(schema_dir.join(file), content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_schema_loading() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
 
         // Create a simple story schema
         let story_schema = r#"{
@@ -260,11 +1073,306 @@ mod tests {
             },
             "required": ["forgepoint-type", "id"]
         }"#;
-        fs::write(schema_dir.join("story.json"), story_schema).unwrap();
+        write_schema_set(schema_dir, "story", &[("story.json", story_schema)]);
 
         let mut loader = SchemaLoader::new(schema_dir);
         assert!(loader.load_schemas().is_ok());
         assert!(loader.is_valid_document_type("story"));
         assert!(!loader.is_valid_document_type("invalid"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_validate_attributes_structured_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+
+        let story_schema = r#"{
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "pattern": "^[a-z0-9-]+$" },
+                "status": { "enum": ["draft", "done"] },
+                "owner": { "type": "string" }
+            },
+            "required": ["id", "owner"],
+            "additionalProperties": false
+        }"#;
+        write_schema_set(schema_dir, "story", &[("story.json", story_schema)]);
+
+        let mut loader = SchemaLoader::new(schema_dir);
+        loader.load_schemas().unwrap();
+
+        let attributes: HashMap<String, String> = [("id", "Bad_ID"), ("status", "open"), ("ownr", "jane")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut errors = loader.get_schema("story").unwrap().validate_attributes(&attributes).unwrap();
+        errors.sort_by(|a, b| a.keyword.cmp(&b.keyword));
+
+        let summary: Vec<(&str, Option<&str>, Vec<&str>)> = errors
+            .iter()
+            .map(|e| {
+                (
+                    e.keyword.as_str(),
+                    e.attribute.as_deref(),
+                    e.expected.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("additionalProperties", Some("ownr"), vec!["id", "owner", "status"]),
+                ("enum", Some("status"), vec!["draft", "done"]),
+                ("pattern", Some("id"), vec!["^[a-z0-9-]+$"]),
+                ("required", Some("owner"), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_additional_schema_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let builtin_dir = temp_dir.path().join("builtin");
+        let local_dir = temp_dir.path().join("local");
+        fs::create_dir_all(&builtin_dir).unwrap();
+        fs::create_dir_all(&local_dir).unwrap();
+
+        let schema = r#"{ "type": "object", "properties": { "id": { "type": "string" } } }"#;
+        fs::write(
+            builtin_dir.join("index.json"),
+            r#"{ "documentTypes": [
+                { "type": "story", "name": "User Story", "description": "Story", "category": "design", "schema": "story.json" }
+            ] }"#,
+        )
+        .unwrap();
+        fs::write(builtin_dir.join("story.json"), schema).unwrap();
+        fs::write(local_dir.join("escalation.json"), schema).unwrap();
+        fs::write(local_dir.join("story.json"), schema).unwrap();
+
+        let write_local_index = |story_overrides: Option<bool>| {
+            let story = story_overrides
+                .map(|overrides| {
+                    format!(
+                        r#", {{ "type": "story", "name": "Team Story", "description": "Story", "category": "design", "schema": "story.json", "overrides": {} }}"#,
+                        overrides
+                    )
+                })
+                .unwrap_or_default();
+            fs::write(
+                local_dir.join("index.json"),
+                format!(
+                    r#"{{ "documentTypes": [
+                        {{ "type": "customer-escalation", "name": "Customer Escalation", "description": "Escalation", "category": "support", "schema": "escalation.json" }}{}
+                    ] }}"#,
+                    story
+                ),
+            )
+            .unwrap();
+        };
+        let load = || {
+            let mut loader = SchemaLoader::new(&builtin_dir);
+            loader.add_schema_path(&local_dir);
+            loader.load_schemas().map(|_| loader)
+        };
+
+        write_local_index(None);
+        let loader = load().unwrap();
+        assert!(loader.is_valid_document_type("story"));
+        assert!(loader.is_valid_document_type("customer-escalation"));
+        assert_eq!(loader.get_schema("customer-escalation").unwrap().definition.source, SchemaSource::Directory(local_dir.clone()));
+
+        write_local_index(Some(false));
+        let error = load().err().unwrap().to_string();
+        assert!(error.contains("Document type 'story'"), "{}", error);
+
+        write_local_index(Some(true));
+        let loader = load().unwrap();
+        assert_eq!(loader.get_schema("story").unwrap().definition.name, "Team Story");
+        assert_eq!(loader.get_document_types().len(), 2);
+    }
+
+    #[test]
+    fn test_schema_inheritance() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+
+        let common = r#"{ "definitions": { "status": { "type": "string", "enum": ["draft", "done"] } } }"#;
+        let base = r#"{
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "schema-version": { "const": "1.0" }
+            },
+            "required": ["id", "schema-version"],
+            "structuralRequirements": { "sections": { "required": ["Summary"], "optional": ["Notes"] } }
+        }"#;
+        let story = r#"{
+            "$id": "https://forgepoint.dev/schemas/story.json",
+            "extends": "base.json",
+            "properties": {
+                "status": { "$ref": "common.json#/definitions/status" }
+            },
+            "required": ["status"],
+            "structuralRequirements": { "sections": { "required": ["Acceptance Criteria"] } }
+        }"#;
+        write_schema_set(schema_dir, "story", &[("common.json", common), ("base.json", base), ("story.json", story)]);
+
+        let mut loader = SchemaLoader::new(schema_dir);
+        loader.load_schemas().unwrap();
+        let schema = loader.get_schema("story").unwrap();
+
+        assert_eq!(schema.version.as_deref(), Some("1.0"));
+        assert_eq!(schema.required_sections(), vec!["Summary", "Acceptance Criteria"]);
+        assert_eq!(schema.optional_sections(), vec!["Notes"]);
+
+        let attributes: HashMap<String, String> = [("id", "login"), ("status", "started")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut errors: Vec<(String, Option<String>)> = schema
+            .validate_attributes(&attributes)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.keyword, e.attribute))
+            .collect();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                ("enum".to_string(), Some("status".to_string())),
+                ("required".to_string(), Some("schema-version".to_string())),
+            ]
+        );
+
+        fs::write(schema_dir.join("base.json"), r#"{ "extends": "story.json" }"#).unwrap();
+        let error = SchemaLoader::new(schema_dir).load_schemas().err().unwrap().to_string();
+        assert!(error.contains("extends itself"), "{}", error);
+    }
+
+    #[test]
+    fn test_embedded_schemas() {
+        let mut loader = SchemaLoader::embedded();
+        loader.load_schemas().unwrap();
+
+        let document_types = loader.get_document_types();
+        assert_eq!(document_types.len(), 37);
+        for document_type in &document_types {
+            assert!(loader.is_valid_document_type(&document_type.doc_type), "{} did not compile", document_type.doc_type);
+        }
+
+        assert!(SchemaSource::Embedded.exists(Path::new("./story.json")));
+        assert_eq!(SchemaSource::Embedded.list(Path::new("")).len(), EMBEDDED_SCHEMAS.len());
+    }
+
+    #[test]
+    fn test_coerce_attribute() {
+        let schema: Value = serde_json::from_str(
+            r#"{
+                "properties": {
+                    "number": { "type": "integer" },
+                    "ratio": { "type": "number" },
+                    "active": { "type": "boolean" },
+                    "deciders": { "type": "array", "items": { "type": "string" } },
+                    "summary": { "type": "object", "properties": { "total": { "type": "integer" } } },
+                    "title": { "type": "string" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let coerce = |name: &str, value: &str| coerce_attribute(value, &schema["properties"][name]);
+
+        assert_eq!(coerce("number", "12"), serde_json::json!(12));
+        assert_eq!(coerce("number", "twelve"), serde_json::json!("twelve"));
+        assert_eq!(coerce("ratio", "0.5"), serde_json::json!(0.5));
+        assert_eq!(coerce("active", "true"), serde_json::json!(true));
+        assert_eq!(coerce("deciders", "alice, bob"), serde_json::json!(["alice", "bob"]));
+        assert_eq!(
+            coerce("summary", "total=10, passed=8"),
+            serde_json::json!({ "total": 10, "passed": "8" })
+        );
+        assert_eq!(coerce("summary", "ten"), serde_json::json!("ten"));
+        assert_eq!(coerce("title", "12"), serde_json::json!("12"));
+
+        let limit = serde_json::json!({ "type": ["integer", "string"] });
+        assert_eq!(coerce_attribute("30", &limit), serde_json::json!(30));
+        assert_eq!(coerce_attribute("unlimited", &limit), serde_json::json!("unlimited"));
+        let optional = serde_json::json!({ "type": ["boolean", "null"] });
+        assert_eq!(coerce_attribute("false", &optional), serde_json::json!(false));
+        assert_eq!(coerce_attribute("maybe", &optional), serde_json::json!("maybe"));
+    }
+
+    #[test]
+    fn test_validate_attributes_coerces_through_refs() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+        let common = r#"{ "definitions": { "points": { "type": "integer", "minimum": 0 }, "names": { "type": "array", "items": { "type": "string" } } } }"#;
+        let sprint_plan = r##"{
+            "$id": "https://forgepoint.dev/schemas/sprint-plan.json",
+            "type": "object",
+            "definitions": { "flag": { "type": "boolean" } },
+            "properties": {
+                "capacity": { "$ref": "common.json#/definitions/points" },
+                "members": { "$ref": "common.json#/definitions/names" },
+                "stretch": { "$ref": "#/definitions/flag" },
+                "length": { "type": ["integer", "string"], "maximum": 30 }
+            }
+        }"##;
+        write_schema_set(schema_dir, "sprint-plan", &[("common.json", common), ("sprint-plan.json", sprint_plan)]);
+
+        let mut loader = SchemaLoader::new(schema_dir);
+        loader.load_schemas().unwrap();
+        let schema = loader.get_schema("sprint-plan").unwrap();
+        assert_eq!(schema.attribute_schema("capacity"), Some(serde_json::json!({ "type": "integer", "minimum": 0 })));
+
+        let errors = |pairs: &[(&str, &str)]| -> Vec<(String, Option<String>)> {
+            let attributes: HashMap<String, String> =
+                pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            let mut errors: Vec<_> = schema
+                .validate_attributes(&attributes)
+                .unwrap()
+                .into_iter()
+                .map(|e| (e.keyword, e.attribute))
+                .collect();
+            errors.sort();
+            errors
+        };
+        assert!(errors(&[("capacity", "13"), ("members", "ana, bo"), ("stretch", "true"), ("length", "14")]).is_empty());
+        assert!(errors(&[("length", "two weeks")]).is_empty());
+        assert_eq!(
+            errors(&[("capacity", "-1"), ("stretch", "yes"), ("length", "60")]),
+            vec![
+                ("maximum".to_string(), Some("length".to_string())),
+                ("minimum".to_string(), Some("capacity".to_string())),
+                ("type".to_string(), Some("stretch".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let lifecycle: Lifecycle = serde_json::from_str(
+            r#"{
+                "transitions": {
+                    "draft": ["review"],
+                    "review": ["draft", "approved"],
+                    "approved": ["archived"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(lifecycle.attribute, "status");
+        assert!(lifecycle.allows("draft", "review"));
+        assert!(lifecycle.allows("draft", "draft"));
+        assert!(!lifecycle.allows("draft", "archived"));
+        assert!(!lifecycle.allows("archived", "draft"));
+        assert!(lifecycle.knows("archived"));
+        assert!(!lifecycle.knows("done"));
+        assert_eq!(
+            lifecycle.path("draft", "archived"),
+            Some(vec!["draft", "review", "approved", "archived"])
+        );
+        assert_eq!(lifecycle.path("archived", "draft"), None);
+    }
+}
@@ -0,0 +1,713 @@
+use crate::schema::{
+    DateRule, DueDateRule, Lifecycle, SchemaLoader, SchemaRef, SchemaRegistry, SchemaSource, SectionRequirements, StructuralCondition,
+    StructuralRequirements,
+    BUILTIN_CATEGORIES,
+};
+use crate::suggest::{closest_match, did_you_mean};
+use crate::validator::Severity;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// structuralRequirements keys that describe conventions for readers rather
+/// than rules the linter enforces
+const DESCRIPTIVE_KEYS: &[&str] = &["versionSections", "length"];
+
+/// A problem found in a schema set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaIssue {
+    pub severity: Severity,
+    /// The schema file the issue was found in
+    pub file: String,
+    pub document_type: Option<String>,
+    pub message: String,
+}
+
+/// Checks a schema directory: its registry, and each schema it lists
+pub struct SchemaChecker<'a> {
+    source: &'a SchemaSource,
+    /// Repository-local schema sets may introduce their own categories
+    local: bool,
+    issues: Vec<SchemaIssue>,
+}
+
+impl<'a> SchemaChecker<'a> {
+    pub fn new(source: &'a SchemaSource, local: bool) -> Self {
+        Self {
+            source,
+            local,
+            issues: Vec::new(),
+        }
+    }
+
+    /// Run every check and return the issues found
+    pub fn check(mut self) -> Vec<SchemaIssue> {
+        let index_path = Path::new("index.json");
+        let index = match self.source.read(index_path) {
+            Ok(content) => content,
+            Err(e) => {
+                self.error(index_path, None, format!("Cannot read the schema index: {}", e));
+                return self.issues;
+            }
+        };
+        let index_json: Value = match serde_json::from_str(&index) {
+            Ok(json) => json,
+            Err(e) => {
+                self.error(index_path, None, format!("Invalid JSON: {}", e));
+                return self.issues;
+            }
+        };
+        let registry: SchemaRegistry = match serde_json::from_value(index_json.clone()) {
+            Ok(registry) => registry,
+            Err(e) => {
+                self.error(index_path, None, format!("Invalid schema index: {}", e));
+                return self.issues;
+            }
+        };
+
+        self.check_registry(&registry, &index_json);
+
+        for definition in &registry.document_types {
+            let doc_type = Some(definition.doc_type.as_str());
+            self.check_category(&definition.category, doc_type);
+            self.check_schema_file(&definition.schema, &definition.doc_type);
+
+            for version in definition.versions.iter().filter(|v| v.schema != definition.schema) {
+                self.check_schema_file(&version.schema, &definition.doc_type);
+            }
+        }
+
+        self.issues
+    }
+
+    /// Check that `documentTypes` and the `schemas` map describe the same types
+    fn check_registry(&mut self, registry: &SchemaRegistry, index_json: &Value) {
+        let index_path = Path::new("index.json");
+
+        let mut seen = HashSet::new();
+        for definition in &registry.document_types {
+            if !seen.insert(definition.doc_type.as_str()) {
+                self.error(
+                    index_path,
+                    Some(&definition.doc_type),
+                    format!("Document type '{}' is listed more than once", definition.doc_type),
+                );
+            }
+        }
+
+        // The built-in index describes the map as a JSON Schema under `properties`
+        let schemas: HashMap<String, SchemaRef> = if registry.schemas.is_empty() {
+            index_json
+                .pointer("/properties/schemas/properties")
+                .and_then(|map| serde_json::from_value(map.clone()).ok())
+                .unwrap_or_default()
+        } else {
+            registry.schemas.clone()
+        };
+        if schemas.is_empty() {
+            return;
+        }
+
+        for definition in &registry.document_types {
+            match schemas.get(&definition.doc_type) {
+                None => self.error(
+                    index_path,
+                    Some(&definition.doc_type),
+                    format!("Document type '{}' is missing from the schemas map", definition.doc_type),
+                ),
+                Some(schema_ref) if schema_ref.reference != definition.schema => self.error(
+                    index_path,
+                    Some(&definition.doc_type),
+                    format!(
+                        "The schemas map points '{}' at {} but documentTypes uses {}",
+                        definition.doc_type, schema_ref.reference, definition.schema
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+
+        let mut unlisted: Vec<&String> = schemas.keys().filter(|name| !seen.contains(name.as_str())).collect();
+        unlisted.sort();
+        for name in unlisted {
+            self.error(
+                index_path,
+                Some(name),
+                format!("Schemas map entry '{}' has no documentTypes entry", name),
+            );
+        }
+    }
+
+    fn check_category(&mut self, category: &str, doc_type: Option<&str>) {
+        if BUILTIN_CATEGORIES.contains(&category) {
+            return;
+        }
+
+        let suggestion = closest_match(category, BUILTIN_CATEGORIES.iter().copied());
+        let message = match suggestion.and_then(|s| did_you_mean(&[s])) {
+            Some(did_you_mean) => format!("Unknown category '{}'. {}", category, did_you_mean),
+            None => format!(
+                "Unknown category '{}', expected one of: {}",
+                category,
+                BUILTIN_CATEGORIES.join(", ")
+            ),
+        };
+
+        // Local types may add categories, so only flag likely typos there
+        if !self.local {
+            self.error(Path::new("index.json"), doc_type, message);
+        } else if suggestion.is_some() {
+            self.warning(Path::new("index.json"), doc_type, message);
+        }
+    }
+
+    /// Check that a schema file exists, compiles and is consistent with its type
+    fn check_schema_file(&mut self, schema_file: &str, doc_type: &str) {
+        let path = Path::new(schema_file);
+        if !self.source.exists(path) {
+            self.error(path, Some(doc_type), format!("Schema file {} does not exist", schema_file));
+            return;
+        }
+
+        let schema_json = match SchemaLoader::read_schema(self.source, path, &mut Vec::new()) {
+            Ok(json) => json,
+            Err(e) => {
+                self.error(path, Some(doc_type), e.to_string());
+                return;
+            }
+        };
+
+        if let Err(e) = SchemaLoader::compile_json_schema(self.source, &schema_json) {
+            self.error(path, Some(doc_type), format!("Schema does not compile: {}", e));
+        }
+
+        match schema_json.pointer("/properties/forgepoint-type/const").and_then(|v| v.as_str()) {
+            Some(declared) if declared != doc_type => self.error(
+                path,
+                Some(doc_type),
+                format!("forgepoint-type is fixed to '{}' but the registry type is '{}'", declared, doc_type),
+            ),
+            Some(_) => {}
+            None => self.warning(path, Some(doc_type), "forgepoint-type has no const value".to_string()),
+        }
+
+        if let Some(structural) = schema_json.get("structuralRequirements") {
+            self.check_structural_requirements(path, doc_type, &schema_json, structural);
+        }
+
+        // An `if` on an attribute the schema doesn't define never matches
+        let conditions = schema_json.get("allOf").and_then(|a| a.as_array()).into_iter().flatten();
+        for properties in conditions.filter_map(|c| c.pointer("/if/properties").and_then(|p| p.as_object())) {
+            for attribute in properties.keys().filter(|a| schema_json.pointer(&format!("/properties/{}", a)).is_none()) {
+                self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Condition refers to unknown attribute '{}'", attribute),
+                );
+            }
+        }
+
+        if let Some(date_rules) = schema_json.get("dateRules") {
+            self.check_date_rules(path, doc_type, &schema_json, date_rules);
+        }
+
+        if let Some(due_dates) = schema_json.get("dueDates") {
+            self.check_due_dates(path, doc_type, &schema_json, due_dates);
+        }
+
+        if let Some(lifecycle) = schema_json.get("lifecycle") {
+            self.check_lifecycle(path, doc_type, &schema_json, lifecycle);
+        }
+    }
+
+    fn check_lifecycle(&mut self, path: &Path, doc_type: &str, schema_json: &Value, lifecycle_json: &Value) {
+        let lifecycle: Lifecycle = match serde_json::from_value(lifecycle_json.clone()) {
+            Ok(lifecycle) => lifecycle,
+            Err(e) => {
+                self.error(path, Some(doc_type), format!("Invalid lifecycle: {}", e));
+                return;
+            }
+        };
+
+        let parsed = serde_json::to_value(&lifecycle).unwrap_or(Value::Null);
+        for key in unknown_keys(lifecycle_json, &parsed, "lifecycle") {
+            self.error(path, Some(doc_type), format!("Unknown key {}", key));
+        }
+
+        let Some(property) = schema_json.pointer(&format!("/properties/{}", lifecycle.attribute)) else {
+            self.error(
+                path,
+                Some(doc_type),
+                format!("Lifecycle refers to unknown attribute '{}'", lifecycle.attribute),
+            );
+            return;
+        };
+        let Some(states) = property.get("enum").and_then(|e| e.as_array()) else {
+            return;
+        };
+        let states: Vec<&str> = states.iter().filter_map(|s| s.as_str()).collect();
+
+        let mut lifecycle_states: Vec<&String> = lifecycle
+            .transitions
+            .iter()
+            .flat_map(|(state, next)| std::iter::once(state).chain(next))
+            .collect();
+        lifecycle_states.sort();
+        lifecycle_states.dedup();
+        for state in lifecycle_states.into_iter().filter(|s| !states.contains(&s.as_str())) {
+            self.error(
+                path,
+                Some(doc_type),
+                format!("Lifecycle state '{}' is not an allowed {} value", state, lifecycle.attribute),
+            );
+        }
+
+        for state in states.iter().filter(|s| !lifecycle.knows(s)) {
+            self.warning(
+                path,
+                Some(doc_type),
+                format!("The {} value '{}' has no lifecycle entry", lifecycle.attribute, state),
+            );
+        }
+    }
+
+    fn check_due_dates(&mut self, path: &Path, doc_type: &str, schema_json: &Value, due_dates: &Value) {
+        let rules: Vec<DueDateRule> = match serde_json::from_value(due_dates.clone()) {
+            Ok(rules) => rules,
+            Err(e) => {
+                self.error(path, Some(doc_type), format!("Invalid dueDates: {}", e));
+                return;
+            }
+        };
+
+        let parsed = serde_json::to_value(&rules).unwrap_or(Value::Null);
+        for (index, rule) in rules.iter().enumerate() {
+            for key in unknown_keys(&due_dates[index], &parsed[index], &format!("dueDates[{}]", index)) {
+                self.error(path, Some(doc_type), format!("Unknown key {}", key));
+            }
+
+            match schema_json
+                .pointer(&format!("/properties/{}", rule.attribute))
+                .map(|property| property.get("format").and_then(|f| f.as_str()))
+            {
+                None => self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Due date refers to unknown attribute '{}'", rule.attribute),
+                ),
+                Some(Some("date" | "date-time")) => {}
+                Some(_) => self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Due date attribute '{}' is not a date or date-time", rule.attribute),
+                ),
+            }
+
+            if let Some(every) = &rule.every {
+                if schema_json.pointer(&format!("/properties/{}", every)).is_none() {
+                    self.error(
+                        path,
+                        Some(doc_type),
+                        format!("Due date interval refers to unknown attribute '{}'", every),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_date_rules(&mut self, path: &Path, doc_type: &str, schema_json: &Value, date_rules: &Value) {
+        let rules: Vec<DateRule> = match serde_json::from_value(date_rules.clone()) {
+            Ok(rules) => rules,
+            Err(e) => {
+                self.error(path, Some(doc_type), format!("Invalid dateRules: {}", e));
+                return;
+            }
+        };
+
+        let parsed = serde_json::to_value(&rules).unwrap_or(Value::Null);
+        for (index, rule) in rules.iter().enumerate() {
+            for key in unknown_keys(&date_rules[index], &parsed[index], &format!("dateRules[{}]", index)) {
+                self.error(path, Some(doc_type), format!("Unknown key {}", key));
+            }
+
+            let Some((reference, _)) = rule.reference() else {
+                self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Date rule for '{}' needs exactly one of after or onOrAfter", rule.attribute),
+                );
+                continue;
+            };
+
+            for attribute in [rule.attribute.as_str(), reference] {
+                match schema_json
+                    .pointer(&format!("/properties/{}", attribute))
+                    .map(|property| property.get("format").and_then(|f| f.as_str()))
+                {
+                    None => self.error(
+                        path,
+                        Some(doc_type),
+                        format!("Date rule refers to unknown attribute '{}'", attribute),
+                    ),
+                    Some(Some("date" | "date-time")) => {}
+                    Some(_) => self.error(
+                        path,
+                        Some(doc_type),
+                        format!("Date rule attribute '{}' is not a date or date-time", attribute),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn check_structural_requirements(&mut self, path: &Path, doc_type: &str, schema_json: &Value, structural: &Value) {
+        let requirements: StructuralRequirements = match serde_json::from_value(structural.clone()) {
+            Ok(requirements) => requirements,
+            Err(e) => {
+                self.error(path, Some(doc_type), format!("Invalid structuralRequirements: {}", e));
+                return;
+            }
+        };
+
+        // Anything that doesn't survive a round trip through the struct is ignored by the tool
+        let parsed = serde_json::to_value(&requirements).unwrap_or(Value::Null);
+        let descriptive: Vec<String> = DESCRIPTIVE_KEYS.iter().map(|key| format!("structuralRequirements.{}", key)).collect();
+        for key in unknown_keys(structural, &parsed, "structuralRequirements") {
+            if descriptive.contains(&key) {
+                continue;
+            }
+            self.error(path, Some(doc_type), format!("Unknown key {}", key));
+        }
+
+        if let Some(sections) = &requirements.sections {
+            self.check_sections(path, doc_type, sections, "sections");
+        }
+
+        for (index, condition) in requirements.conditions.iter().flatten().enumerate() {
+            self.check_condition(path, doc_type, schema_json, index, condition);
+        }
+    }
+
+    fn check_condition(
+        &mut self,
+        path: &Path,
+        doc_type: &str,
+        schema_json: &Value,
+        index: usize,
+        condition: &StructuralCondition,
+    ) {
+        if condition.when.is_empty() {
+            self.error(path, Some(doc_type), format!("Condition {} has an empty if", index));
+        }
+
+        let mut attributes: Vec<_> = condition.when.iter().collect();
+        attributes.sort();
+        for (attribute, values) in attributes {
+            let Some(property) = schema_json.pointer(&format!("/properties/{}", attribute)) else {
+                self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Condition refers to unknown attribute '{}'", attribute),
+                );
+                continue;
+            };
+            let Some(allowed) = property.get("enum").and_then(|e| e.as_array()) else {
+                continue;
+            };
+            for value in values.iter().filter(|value| !allowed.iter().any(|a| a.as_str() == Some(value.as_str()))) {
+                self.error(
+                    path,
+                    Some(doc_type),
+                    format!("Condition value '{}' is not an allowed {} value", value, attribute),
+                );
+            }
+        }
+
+        match &condition.then.sections {
+            Some(sections) => self.check_sections(path, doc_type, sections, &format!("condition {}", index)),
+            None => self.warning(path, Some(doc_type), format!("Condition {} has no requirements", index)),
+        }
+    }
+
+    fn check_sections(&mut self, path: &Path, doc_type: &str, sections: &SectionRequirements, context: &str) {
+        let optional = sections.optional.clone().unwrap_or_default();
+        for section in sections.required.iter().flatten().filter(|s| optional.contains(s)) {
+            self.error(
+                path,
+                Some(doc_type),
+                format!("Section '{}' is both required and optional in {}", section, context),
+            );
+        }
+
+        let mut content: Vec<_> = sections.content.iter().flatten().collect();
+        content.sort_by(|a, b| a.0.cmp(b.0));
+        for (title, requirement) in content {
+            if let Some(subsections) = &requirement.subsections {
+                self.check_sections(path, doc_type, subsections, &format!("'{}' subsections", title));
+            }
+        }
+    }
+
+    fn error(&mut self, file: &Path, doc_type: Option<&str>, message: String) {
+        self.push(Severity::Error, file, doc_type, message);
+    }
+
+    fn warning(&mut self, file: &Path, doc_type: Option<&str>, message: String) {
+        self.push(Severity::Warning, file, doc_type, message);
+    }
+
+    fn push(&mut self, severity: Severity, file: &Path, doc_type: Option<&str>, message: String) {
+        self.issues.push(SchemaIssue {
+            severity,
+            file: self.source.display(file),
+            document_type: doc_type.map(String::from),
+            message,
+        });
+    }
+}
+
+/// Keys of `original` that are missing from `parsed`, as dotted paths
+fn unknown_keys(original: &Value, parsed: &Value, path: &str) -> Vec<String> {
+    let (Value::Object(original), Value::Object(parsed)) = (original, parsed) else {
+        return Vec::new();
+    };
+
+    let mut keys: Vec<&String> = original.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .flat_map(|key| {
+            let key_path = format!("{}.{}", path, key);
+            match (&original[key], parsed.get(key)) {
+                (Value::Array(items), Some(Value::Array(parsed_items))) => items
+                    .iter()
+                    .zip(parsed_items)
+                    .enumerate()
+                    .flat_map(|(index, (item, parsed_item))| {
+                        unknown_keys(item, parsed_item, &format!("{}[{}]", key_path, index))
+                    })
+                    .collect(),
+                (original_value, Some(parsed_value)) => unknown_keys(original_value, parsed_value, &key_path),
+                (_, None) => vec![key_path],
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn messages(issues: &[SchemaIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_builtin_schemas_pass() {
+        let issues = SchemaChecker::new(&SchemaSource::Embedded, false).check();
+        assert!(issues.is_empty(), "{:?}", messages(&issues));
+    }
+
+    #[test]
+    fn test_schema_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+        fs::write(
+            schema_dir.join("index.json"),
+            r#"{
+                "schemas": {
+                    "story": { "$ref": "story.json" },
+                    "epic": { "$ref": "epics.json" },
+                    "task": { "$ref": "task.json" }
+                },
+                "documentTypes": [
+                    { "type": "story", "name": "Story", "description": "Story", "category": "desgin", "schema": "story.json" },
+                    { "type": "epic", "name": "Epic", "description": "Epic", "category": "design", "schema": "epic.json" },
+                    { "type": "bug", "name": "Bug", "description": "Bug", "category": "testing", "schema": "bug.json" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            schema_dir.join("story.json"),
+            r#"{
+                "properties": { "forgepoint-type": { "const": "user-story" } },
+                "structuralRequirements": {
+                    "length": { "maximum": "1 page" },
+                    "pageLimit": 1,
+                    "sections": {
+                        "required": ["Acceptance Criteria"],
+                        "optional": ["Acceptance Criteria", "Notes"],
+                        "content": { "Notes": { "minWord": 10 } }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            schema_dir.join("epic.json"),
+            r#"{
+                "properties": { "forgepoint-type": { "const": "epic" }, "status": { "type": "unknown" } }
+            }"#,
+        )
+        .unwrap();
+
+        let issues = SchemaChecker::new(&SchemaSource::Directory(schema_dir.to_path_buf()), false).check();
+        let messages = messages(&issues);
+
+        assert!(messages.contains(&"The schemas map points 'epic' at epics.json but documentTypes uses epic.json"));
+        assert!(messages.contains(&"Document type 'bug' is missing from the schemas map"));
+        assert!(messages.contains(&"Schemas map entry 'task' has no documentTypes entry"));
+        assert!(messages.contains(&"Unknown category 'desgin'. Did you mean 'design'?"));
+        assert!(messages.contains(&"Schema file bug.json does not exist"));
+        assert!(messages.contains(&"forgepoint-type is fixed to 'user-story' but the registry type is 'story'"));
+        assert!(messages.contains(&"Unknown key structuralRequirements.pageLimit"));
+        assert!(!messages.contains(&"Unknown key structuralRequirements.length"));
+        assert!(messages.contains(&"Unknown key structuralRequirements.sections.content.Notes.minWord"));
+        assert!(messages.contains(&"Section 'Acceptance Criteria' is both required and optional in sections"));
+        assert!(messages.iter().any(|m| m.starts_with("Schema does not compile")));
+        assert_eq!(issues.len(), 10, "{:?}", messages);
+    }
+
+    #[test]
+    fn test_check_date_rules_and_due_dates() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+        fs::write(
+            schema_dir.join("index.json"),
+            r#"{
+                "documentTypes": [
+                    { "type": "sprint", "name": "Sprint", "description": "Sprint", "category": "development", "schema": "sprint.json" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            schema_dir.join("sprint.json"),
+            r#"{
+                "properties": {
+                    "forgepoint-type": { "const": "sprint" },
+                    "start": { "type": "string", "format": "date" },
+                    "end": { "type": "string", "format": "date" },
+                    "team": { "type": "string" }
+                },
+                "dateRules": [
+                    { "attribute": "end", "after": "start" },
+                    { "attribute": "end", "after": "start", "onOrAfter": "start" },
+                    { "attribute": "end", "onOrAfter": "team" },
+                    { "attribute": "end", "after": "begin", "inclusive": true }
+                ],
+                "dueDates": [
+                    { "attribute": "end", "every": "team" },
+                    { "attribute": "team", "every": "cadence" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let issues = SchemaChecker::new(&SchemaSource::Directory(schema_dir.to_path_buf()), false).check();
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "Date rule for 'end' needs exactly one of after or onOrAfter",
+                "Date rule attribute 'team' is not a date or date-time",
+                "Unknown key dateRules[3].inclusive",
+                "Date rule refers to unknown attribute 'begin'",
+                "Due date attribute 'team' is not a date or date-time",
+                "Due date interval refers to unknown attribute 'cadence'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+        fs::write(
+            schema_dir.join("index.json"),
+            r#"{
+                "documentTypes": [
+                    { "type": "bug", "name": "Bug", "description": "Bug", "category": "testing", "schema": "bug.json" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            schema_dir.join("bug.json"),
+            r#"{
+                "properties": {
+                    "forgepoint-type": { "const": "bug" },
+                    "status": { "type": "string", "enum": ["new", "fixed", "closed", "reopened"] }
+                },
+                "lifecycle": {
+                    "transitions": {
+                        "new": ["fixed", "wontfix"],
+                        "fixed": ["closed"]
+                    },
+                    "initial": "new"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let issues = SchemaChecker::new(&SchemaSource::Directory(schema_dir.to_path_buf()), false).check();
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "Unknown key lifecycle.initial",
+                "Lifecycle state 'wontfix' is not an allowed status value",
+                "The status value 'reopened' has no lifecycle entry",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_conditions() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_dir = temp_dir.path();
+        fs::write(
+            schema_dir.join("index.json"),
+            r#"{
+                "documentTypes": [
+                    { "type": "bug", "name": "Bug", "description": "Bug", "category": "testing", "schema": "bug.json" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            schema_dir.join("bug.json"),
+            r#"{
+                "properties": {
+                    "forgepoint-type": { "const": "bug" },
+                    "status": { "type": "string", "enum": ["new", "fixed"] }
+                },
+                "allOf": [
+                    { "if": { "properties": { "state": { "const": "fixed" } } }, "then": { "required": ["fix"] } }
+                ],
+                "structuralRequirements": {
+                    "conditions": [
+                        {
+                            "if": { "status": ["fixed", "closed"], "resolution": ["done"] },
+                            "then": { "sections": { "required": ["Fix"], "optional": ["Fix"], "minWords": 10 } }
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let issues = SchemaChecker::new(&SchemaSource::Directory(schema_dir.to_path_buf()), false).check();
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "Unknown key structuralRequirements.conditions[0].then.sections.minWords",
+                "Condition refers to unknown attribute 'resolution'",
+                "Condition value 'closed' is not an allowed status value",
+                "Section 'Fix' is both required and optional in condition 0",
+                "Condition refers to unknown attribute 'state'",
+            ]
+        );
+    }
+}
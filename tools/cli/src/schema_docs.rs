@@ -0,0 +1,876 @@
+use crate::schema::{
+    describe_condition, AdditionalSections, AdditionalSectionsMode, CompiledSchema, SectionContentRequirement, SectionRequirements,
+    BUILTIN_CATEGORIES,
+};
+use crate::html::{self, escape_html};
+use crate::DocsFormat;
+use regex::Regex;
+use serde_json::Value;
+
+/// File extension of the pages written for a format
+pub fn extension(format: DocsFormat) -> &'static str {
+    match format {
+        DocsFormat::Adoc => "adoc",
+        DocsFormat::Md => "md",
+        DocsFormat::Html => "html",
+    }
+}
+
+/// Render the reference page for one document type
+pub fn render_type_page(schema: &CompiledSchema, format: DocsFormat) -> String {
+    let definition = &schema.definition;
+    let mut page = Page::new(format);
+
+    page.heading(1, &format!("{} ({})", definition.name, definition.doc_type));
+    page.paragraph(&page.text(&definition.description));
+
+    let mut facts = vec![
+        format!("{} {}", page.strong("Type:"), page.code(&definition.doc_type)),
+        format!("{} {}", page.strong("Category:"), page.text(&definition.category)),
+        format!("{} {}", page.strong("Schema:"), page.code(&definition.schema)),
+    ];
+    if let Some(version) = &schema.version {
+        facts.push(format!("{} {}", page.strong("Schema version:"), page.code(version)));
+    }
+    if !definition.versions.is_empty() {
+        let versions: Vec<String> = definition
+            .versions
+            .iter()
+            .map(|v| match v.deprecated {
+                true => format!("{} (deprecated)", page.code(&v.version)),
+                false => page.code(&v.version),
+            })
+            .collect();
+        facts.push(format!("{} {}", page.strong("Supported versions:"), versions.join(", ")));
+    }
+    page.list(&facts);
+
+    page.heading(2, "Attributes");
+    let rows: Vec<Vec<String>> = attribute_names(schema)
+        .into_iter()
+        .map(|(name, required)| {
+            let property = &schema.schema_json["properties"][name.as_str()];
+            vec![
+                page.code(&name),
+                if required { "yes" } else { "no" }.to_string(),
+                page.text(&type_name(property)),
+                allowed_values(&page, property),
+                page.text(property.get("description").and_then(|d| d.as_str()).unwrap_or("")),
+            ]
+        })
+        .collect();
+    page.table(&["Attribute", "Required", "Type", "Allowed values", "Description"], &rows);
+
+    page.heading(2, "Structure");
+    let structure = &schema.structural_requirements;
+    let mut items = Vec::new();
+    if let Some(title) = &structure.title {
+        let mut item = format!(
+            "{} {}",
+            page.strong("Title:"),
+            if title.required.unwrap_or(false) { "required" } else { "optional" }
+        );
+        if let Some(format) = &title.format {
+            item.push_str(&format!(", formatted as {}", page.code(format)));
+        }
+        if let Some(description) = &title.description {
+            item.push_str(&format!(". {}", page.text(description)));
+        }
+        items.push(item);
+    }
+    if let Some(abstract_req) = &structure.abstract_req {
+        let mut item = format!(
+            "{} {}",
+            page.strong("Abstract:"),
+            if abstract_req.required.unwrap_or(false) { "required" } else { "optional" }
+        );
+        if let Some(description) = &abstract_req.description {
+            item.push_str(&format!(". {}", page.text(description)));
+        }
+        items.push(item);
+    }
+    let date_rules: Vec<String> = schema
+        .date_rules
+        .iter()
+        .filter_map(|rule| {
+            let (reference, inclusive) = rule.reference()?;
+            Some(format!(
+                "{} must be {} {}",
+                page.code(&rule.attribute),
+                if inclusive { "on or after" } else { "after" },
+                page.code(reference)
+            ))
+        })
+        .collect();
+    if !date_rules.is_empty() {
+        items.push(format!("{} {}", page.strong("Dates:"), date_rules.join("; ")));
+    }
+    for rule in &schema.due_dates {
+        let mut item = format!(
+            "{} {} falls due on {}",
+            page.strong("Due:"),
+            page.text(rule.description.as_deref().unwrap_or("The document")),
+            page.code(&rule.attribute)
+        );
+        if let Some(every) = &rule.every {
+            item.push_str(&format!(" plus the interval in {}", page.code(every)));
+        }
+        if let Some(statuses) = &rule.status {
+            let statuses: Vec<String> = statuses.iter().map(|s| page.code(s)).collect();
+            item.push_str(&format!(" while the status is {}", statuses.join(" or ")));
+        }
+        items.push(item);
+    }
+    if !items.is_empty() {
+        page.list(&items);
+    }
+
+    match &structure.sections {
+        Some(sections) => {
+            page.heading(3, "Sections");
+            if let Some(description) = &sections.description {
+                page.paragraph(&page.text(description));
+            }
+            let mut items = Vec::new();
+            describe_sections(&page, sections, None, &mut items);
+            page.list(&items);
+        }
+        None => page.paragraph("The schema places no requirements on sections."),
+    }
+
+    let conditions = describe_conditions(&page, schema);
+    if !conditions.is_empty() {
+        page.heading(3, "Conditional Requirements");
+        page.list(&conditions);
+    }
+
+    if let Some(lifecycle) = &schema.lifecycle {
+        page.heading(2, "Lifecycle");
+        page.paragraph(&format!(
+            "Allowed changes of {}, checked by {}:",
+            page.code(&lifecycle.attribute),
+            page.code("forgepoint lint --against <git-rev>")
+        ));
+
+        // Follow the order of the attribute's allowed values where there is one
+        let mut states: Vec<String> = schema.schema_json["properties"][lifecycle.attribute.as_str()]["enum"]
+            .as_array()
+            .map(|values| values.iter().filter_map(|v| v.as_str()).map(String::from).collect())
+            .unwrap_or_else(|| {
+                let mut states: Vec<String> = lifecycle.transitions.iter().flat_map(|(state, next)| std::iter::once(state).chain(next)).cloned().collect();
+                states.sort();
+                states.dedup();
+                states
+            });
+        states.retain(|state| lifecycle.knows(state));
+
+        let items: Vec<String> = states
+            .iter()
+            .map(|state| match lifecycle.transitions.get(state).map(Vec::as_slice).unwrap_or_default() {
+                [] => format!("{} (final)", page.code(state)),
+                next => format!(
+                    "{} → {}",
+                    page.code(state),
+                    next.iter().map(|s| page.code(s)).collect::<Vec<_>>().join(", ")
+                ),
+            })
+            .collect();
+        page.list(&items);
+    }
+
+    page.heading(2, "Minimal Example");
+    page.paragraph(&format!(
+        "The smallest document of this type that passes {}:",
+        page.code("forgepoint lint")
+    ));
+    page.code_block("asciidoc", &minimal_example(schema));
+
+    page.finish(&definition.name)
+}
+
+/// Render the index page linking every document type, grouped by category
+pub fn render_index(schemas: &[&CompiledSchema], format: DocsFormat) -> String {
+    let mut page = Page::new(format);
+    page.heading(1, "Forgepoint Document Types");
+    page.paragraph(&format!(
+        "Reference for the {} document types known to this repository, generated from their schemas.",
+        schemas.len()
+    ));
+
+    let mut categories: Vec<&str> = BUILTIN_CATEGORIES.to_vec();
+    let mut custom: Vec<&str> = schemas
+        .iter()
+        .map(|s| s.definition.category.as_str())
+        .filter(|category| !BUILTIN_CATEGORIES.contains(category))
+        .collect();
+    custom.sort();
+    custom.dedup();
+    categories.extend(custom);
+
+    for category in categories {
+        let mut in_category: Vec<&&CompiledSchema> =
+            schemas.iter().filter(|s| s.definition.category == category).collect();
+        if in_category.is_empty() {
+            continue;
+        }
+        in_category.sort_by(|a, b| a.definition.doc_type.cmp(&b.definition.doc_type));
+
+        page.heading(2, &capitalize(category));
+        let rows: Vec<Vec<String>> = in_category
+            .iter()
+            .map(|s| {
+                let target = format!("{}.{}", s.definition.doc_type, extension(format));
+                vec![
+                    page.link(&s.definition.doc_type, &target),
+                    page.text(&s.definition.name),
+                    page.text(&s.definition.description),
+                ]
+            })
+            .collect();
+        page.table(&["Type", "Name", "Description"], &rows);
+    }
+
+    page.finish("Forgepoint Document Types")
+}
+
+/// Build the smallest document that satisfies a schema: its required
+/// attributes, abstract and sections, with content meeting each section's
+/// content requirements
+pub fn minimal_example(schema: &CompiledSchema) -> String {
+    let doc_type = &schema.definition.doc_type;
+    let properties = &schema.schema_json["properties"];
+    // Dates constrained by a date rule are set later than the dates they follow
+    let later_dates: Vec<&str> = schema.date_rules.iter().map(|rule| rule.attribute.as_str()).collect();
+    let value_of = |name: &str| {
+        let value = example_value(name, &properties[name], doc_type);
+        match later_dates.contains(&name) {
+            true => value.replace("2024-01-15", "2024-01-31"),
+            false => value,
+        }
+    };
+
+    let title = schema
+        .title_format()
+        .and_then(|format| {
+            let placeholder = Regex::new(r"\{([^}]+)\}").unwrap();
+            let mut resolved = true;
+            let title = placeholder.replace_all(&format, |cap: &regex::Captures| match properties.get(&cap[1]) {
+                Some(_) => value_of(&cap[1]),
+                None => {
+                    resolved = false;
+                    String::new()
+                }
+            });
+            resolved.then(|| title.to_string())
+        })
+        .unwrap_or_else(|| format!("Example {}", schema.definition.name));
+
+    let mut lines = vec![format!("= {}", title)];
+    for (name, _) in attribute_names(schema).into_iter().filter(|(_, required)| *required) {
+        lines.push(format!(":{}: {}", name, value_of(&name)));
+    }
+
+    if schema.is_abstract_required() {
+        lines.push(String::new());
+        lines.push("[abstract]".to_string());
+        lines.push(format!("A short summary of the example {}.", schema.definition.name));
+    }
+
+    if let Some(sections) = schema.section_requirements() {
+        example_sections(sections, 2, &mut lines);
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Attribute names in display order: required attributes as the schema lists
+/// them, then the optional ones alphabetically
+fn attribute_names(schema: &CompiledSchema) -> Vec<(String, bool)> {
+    let required: Vec<String> = schema.schema_json["required"]
+        .as_array()
+        .map(|names| names.iter().filter_map(|n| n.as_str()).map(String::from).collect())
+        .unwrap_or_default();
+
+    let mut optional: Vec<String> = schema.schema_json["properties"]
+        .as_object()
+        .map(|properties| properties.keys().filter(|name| !required.contains(name)).cloned().collect())
+        .unwrap_or_default();
+    optional.sort();
+
+    required
+        .into_iter()
+        .map(|name| (name, true))
+        .chain(optional.into_iter().map(|name| (name, false)))
+        .collect()
+}
+
+fn type_name(property: &Value) -> String {
+    if property.get("const").is_some() {
+        return "constant".to_string();
+    }
+
+    let base = property.get("type").and_then(|t| t.as_str()).unwrap_or("any");
+    match (base, property.get("format").and_then(|f| f.as_str())) {
+        ("array", _) => format!("array of {}", type_name(property.get("items").unwrap_or(&Value::Null))),
+        (base, Some(format)) => format!("{} ({})", base, format),
+        (base, None) => base.to_string(),
+    }
+}
+
+fn allowed_values(page: &Page, property: &Value) -> String {
+    let mut constraints = Vec::new();
+
+    if let Some(value) = property.get("const") {
+        constraints.push(page.code(&display_value(value)));
+    }
+    if let Some(values) = property.get("enum").and_then(|v| v.as_array()) {
+        constraints.push(values.iter().map(|v| page.code(&display_value(v))).collect::<Vec<_>>().join(", "));
+    }
+    if let Some(pattern) = property.get("pattern").and_then(|p| p.as_str()) {
+        constraints.push(format!("matches {}", page.code(pattern)));
+    }
+    if let Some(minimum) = property.get("minimum") {
+        constraints.push(format!("at least {}", minimum));
+    }
+    if let Some(maximum) = property.get("maximum") {
+        constraints.push(format!("at most {}", maximum));
+    }
+    if let Some(items) = property.get("items") {
+        let items = allowed_values(page, items);
+        if !items.is_empty() {
+            constraints.push(format!("items: {}", items));
+        }
+    }
+    if let Some(properties) = property.get("properties").and_then(|p| p.as_object()) {
+        let keys: Vec<String> = properties
+            .iter()
+            .map(|(key, value)| format!("{} ({})", page.code(key), type_name(value)))
+            .collect();
+        constraints.push(format!("keys: {}", keys.join(", ")));
+    }
+
+    constraints.join("; ")
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Describe section requirements as list items, prefixing nested
+/// requirements with the path of their parent sections
+fn describe_sections(page: &Page, sections: &SectionRequirements, parent: Option<&str>, items: &mut Vec<String>) {
+    let prefix = parent.map(|p| format!("{} › ", page.text(p))).unwrap_or_default();
+    let subject = if parent.is_some() { "subsections" } else { "sections" };
+    let names = |titles: &[String]| titles.iter().map(|t| page.code(t)).collect::<Vec<_>>().join(", ");
+
+    if let Some(required) = sections.required.as_ref().filter(|r| !r.is_empty()) {
+        items.push(format!("{}{} {}", prefix, page.strong(&format!("Required {}:", subject)), names(required)));
+    }
+    if let Some(optional) = sections.optional.as_ref().filter(|o| !o.is_empty()) {
+        items.push(format!("{}{} {}", prefix, page.strong(&format!("Optional {}:", subject)), names(optional)));
+    }
+    if sections.ordered.unwrap_or(false) {
+        items.push(format!(
+            "{}{} must appear in the order listed, required before optional",
+            prefix,
+            capitalize(subject)
+        ));
+    }
+    match sections.additional_sections {
+        Some(AdditionalSections::Allowed(false)) => {
+            items.push(format!("{}Other {} are not allowed", prefix, subject))
+        }
+        Some(AdditionalSections::Mode(AdditionalSectionsMode::Warn)) => {
+            items.push(format!("{}Other {} are reported as warnings", prefix, subject))
+        }
+        _ => {}
+    }
+
+    let mut content: Vec<_> = sections.content.iter().flatten().collect();
+    content.sort_by(|a, b| a.0.cmp(b.0));
+    for (title, requirement) in content {
+        let path = match parent {
+            Some(parent) => format!("{} › {}", parent, title),
+            None => title.clone(),
+        };
+        let rules = describe_content(page, requirement);
+        if !rules.is_empty() {
+            items.push(format!("{} {}", page.strong(&format!("{}:", page.text(&path))), rules.join("; ")));
+        }
+        if let Some(subsections) = &requirement.subsections {
+            describe_sections(page, subsections, Some(&path), items);
+        }
+    }
+}
+
+/// Requirements that apply only while attributes have certain values, from
+/// the schema's `allOf` rules and structural conditions
+fn describe_conditions(page: &Page, schema: &CompiledSchema) -> Vec<String> {
+    let mut items = Vec::new();
+
+    let rules = schema.schema_json.get("allOf").and_then(|a| a.as_array()).into_iter().flatten();
+    for rule in rules {
+        let (Some(condition), Some(then)) = (rule.get("if").and_then(describe_condition), rule.get("then")) else {
+            continue;
+        };
+
+        let mut requirements: Vec<String> = then
+            .get("required")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str())
+            .map(|name| format!("{} is required", page.code(name)))
+            .collect();
+        if let Some(properties) = then.get("properties").and_then(|p| p.as_object()) {
+            let mut properties: Vec<_> = properties.iter().collect();
+            properties.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, property) in properties {
+                if let Some(value) = property.get("const") {
+                    requirements.push(format!("{} must be {}", page.code(name), page.code(&display_value(value))));
+                } else if let Some(Value::Array(values)) = property.get("enum") {
+                    let values: Vec<String> = values.iter().map(|v| page.code(&display_value(v))).collect();
+                    requirements.push(format!("{} must be one of {}", page.code(name), values.join(", ")));
+                }
+            }
+        }
+
+        if !requirements.is_empty() {
+            items.push(format!(
+                "{} {}",
+                page.strong(&format!("When {}:", page.text(&condition))),
+                requirements.join("; ")
+            ));
+        }
+    }
+
+    for condition in schema.structural_conditions() {
+        let Some(sections) = &condition.then.sections else {
+            continue;
+        };
+        let mut requirements = Vec::new();
+        describe_sections(page, sections, None, &mut requirements);
+
+        let mut item = format!(
+            "{} {}",
+            page.strong(&format!("When {}:", page.text(&condition.describe()))),
+            requirements.join("; ")
+        );
+        if let Some(description) = &condition.description {
+            item.push_str(&format!(". {}", page.text(description)));
+        }
+        items.push(item);
+    }
+
+    items
+}
+
+fn describe_content(page: &Page, requirement: &SectionContentRequirement) -> Vec<String> {
+    let mut rules = Vec::new();
+
+    if let Some(checklist) = &requirement.checklist {
+        rules.push(match (checklist.min_items, checklist.max_items) {
+            (Some(min), Some(max)) => format!("a checklist of {} to {} items", min, max),
+            (Some(min), None) => format!("a checklist of at least {} items", min),
+            (None, Some(max)) => format!("a checklist of at most {} items", max),
+            (None, None) if checklist.all_checked.unwrap_or(false) => "every checklist item checked".to_string(),
+            (None, None) => "a checklist".to_string(),
+        });
+        if checklist.all_checked.unwrap_or(false) && (checklist.min_items.is_some() || checklist.max_items.is_some()) {
+            rules.push("every item checked".to_string());
+        }
+    }
+    if let Some(table) = &requirement.table {
+        let columns: Vec<String> = table.columns.iter().map(|c| page.code(c)).collect();
+        rules.push(format!("a table with columns {}", columns.join(", ")));
+    }
+    if let Some(source_block) = &requirement.source_block {
+        rules.push(format!("a {} block", page.code(&format!("[source,{}]", source_block.language))));
+    }
+    if let Some(min_words) = requirement.min_words {
+        rules.push(format!("at least {} words", min_words));
+    }
+    if let Some(description) = &requirement.description {
+        rules.push(page.text(description));
+    }
+
+    rules
+}
+
+/// An example value for an attribute that satisfies its property schema
+fn example_value(name: &str, property: &Value, doc_type: &str) -> String {
+    if let Some(value) = property.get("const") {
+        return display_value(value);
+    }
+    if let Some(value) = property.get("enum").and_then(|v| v.as_array()).and_then(|v| v.first()) {
+        return display_value(value);
+    }
+    if name == "id" {
+        return format!("example-{}", doc_type);
+    }
+
+    match property.get("format").and_then(|f| f.as_str()) {
+        Some("date") => return "2024-01-15".to_string(),
+        Some("date-time") => return "2024-01-15T09:00:00Z".to_string(),
+        Some("uri") => return "https://example.com".to_string(),
+        _ => {}
+    }
+
+    if let Some(pattern) = property.get("pattern").and_then(|p| p.as_str()).and_then(|p| Regex::new(p).ok()) {
+        let id = format!("example-{}", doc_type);
+        let candidates = [id.as_str(), "1.0.0", "1.0", "v1", "2024-Q1", "2024-01-15", "1", "example"];
+        return candidates
+            .iter()
+            .find(|candidate| pattern.is_match(candidate))
+            .unwrap_or(&"example")
+            .to_string();
+    }
+
+    match property.get("type").and_then(|t| t.as_str()) {
+        Some("integer") | Some("number") => property
+            .get("minimum")
+            .map(display_value)
+            .unwrap_or_else(|| "1".to_string()),
+        Some("boolean") => "true".to_string(),
+        Some("array") => example_value(name, property.get("items").unwrap_or(&Value::Null), doc_type),
+        Some("object") => property
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, example_value(key, value, doc_type)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        _ => format!("Example {}", name.replace('-', " ")),
+    }
+}
+
+/// Append the required sections at `level`, recursing into required subsections
+fn example_sections(sections: &SectionRequirements, level: usize, lines: &mut Vec<String>) {
+    for title in sections.required.iter().flatten() {
+        lines.push(String::new());
+        lines.push(format!("{} {}", "=".repeat(level), title));
+
+        let requirement = sections.content.as_ref().and_then(|content| content.get(title));
+        let content = example_content(requirement);
+        if !content.is_empty() {
+            lines.push(String::new());
+            lines.extend(content);
+        }
+
+        if let Some(subsections) = requirement.and_then(|r| r.subsections.as_ref()) {
+            example_sections(subsections, level + 1, lines);
+        }
+    }
+}
+
+fn example_content(requirement: Option<&SectionContentRequirement>) -> Vec<String> {
+    let Some(requirement) = requirement else {
+        return vec!["Example content.".to_string()];
+    };
+    let mut lines = Vec::new();
+
+    if let Some(checklist) = &requirement.checklist {
+        for item in 1..=checklist.min_items.unwrap_or(1).max(1) {
+            lines.push(format!("* [ ] Example item {}", item));
+        }
+    }
+    if let Some(table) = &requirement.table {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("|===".to_string());
+        lines.push(table.columns.iter().map(|c| format!("|{}", c)).collect::<Vec<_>>().join(" "));
+        lines.push(String::new());
+        lines.push(table.columns.iter().map(|_| "|Example").collect::<Vec<_>>().join(" "));
+        lines.push("|===".to_string());
+    }
+    if let Some(source_block) = &requirement.source_block {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("[source,{}]", source_block.language));
+        lines.push("----".to_string());
+        if source_block.language.eq_ignore_ascii_case("gherkin") {
+            lines.extend(
+                [
+                    "Feature: Example feature",
+                    "  Scenario: Example scenario",
+                    "    Given an example precondition",
+                    "    When an example action happens",
+                    "    Then an example outcome is observed",
+                ]
+                .map(String::from),
+            );
+        } else {
+            lines.push("example".to_string());
+        }
+        lines.push("----".to_string());
+    }
+    if let Some(min_words) = requirement.min_words {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let sentence = "This section is filled in with example text.";
+        let sentences = min_words.div_ceil(sentence.split_whitespace().count());
+        lines.push(vec![sentence; sentences].join(" "));
+    }
+    if lines.is_empty() && requirement.subsections.is_none() {
+        lines.push("Example content.".to_string());
+    }
+
+    lines
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A documentation page being written in one of the output formats
+struct Page {
+    format: DocsFormat,
+    out: String,
+}
+
+impl Page {
+    fn new(format: DocsFormat) -> Self {
+        Self {
+            format,
+            out: String::new(),
+        }
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        let heading = match self.format {
+            DocsFormat::Adoc => format!("{} {}\n\n", "=".repeat(level), self.text(text)),
+            DocsFormat::Md => format!("{} {}\n\n", "#".repeat(level), self.text(text)),
+            DocsFormat::Html => format!("<h{0}>{1}</h{0}>\n", level, self.text(text)),
+        };
+        self.out.push_str(&heading);
+    }
+
+    /// Add a paragraph of already rendered inline text
+    fn paragraph(&mut self, text: &str) {
+        match self.format {
+            DocsFormat::Html => self.out.push_str(&format!("<p>{}</p>\n", text)),
+            _ => self.out.push_str(&format!("{}\n\n", text)),
+        }
+    }
+
+    fn list(&mut self, items: &[String]) {
+        match self.format {
+            DocsFormat::Adoc | DocsFormat::Md => {
+                let bullet = if matches!(self.format, DocsFormat::Adoc) { "*" } else { "-" };
+                for item in items {
+                    self.out.push_str(&format!("{} {}\n", bullet, item));
+                }
+                self.out.push('\n');
+            }
+            DocsFormat::Html => {
+                self.out.push_str("<ul>\n");
+                for item in items {
+                    self.out.push_str(&format!("<li>{}</li>\n", item));
+                }
+                self.out.push_str("</ul>\n");
+            }
+        }
+    }
+
+    fn table(&mut self, headers: &[&str], rows: &[Vec<String>]) {
+        match self.format {
+            DocsFormat::Adoc => {
+                self.out.push_str("[options=\"header\"]\n|===\n");
+                self.out.push_str(&headers.iter().map(|h| format!("|{}", h)).collect::<Vec<_>>().join(" "));
+                self.out.push('\n');
+                for row in rows {
+                    let cells: Vec<String> = row.iter().map(|c| format!("|{}", c.replace('|', "\\|"))).collect();
+                    self.out.push_str(&cells.join(" "));
+                    self.out.push('\n');
+                }
+                self.out.push_str("|===\n\n");
+            }
+            DocsFormat::Md => {
+                self.out.push_str(&format!("| {} |\n", headers.join(" | ")));
+                self.out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                for row in rows {
+                    let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                    self.out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                self.out.push('\n');
+            }
+            DocsFormat::Html => {
+                self.out.push_str("<table>\n<thead><tr>");
+                for header in headers {
+                    self.out.push_str(&format!("<th>{}</th>", header));
+                }
+                self.out.push_str("</tr></thead>\n<tbody>\n");
+                for row in rows {
+                    self.out.push_str("<tr>");
+                    for cell in row {
+                        self.out.push_str(&format!("<td>{}</td>", cell));
+                    }
+                    self.out.push_str("</tr>\n");
+                }
+                self.out.push_str("</tbody>\n</table>\n");
+            }
+        }
+    }
+
+    fn code_block(&mut self, language: &str, code: &str) {
+        let block = match self.format {
+            // A longer delimiter so that ---- blocks inside the example don't end it
+            DocsFormat::Adoc => format!("[source,{}]\n------\n{}------\n\n", language, code),
+            DocsFormat::Md => format!("```{}\n{}```\n\n", language, code),
+            DocsFormat::Html => format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>\n",
+                language,
+                escape_html(code)
+            ),
+        };
+        self.out.push_str(&block);
+    }
+
+    /// Escape plain text for the format
+    fn text(&self, text: &str) -> String {
+        match self.format {
+            // Braces would otherwise be read as attribute references
+            DocsFormat::Adoc => text.replace('{', "\\{"),
+            DocsFormat::Md => text.replace('*', "\\*").replace('_', "\\_"),
+            DocsFormat::Html => escape_html(text),
+        }
+    }
+
+    fn code(&self, text: &str) -> String {
+        match self.format {
+            DocsFormat::Adoc => format!("`++{}++`", text),
+            DocsFormat::Md => format!("`{}`", text),
+            DocsFormat::Html => format!("<code>{}</code>", escape_html(text)),
+        }
+    }
+
+    fn strong(&self, text: &str) -> String {
+        match self.format {
+            DocsFormat::Adoc => format!("*{}*", text),
+            DocsFormat::Md => format!("**{}**", text),
+            DocsFormat::Html => format!("<strong>{}</strong>", text),
+        }
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        match self.format {
+            DocsFormat::Adoc => format!("xref:{}[{}]", target, text),
+            DocsFormat::Md => format!("[{}]({})", text, target),
+            DocsFormat::Html => format!("<a href=\"{}\">{}</a>", escape_html(target), escape_html(text)),
+        }
+    }
+
+    /// The finished page, wrapped in a standalone document for HTML
+    fn finish(self, title: &str) -> String {
+        match self.format {
+            DocsFormat::Html => html::page(title, &self.out),
+            _ => self.out.trim_end().to_string() + "\n",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use crate::schema::SchemaLoader;
+    use crate::validator::DocumentValidator;
+    use std::path::PathBuf;
+
+    fn loader() -> SchemaLoader {
+        let mut loader = SchemaLoader::embedded();
+        loader.load_schemas().unwrap();
+        loader
+    }
+
+    #[test]
+    fn test_minimal_examples_validate() {
+        let loader = loader();
+        let examples: Vec<(String, String)> = loader
+            .get_document_types()
+            .iter()
+            .map(|definition| {
+                let schema = loader.get_schema(&definition.doc_type).unwrap();
+                (definition.doc_type.clone(), minimal_example(schema))
+            })
+            .collect();
+        assert_eq!(examples.len(), 37);
+
+        let parser = DocumentParser::new();
+        let mut validator = DocumentValidator::new(loader);
+        for (doc_type, example) in examples {
+            let doc = parser
+                .parse_content(&example, PathBuf::from(format!("{}.adoc", doc_type)))
+                .unwrap();
+            let result = validator.validate_document(&doc);
+            let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+            assert!(result.valid, "{} example is invalid: {:?}\n{}", doc_type, messages, example);
+        }
+    }
+
+    #[test]
+    fn test_minimal_example_content() {
+        let loader = loader();
+
+        let adr = minimal_example(loader.get_schema("adr").unwrap());
+        assert!(adr.starts_with("= ADR-1: Example decision\n:forgepoint-type: adr\n:id: example-adr\n"));
+        assert!(adr.contains(":deciders: Example deciders\n"));
+
+        let risk_register = minimal_example(loader.get_schema("risk-register").unwrap());
+        assert!(risk_register.contains("|===\n|ID |Description |Category"));
+
+        let sprint_plan = minimal_example(loader.get_schema("sprint-plan").unwrap());
+        assert!(sprint_plan.contains(":start-date: 2024-01-15\n:end-date: 2024-01-31\n"));
+
+        let prfaq = minimal_example(loader.get_schema("prfaq").unwrap());
+        assert!(prfaq.contains("== Press Release\n\n=== Headline\n\nExample content.\n"));
+    }
+
+    #[test]
+    fn test_render_type_page() {
+        let loader = loader();
+        let okr = loader.get_schema("okr").unwrap();
+
+        let markdown = render_type_page(okr, DocsFormat::Md);
+        assert!(markdown.starts_with("# "));
+        assert!(markdown.contains("| Attribute | Required | Type | Allowed values | Description |"));
+        assert!(markdown.contains("| `id` | yes | string | matches `^[a-z0-9-]+$` |"));
+        assert!(markdown.contains("**Key Results:** a checklist of 1 to 5 items"));
+        assert!(markdown.contains("**Dates:** `updated` must be on or after `created`"));
+        assert!(markdown.contains("```asciidoc\n= "));
+        assert!(markdown.contains("- `draft` → `active`, `cancelled`\n"));
+        assert!(markdown.contains("- `completed` (final)\n"));
+
+        let asciidoc = render_type_page(okr, DocsFormat::Adoc);
+        assert!(asciidoc.contains("|`++id++` |yes |string |matches `++^[a-z0-9-]+$++`"));
+        assert!(asciidoc.contains("[source,asciidoc]\n------\n= "));
+
+        let html = render_type_page(okr, DocsFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td><code>id</code></td><td>yes</td>"));
+
+        let story = render_type_page(loader.get_schema("story").unwrap(), DocsFormat::Md);
+        assert!(story.contains(
+            "- **When :status: is done:** **Acceptance Criteria:** every checklist item checked. A story is only done when every acceptance criterion is met\n"
+        ));
+        let adr = render_type_page(loader.get_schema("adr").unwrap(), DocsFormat::Md);
+        assert!(adr.contains("- **When :status: is superseded:** `superseded-by` is required\n"));
+
+        let index = render_index(&[okr], DocsFormat::Md);
+        assert!(index.contains("[okr](okr.md)"));
+    }
+}
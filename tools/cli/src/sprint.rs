@@ -0,0 +1,280 @@
+use crate::document::ForgepointDocument;
+use crate::progress::COMPLETED_STATUSES;
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use serde::{Deserialize, Serialize};
+
+/// Document types that are planned into sprints
+pub const SPRINT_ITEM_TYPES: &[&str] = &["story", "task"];
+
+/// The sprint-plan section listing the planned work
+pub const BACKLOG_SECTION: &str = "Sprint Backlog";
+
+/// A story or task referenced from a sprint backlog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacklogEntry {
+    pub document_type: String,
+    pub document_id: String,
+    pub line: Option<usize>,
+}
+
+/// A sprint-plan document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintPlan {
+    pub file_path: String,
+    pub document_id: String,
+    pub title: Option<String>,
+    pub team: Option<String>,
+    pub capacity: Option<u32>,
+    pub velocity: Option<u32>,
+    /// Line of the `capacity` attribute, where over-commitment is reported
+    pub capacity_line: Option<usize>,
+    pub backlog: Vec<BacklogEntry>,
+}
+
+impl SprintPlan {
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        if doc.document_type().map(String::as_str) != Some("sprint-plan") {
+            return None;
+        }
+
+        let backlog = match doc
+            .sections
+            .iter()
+            .position(|section| section.level == 2 && section.title == BACKLOG_SECTION)
+        {
+            Some(index) => {
+                let lines: Vec<usize> = doc.section_lines(index).into_iter().map(|(line, _)| line).collect();
+                let mut entries: Vec<BacklogEntry> = Vec::new();
+                for reference in doc.extract_cross_references() {
+                    let in_backlog = reference.line_number.is_some_and(|line| lines.contains(&line));
+                    let listed = entries.iter().any(|e| e.document_id == reference.id);
+                    if in_backlog && !listed && !reference.external && SPRINT_ITEM_TYPES.contains(&reference.ref_type.as_str()) {
+                        entries.push(BacklogEntry {
+                            document_type: reference.ref_type,
+                            document_id: reference.id,
+                            line: reference.line_number,
+                        });
+                    }
+                }
+                entries
+            }
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_id: doc.document_id()?.clone(),
+            title: doc.title.clone(),
+            team: doc.attributes.get("team").cloned(),
+            capacity: doc.attributes.get("capacity").and_then(|v| v.trim().parse().ok()),
+            velocity: doc.attributes.get("velocity").and_then(|v| v.trim().parse().ok()),
+            capacity_line: doc.attribute_location("capacity").map(|location| location.line),
+            backlog,
+        })
+    }
+}
+
+/// A story or task assigned to a sprint with its `sprint` attribute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintItem {
+    pub file_path: String,
+    pub document_type: String,
+    pub document_id: String,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub sprint: Option<String>,
+    pub points: Option<u32>,
+    pub assignee: Option<String>,
+}
+
+impl SprintItem {
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        let document_type = doc.document_type()?;
+        if !SPRINT_ITEM_TYPES.contains(&document_type.as_str()) {
+            return None;
+        }
+
+        Some(Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_type: document_type.clone(),
+            document_id: doc.document_id()?.clone(),
+            title: doc.title.clone(),
+            status: doc.attributes.get("status").cloned(),
+            sprint: doc.attributes.get("sprint").map(|v| v.trim().to_string()).filter(|v| !v.is_empty()),
+            points: doc.attributes.get("points").and_then(|v| v.trim().parse().ok()),
+            assignee: doc.attributes.get("assignee").cloned(),
+        })
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status.as_deref().is_some_and(|status| COMPLETED_STATUSES.contains(&status))
+    }
+}
+
+/// A backlog entry whose story or task isn't assigned to the sprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnassignedEntry {
+    #[serde(flatten)]
+    pub entry: BacklogEntry,
+    /// The sprint the item is assigned to instead, if it was found
+    pub sprint: Option<String>,
+    pub found: bool,
+}
+
+/// A sprint plan reconciled with the stories and tasks assigned to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintReport {
+    pub plan: SprintPlan,
+    pub items: Vec<SprintItem>,
+    /// Points of all items assigned to the sprint
+    pub committed: u32,
+    /// Points of the items that are done
+    pub completed: u32,
+    /// Items assigned to the sprint without points
+    pub unestimated: usize,
+    /// Backlog entries that aren't assigned to the sprint
+    pub not_assigned: Vec<UnassignedEntry>,
+    /// Items assigned to the sprint that the backlog doesn't list
+    pub not_in_backlog: Vec<SprintItem>,
+}
+
+impl SprintReport {
+    pub fn build(plan: SprintPlan, all_items: &[SprintItem]) -> Self {
+        let mut items: Vec<SprintItem> = all_items
+            .iter()
+            .filter(|item| item.sprint.as_deref() == Some(plan.document_id.as_str()))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| a.document_id.cmp(&b.document_id));
+
+        let not_assigned = plan
+            .backlog
+            .iter()
+            .filter(|entry| !items.iter().any(|item| item.document_id == entry.document_id))
+            .map(|entry| {
+                let item = all_items.iter().find(|item| item.document_id == entry.document_id);
+                UnassignedEntry {
+                    entry: entry.clone(),
+                    sprint: item.and_then(|item| item.sprint.clone()),
+                    found: item.is_some(),
+                }
+            })
+            .collect();
+        let not_in_backlog = items
+            .iter()
+            .filter(|item| !plan.backlog.iter().any(|entry| entry.document_id == item.document_id))
+            .cloned()
+            .collect();
+
+        Self {
+            committed: items.iter().filter_map(|item| item.points).sum(),
+            completed: items.iter().filter(|item| item.is_completed()).filter_map(|item| item.points).sum(),
+            unestimated: items.iter().filter(|item| item.points.is_none()).count(),
+            plan,
+            items,
+            not_assigned,
+            not_in_backlog,
+        }
+    }
+
+    /// Warn when the sprint commits more points than the team has capacity for
+    pub fn check_commitment(&self) -> Option<ValidationError> {
+        let capacity = self.plan.capacity?;
+        if self.committed <= capacity {
+            return None;
+        }
+
+        let mut message = format!(
+            "Sprint {} commits {} points against a capacity of {}",
+            self.plan.document_id, self.committed, capacity
+        );
+        if let Some(velocity) = self.plan.velocity.filter(|velocity| self.committed > *velocity) {
+            message.push_str(&format!(" and a velocity of {}", velocity));
+        }
+
+        Some(ValidationError {
+            error_type: ErrorType::Schedule,
+            severity: Severity::Warning,
+            message,
+            location: Some(Location {
+                line: self.plan.capacity_line,
+                column: None,
+                section: Some("attributes".to_string()),
+            }),
+            rule: Some("over-commitment".to_string()),
+            suggestion: Some(format!(
+                "Move {} points out of the sprint, or raise :capacity: if the team has more time",
+                self.committed - capacity
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn parse(content: &str) -> ForgepointDocument {
+        DocumentParser::new()
+            .parse_content(content, PathBuf::from("doc.adoc"))
+            .unwrap()
+    }
+
+    fn item(doc_type: &str, id: &str, sprint: &str, points: &str, status: &str) -> SprintItem {
+        let points = match points {
+            "" => String::new(),
+            points => format!(":points: {}\n", points),
+        };
+        SprintItem::from_document(&parse(&format!(
+            "= Item\n:forgepoint-type: {}\n:id: {}\n:status: {}\n:sprint: {}\n{}:schema-version: 1.0\n",
+            doc_type, id, status, sprint, points
+        )))
+        .unwrap()
+    }
+
+    fn plan() -> SprintPlan {
+        SprintPlan::from_document(&parse(
+            "= Sprint 3 Plan - Core\n:forgepoint-type: sprint-plan\n:id: core-s3\n:status: planning\n:capacity: 10\n:velocity: 8\n:schema-version: 1.0\n\n== Sprint Goal\n\nShip checkout\n\n== Sprint Backlog\n\n* xref:story:checkout[Checkout]\n* xref:story:refunds[Refunds]\n* xref:task:ledger[Ledger]\n* xref:epic:payments[Payments]\n\n== Team Capacity\n\nSee xref:story:search[]\n",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sprint_plan() {
+        let plan = plan();
+        assert_eq!((plan.capacity, plan.velocity, plan.capacity_line), (Some(10), Some(8), Some(5)));
+        let backlog: Vec<_> = plan.backlog.iter().map(|e| (e.document_id.as_str(), e.line)).collect();
+        assert_eq!(backlog, vec![("checkout", Some(15)), ("refunds", Some(16)), ("ledger", Some(17))]);
+    }
+
+    #[test]
+    fn test_sprint_report() {
+        let items = vec![
+            item("story", "checkout", "core-s3", "5", "done"),
+            item("story", "refunds", "core-s4", "3", "ready"),
+            item("task", "ledger", "core-s3", "", "todo"),
+            item("story", "search", "core-s3", "8", "in-progress"),
+        ];
+        let report = SprintReport::build(plan(), &items);
+
+        assert_eq!((report.committed, report.completed, report.unestimated), (13, 5, 1));
+        assert_eq!(report.items.len(), 3);
+        assert_eq!(report.not_assigned.len(), 1);
+        assert_eq!(report.not_assigned[0].entry.document_id, "refunds");
+        assert_eq!(report.not_assigned[0].sprint.as_deref(), Some("core-s4"));
+        let not_in_backlog: Vec<_> = report.not_in_backlog.iter().map(|i| i.document_id.as_str()).collect();
+        assert_eq!(not_in_backlog, vec!["search"]);
+
+        let warning = report.check_commitment().unwrap();
+        assert_eq!(
+            warning.message,
+            "Sprint core-s3 commits 13 points against a capacity of 10 and a velocity of 8"
+        );
+        assert_eq!(warning.location.unwrap().line, Some(5));
+
+        let report = SprintReport::build(plan(), &items[..3]);
+        assert!(report.check_commitment().is_none());
+    }
+}
@@ -0,0 +1,120 @@
+/// Compute the Levenshtein edit distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Find the candidates closest to `input`, ignoring case, that are close enough
+/// to be plausible typos. A candidate that contains the input or is contained
+/// by it (e.g. `story` for `user-story`) also counts as close. Results are
+/// ordered by distance, nearest first.
+pub fn closest_matches<'a, I>(input: &str, candidates: I, limit: usize) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input_lower = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != input)
+        .filter_map(|candidate| {
+            let candidate_lower = candidate.to_lowercase();
+            let distance = edit_distance(&input_lower, &candidate_lower);
+            let (shorter, longer) = if input_lower.len() < candidate_lower.len() {
+                (&input_lower, &candidate_lower)
+            } else {
+                (&candidate_lower, &input_lower)
+            };
+            let contained = shorter.len() >= 3 && longer.contains(shorter.as_str());
+
+            (distance <= max_distance || contained).then_some((distance, candidate))
+        })
+        .collect();
+
+    matches.sort();
+    matches.dedup();
+    matches.into_iter().take(limit).map(|(_, candidate)| candidate).collect()
+}
+
+/// Find the single candidate closest to `input`, if any is close enough
+pub fn closest_match<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    closest_matches(input, candidates, 1).into_iter().next()
+}
+
+/// Format a "did you mean" suggestion from a list of options
+pub fn did_you_mean<S: AsRef<str>>(options: &[S]) -> Option<String> {
+    let quoted: Vec<String> = options.iter().map(|o| format!("'{}'", o.as_ref())).collect();
+
+    match quoted.split_last() {
+        None => None,
+        Some((last, [])) => Some(format!("Did you mean {}?", last)),
+        Some((last, rest)) => Some(format!("Did you mean {} or {}?", rest.join(", "), last)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("Acceptence Criteria", "Acceptance Criteria"), 1);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let sections = ["Acceptance Criteria", "Scenarios", "Technical Notes"];
+
+        assert_eq!(
+            closest_match("Acceptence Criteria", sections.iter().copied()),
+            Some("Acceptance Criteria")
+        );
+        assert_eq!(closest_match("scenarios", sections.iter().copied()), Some("Scenarios"));
+        assert_eq!(closest_match("Deployment", sections.iter().copied()), None);
+    }
+
+    #[test]
+    fn test_closest_matches() {
+        let statuses = ["draft", "ready", "in-progress", "done", "blocked"];
+
+        assert_eq!(closest_matches("inprogress", statuses.iter().copied(), 3), vec!["in-progress"]);
+        assert_eq!(closest_matches("dome", statuses.iter().copied(), 3), vec!["done"]);
+        assert_eq!(closest_matches("rady", statuses.iter().copied(), 3), vec!["ready"]);
+        assert!(closest_matches("done", statuses.iter().copied(), 3).is_empty());
+
+        let types = ["story", "epic", "task", "bug-report"];
+        assert_eq!(closest_matches("user-story", types.iter().copied(), 3), vec!["story"]);
+        assert_eq!(closest_matches("bug", types.iter().copied(), 3), vec!["bug-report"]);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(did_you_mean::<&str>(&[]), None);
+        assert_eq!(did_you_mean(&["story"]), Some("Did you mean 'story'?".to_string()));
+        assert_eq!(
+            did_you_mean(&["a", "b", "c"]),
+            Some("Did you mean 'a', 'b' or 'c'?".to_string())
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use crate::config::TimelineConfig;
+use crate::document::ForgepointDocument;
+use crate::schema::parse_date;
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Document types placed on the timeline
+pub const TIMELINE_ITEM_TYPES: &[&str] = &["epic", "shape-up-pitch"];
+
+/// Length of a pitch's cycle by appetite; big batches are the default
+pub const SMALL_BATCH_WEEKS: u64 = 2;
+pub const BIG_BATCH_WEEKS: u64 = 6;
+
+/// A product roadmap the timeline is grouped by
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roadmap {
+    pub file_path: String,
+    pub document_id: String,
+    pub title: Option<String>,
+    pub product: Option<String>,
+    pub status: Option<String>,
+    /// IDs of the epics and pitches the roadmap references
+    pub references: Vec<String>,
+}
+
+impl Roadmap {
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        if doc.document_type().map(String::as_str) != Some("product-roadmap") {
+            return None;
+        }
+        Some(Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_id: doc.document_id()?.clone(),
+            title: doc.title.clone(),
+            product: doc.attributes.get("product").cloned(),
+            status: doc.attributes.get("status").cloned(),
+            references: doc
+                .extract_cross_references()
+                .into_iter()
+                .filter(|reference| !reference.external && TIMELINE_ITEM_TYPES.contains(&reference.ref_type.as_str()))
+                .map(|reference| reference.id)
+                .collect(),
+        })
+    }
+}
+
+/// An epic or pitch, with the dates its target release or cycle resolve to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineItem {
+    pub file_path: String,
+    pub document_type: String,
+    pub document_id: String,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub teams: Vec<String>,
+    /// The target release or cycle the dates come from
+    pub schedule: Option<String>,
+    pub start: Option<NaiveDate>,
+    /// The target date; an item without a start is a milestone
+    pub end: Option<NaiveDate>,
+    /// ID of the roadmap the item belongs to
+    pub roadmap: Option<String>,
+    /// IDs of the roadmaps the item references
+    #[serde(skip)]
+    pub roadmap_references: Vec<String>,
+}
+
+impl TimelineItem {
+    /// Build an item, resolving `target-release` through `releases` (dates by
+    /// version) and `cycle` through `cycles` (start dates by cycle).
+    ///
+    /// Epics have no start date attribute, so an epic's bar starts at its
+    /// `created` date, the earliest work on it can have begun. An epic without
+    /// one, or created after its target date, is shown as a milestone
+    pub fn from_document(
+        doc: &ForgepointDocument,
+        releases: &HashMap<String, NaiveDate>,
+        cycles: &HashMap<String, NaiveDate>,
+    ) -> Option<Self> {
+        let document_type = doc.document_type()?.clone();
+        let date = |value: &str| parse_date(value).map(|moment| moment.date());
+
+        let (schedule, start, end, teams) = match document_type.as_str() {
+            "epic" => {
+                let target = doc.attributes.get("target-release").map(|value| value.trim().to_string());
+                let end = target.as_deref().and_then(|target| date(target).or_else(|| releases.get(&release_key(target)).copied()));
+                let created = doc.attributes.get("created").and_then(|value| date(value));
+                let start = created.filter(|created| end.is_some_and(|end| *created < end));
+                (target, start, end, doc.attributes.get("team").cloned().into_iter().collect())
+            }
+            "shape-up-pitch" => {
+                let cycle = doc.attributes.get("cycle").map(|value| value.trim().to_string());
+                let start = cycle.as_deref().and_then(|cycle| cycles.get(cycle).copied().or_else(|| date(cycle)));
+                let weeks = match doc.attributes.get("appetite").map(String::as_str) {
+                    Some("small-batch") => SMALL_BATCH_WEEKS,
+                    _ => BIG_BATCH_WEEKS,
+                };
+                let end = start.and_then(|start| start.checked_add_days(Days::new(weeks * 7)));
+                (cycle, start, end, pitch_team(doc))
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_id: doc.document_id()?.clone(),
+            document_type,
+            title: doc.title.clone(),
+            status: doc.attributes.get("status").cloned(),
+            teams,
+            schedule,
+            start,
+            end,
+            roadmap: None,
+            roadmap_references: doc
+                .extract_cross_references()
+                .into_iter()
+                .filter(|reference| !reference.external && reference.ref_type == "product-roadmap")
+                .map(|reference| reference.id)
+                .collect(),
+        })
+    }
+
+    pub fn is_scheduled(&self) -> bool {
+        self.end.is_some()
+    }
+}
+
+/// The members of a pitch's `team`, e.g. `designer=ana, programmers=bo, cy`
+fn pitch_team(doc: &ForgepointDocument) -> Vec<String> {
+    doc.attributes
+        .get("team")
+        .map(|team| {
+            team.split(',')
+                .map(|member| member.rsplit('=').next().unwrap_or(member).trim().to_string())
+                .filter(|member| !member.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compare release versions with or without a leading `v`
+fn release_key(version: &str) -> String {
+    version.trim().trim_start_matches(['v', 'V']).to_string()
+}
+
+/// The items of one roadmap, or those that belong to none
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoadmapLane {
+    pub roadmap: Option<Roadmap>,
+    pub items: Vec<TimelineItem>,
+}
+
+impl RoadmapLane {
+    pub fn name(&self) -> String {
+        match &self.roadmap {
+            Some(roadmap) => roadmap.title.clone().unwrap_or_else(|| roadmap.document_id.clone()),
+            None => "No roadmap".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timeline {
+    pub lanes: Vec<RoadmapLane>,
+}
+
+impl Timeline {
+    /// Assemble the timeline: release dates come from release notes and the
+    /// configured releases, and each item joins the roadmap it references or,
+    /// failing that, the first roadmap referencing it
+    pub fn build(docs: &[ForgepointDocument], config: &TimelineConfig) -> Self {
+        let mut releases: HashMap<String, NaiveDate> =
+            config.releases.iter().map(|(version, date)| (release_key(version), *date)).collect();
+        for doc in docs.iter().filter(|doc| doc.document_type().map(String::as_str) == Some("release-notes")) {
+            let released = doc.attributes.get("release-date").and_then(|value| parse_date(value));
+            if let (Some(version), Some(released)) = (doc.attributes.get("version"), released) {
+                releases.insert(release_key(version), released.date());
+            }
+        }
+
+        let mut roadmaps: Vec<Roadmap> = docs.iter().filter_map(Roadmap::from_document).collect();
+        roadmaps.sort_by(|a, b| a.document_id.cmp(&b.document_id));
+        let mut items: Vec<TimelineItem> = docs
+            .iter()
+            .filter_map(|doc| TimelineItem::from_document(doc, &releases, &config.cycles))
+            .collect();
+        for item in &mut items {
+            item.roadmap = item
+                .roadmap_references
+                .iter()
+                .find(|id| roadmaps.iter().any(|roadmap| &roadmap.document_id == *id))
+                .or_else(|| {
+                    roadmaps
+                        .iter()
+                        .find(|roadmap| roadmap.references.contains(&item.document_id))
+                        .map(|roadmap| &roadmap.document_id)
+                })
+                .cloned();
+        }
+        items.sort_by(|a, b| {
+            (!a.is_scheduled(), a.start.or(a.end), a.end, &a.document_id)
+                .cmp(&(!b.is_scheduled(), b.start.or(b.end), b.end, &b.document_id))
+        });
+
+        let mut lanes: Vec<RoadmapLane> = roadmaps
+            .into_iter()
+            .map(|roadmap| RoadmapLane {
+                items: items
+                    .iter()
+                    .filter(|item| item.roadmap.as_ref() == Some(&roadmap.document_id))
+                    .cloned()
+                    .collect(),
+                roadmap: Some(roadmap),
+            })
+            .collect();
+        lanes.push(RoadmapLane {
+            roadmap: None,
+            items: items.into_iter().filter(|item| item.roadmap.is_none()).collect(),
+        });
+        Self { lanes }
+    }
+
+    /// Keep the items of roadmaps for `product` that belong to `team` and
+    /// have `status`, dropping lanes left empty
+    pub fn retain(&mut self, product: Option<&str>, team: Option<&str>, status: Option<&str>) {
+        let matches = |value: &str, filter: &str| value.trim().eq_ignore_ascii_case(filter.trim());
+        if let Some(product) = product {
+            self.lanes.retain(|lane| {
+                lane.roadmap.as_ref().and_then(|roadmap| roadmap.product.as_deref()).is_some_and(|p| matches(p, product))
+            });
+        }
+        for lane in &mut self.lanes {
+            lane.items.retain(|item| {
+                team.is_none_or(|team| item.teams.iter().any(|t| matches(t, team)))
+                    && status.is_none_or(|status| item.status.as_deref().is_some_and(|s| matches(s, status)))
+            });
+        }
+        self.lanes.retain(|lane| !lane.items.is_empty());
+    }
+
+    /// The first and last dates on the timeline
+    pub fn range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let dates = self.lanes.iter().flat_map(|lane| &lane.items).flat_map(|item| item.start.into_iter().chain(item.end));
+        let (first, last) = dates.fold((None, None), |(first, last): (Option<NaiveDate>, Option<NaiveDate>), date| {
+            (Some(first.map_or(date, |first| first.min(date))), Some(last.map_or(date, |last| last.max(date))))
+        });
+        first.zip(last)
+    }
+
+    /// Render the scheduled items as a Mermaid gantt chart, with a section per
+    /// roadmap; unscheduled items are listed as comments
+    pub fn render_gantt(&self, title: &str) -> String {
+        let mut gantt = format!("gantt\n    title {}\n    dateFormat YYYY-MM-DD\n", gantt_text(title));
+        for lane in &self.lanes {
+            let scheduled: Vec<&TimelineItem> = lane.items.iter().filter(|item| item.is_scheduled()).collect();
+            if !scheduled.is_empty() {
+                gantt.push_str(&format!("    section {}\n", gantt_text(&lane.name())));
+            }
+            for item in scheduled {
+                let name = gantt_text(item.title.as_deref().unwrap_or(&item.document_id));
+                let mut tags: Vec<&str> = Vec::new();
+                match item.status.as_deref() {
+                    Some("done" | "shipped") => tags.push("done"),
+                    Some("in-progress" | "building") => tags.push("active"),
+                    _ => {}
+                }
+                let dates = match (item.start, item.end) {
+                    (Some(start), Some(end)) => format!("{}, {}", start, end),
+                    (_, Some(end)) => {
+                        tags.push("milestone");
+                        format!("{}, 0d", end)
+                    }
+                    _ => continue,
+                };
+                tags.push(&item.document_id);
+                gantt.push_str(&format!("    {} :{}, {}\n", name, tags.join(", "), dates));
+            }
+        }
+        for item in self.lanes.iter().flat_map(|lane| &lane.items).filter(|item| !item.is_scheduled()) {
+            gantt.push_str(&format!(
+                "    %% Unscheduled: {}{}\n",
+                item.document_id,
+                item.schedule.as_ref().map(|schedule| format!(" ({})", schedule)).unwrap_or_default()
+            ));
+        }
+        gantt
+    }
+}
+
+/// Text safe to use in a gantt title, section or task name
+fn gantt_text(text: &str) -> String {
+    text.replace([':', ';', '#'], " ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    const DOCS: &str = "= Checkout Roadmap
+:forgepoint-type: product-roadmap
+:id: checkout-roadmap
+:product: Checkout
+:status: active
+
+== Major Initiatives
+
+* xref:epic:guest-checkout[]
+
+<<<
+
+= Guest checkout
+:forgepoint-type: epic
+:id: guest-checkout
+:status: in-progress
+:team: payments
+:created: 2024-09-02
+:target-release: v2.1
+
+<<<
+
+= Saved carts
+:forgepoint-type: shape-up-pitch
+:id: saved-carts
+:status: bet
+:appetite: small-batch
+:cycle: 2024-c4
+:team: designer=ana, programmers=bo, cy
+
+See xref:product-roadmap:checkout-roadmap[].
+
+<<<
+
+= Wallets
+:forgepoint-type: epic
+:id: wallets
+:status: draft
+:team: payments
+:target-release: 3.0
+
+<<<
+
+= Checkout 2.1
+:forgepoint-type: release-notes
+:id: checkout-2-1
+:version: 2.1
+:release-date: 2024-11-04
+";
+
+    fn timeline() -> Timeline {
+        let docs: Vec<ForgepointDocument> = DOCS
+            .split("<<<\n")
+            .map(|content| DocumentParser::new().parse_content(content, PathBuf::from("roadmap.adoc")).unwrap())
+            .collect();
+        let config = TimelineConfig {
+            releases: HashMap::new(),
+            cycles: HashMap::from([("2024-c4".to_string(), NaiveDate::from_ymd_opt(2024, 11, 11).unwrap())]),
+        };
+        Timeline::build(&docs, &config)
+    }
+
+    #[test]
+    fn test_build_timeline() {
+        let timeline = timeline();
+        assert_eq!(timeline.lanes.len(), 2);
+        assert_eq!(timeline.lanes[0].name(), "Checkout Roadmap");
+
+        let checkout: Vec<_> = timeline.lanes[0]
+            .items
+            .iter()
+            .map(|item| (item.document_id.as_str(), item.start.map(|d| d.to_string()), item.end.map(|d| d.to_string())))
+            .collect();
+        assert_eq!(
+            checkout,
+            vec![
+                ("guest-checkout", Some("2024-09-02".to_string()), Some("2024-11-04".to_string())),
+                ("saved-carts", Some("2024-11-11".to_string()), Some("2024-11-25".to_string())),
+            ]
+        );
+        assert_eq!(timeline.lanes[0].items[1].teams, vec!["ana", "bo", "cy"]);
+        assert_eq!(timeline.lanes[1].items[0].document_id, "wallets");
+        assert!(!timeline.lanes[1].items[0].is_scheduled());
+
+        let mut payments = timeline.clone();
+        payments.retain(Some("checkout"), Some("payments"), None);
+        assert_eq!(payments.lanes.len(), 1);
+        assert_eq!(payments.lanes[0].items.len(), 1);
+
+        let mut drafts = timeline;
+        drafts.retain(None, None, Some("draft"));
+        assert_eq!(drafts.lanes.len(), 1);
+        assert!(drafts.lanes[0].roadmap.is_none());
+    }
+
+    #[test]
+    fn test_render_gantt() {
+        let gantt = timeline().render_gantt("Roadmap: 2024");
+        assert_eq!(
+            gantt,
+            "gantt
+    title Roadmap 2024
+    dateFormat YYYY-MM-DD
+    section Checkout Roadmap
+    Guest checkout :active, guest-checkout, 2024-09-02, 2024-11-04
+    Saved carts :saved-carts, 2024-11-11, 2024-11-25
+    %% Unscheduled: wallets (3.0)
+"
+        );
+    }
+}
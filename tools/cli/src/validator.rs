@@ -1,6 +1,20 @@
-use crate::document::{CrossReference, ForgepointDocument};
+use crate::config::DueConfig;
+use crate::document::{CrossReference, ForgepointDocument, Section};
+use crate::due::find_due_items;
 use crate::error::{ForgepointError, Result};
-use crate::schema::SchemaLoader;
+use crate::flags::check_flag_variations;
+use crate::gherkin::check_gherkin;
+use crate::okr::check_okr;
+use crate::openapi::check_api_spec;
+use crate::progress::{check_progress_attribute, check_unchecked_items};
+use crate::sprint::{SprintItem, SprintPlan, SprintReport};
+use crate::schema::{
+    parse_date, AdditionalSections, AdditionalSectionsMode, AttributeError, CompiledSchema, SchemaLoader,
+    SectionContentRequirement, SectionRequirements,
+};
+use crate::suggest::{closest_match, closest_matches, did_you_mean};
+use crate::wsjf::check_wsjf;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -31,6 +45,7 @@ pub enum ErrorType {
     Reference,
     IdConflict,
     Format,
+    Schedule,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +64,11 @@ pub struct Location {
 pub struct DocumentValidator {
     schema_loader: SchemaLoader,
     document_index: HashMap<String, HashMap<String, DocumentInfo>>,
+    /// Report overdue reviews and stale documents as of this date
+    due_checks: Option<(DueConfig, NaiveDate)>,
+    /// Sprint plans and the stories and tasks they are reconciled with
+    sprint_plans: Vec<SprintPlan>,
+    sprint_items: Vec<SprintItem>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +82,23 @@ impl DocumentValidator {
         Self {
             schema_loader,
             document_index: HashMap::new(),
+            due_checks: None,
+            sprint_plans: Vec::new(),
+            sprint_items: Vec::new(),
+        }
+    }
+
+    /// Also warn about documents with due dates before `today`
+    pub fn with_due_checks(mut self, config: DueConfig, today: NaiveDate) -> Self {
+        self.due_checks = Some((config, today));
+        self
+    }
+
+    /// The date time-dependent checks are made against
+    fn today(&self) -> NaiveDate {
+        match &self.due_checks {
+            Some((_, today)) => *today,
+            None => chrono::Utc::now().date_naive(),
         }
     }
 
@@ -101,24 +138,38 @@ impl DocumentValidator {
                     message: format!("Unknown document type: {}", doc_type),
                     location: None,
                     rule: Some("valid-document-type".to_string()),
-                    suggestion: Some("Use 'forgepoint list-types' to see available document types".to_string()),
+                    suggestion: self.suggest_document_type(doc_type).or_else(|| {
+                        Some("Use 'forgepoint list-types' to see available document types".to_string())
+                    }),
                 });
-            } else {
+            } else if let Some(schema) = self.resolve_schema(doc, doc_type, &mut errors, &mut warnings) {
                 // Validate attributes against schema
-                match self.schema_loader.validate_attributes(doc_type, &doc.attributes) {
+                match schema.validate_attributes(&doc.attributes) {
                     Ok(schema_errors) => {
-                        for error in schema_errors {
+                        // An unsupported schema-version has already been reported
+                        let version_supported = schema.version.is_none() || schema.version.as_ref() == doc.schema_version();
+
+                        for error in schema_errors
+                            .into_iter()
+                            .filter(|e| version_supported || e.attribute.as_deref() != Some("schema-version"))
+                        {
+                            let location = error
+                                .attribute
+                                .as_deref()
+                                .filter(|_| error.keyword != "required")
+                                .and_then(|attribute| doc.attribute_location(attribute));
+
                             errors.push(ValidationError {
                                 error_type: ErrorType::Schema,
                                 severity: Severity::Error,
-                                message: error,
+                                message: Self::describe_attribute_error(&error, doc),
                                 location: Some(Location {
-                                    line: None,
-                                    column: None,
+                                    line: location.map(|l| l.line),
+                                    column: location.map(|l| l.column),
                                     section: Some("attributes".to_string()),
                                 }),
                                 rule: Some("schema-validation".to_string()),
-                                suggestion: None,
+                                suggestion: Self::suggest_attribute_fix(&error, doc),
                             });
                         }
                     }
@@ -134,28 +185,63 @@ impl DocumentValidator {
                     }
                 }
 
+                // Validate the order of related dates
+                errors.extend(Self::validate_date_rules(doc, schema));
+
                 // Validate required sections
-                let required_sections = self.schema_loader.get_required_sections(doc_type);
+                let required_sections = schema.required_sections();
+                let optional_sections = schema.optional_sections();
                 let document_sections: Vec<String> = doc.level_2_sections()
                     .iter()
                     .map(|s| s.title.clone())
                     .collect();
 
-                for required_section in required_sections {
-                    if !document_sections.contains(&required_section) {
+                // Sections the schema doesn't know about may be misspelled required ones
+                let unknown_sections: Vec<&Section> = doc
+                    .level_2_sections()
+                    .into_iter()
+                    .filter(|s| !required_sections.contains(&s.title) && !optional_sections.contains(&s.title))
+                    .collect();
+
+                for required_section in required_sections.iter() {
+                    if !document_sections.contains(required_section) {
+                        let near_miss = closest_match(
+                            required_section,
+                            unknown_sections.iter().map(|s| s.title.as_str()),
+                        )
+                        .and_then(|title| unknown_sections.iter().find(|s| s.title == title));
+
                         errors.push(ValidationError {
                             error_type: ErrorType::Structure,
                             severity: Severity::Error,
                             message: format!("Missing required section: {}", required_section),
-                            location: None,
+                            location: near_miss.map(|s| Location {
+                                line: s.line_number,
+                                column: None,
+                                section: Some(s.title.clone()),
+                            }),
                             rule: Some("required-sections".to_string()),
-                            suggestion: Some(format!("Add a '== {}' section to your document", required_section)),
+                            suggestion: Some(match near_miss {
+                                Some(s) => format!("Did you mean '{}'? Rename '== {}' to '== {}'", required_section, s.title, required_section),
+                                None => format!("Add a '== {}' section to your document", required_section),
+                            }),
                         });
                     }
                 }
 
+                // Validate section content, ordering and unknown sections
+                for error in Self::validate_section_content(doc, schema) {
+                    match error.severity {
+                        Severity::Error => errors.push(error),
+                        Severity::Warning => warnings.push(error),
+                    }
+                }
+
+                // Validate requirements that depend on attribute values
+                errors.extend(Self::validate_structural_conditions(doc, schema));
+
                 // Validate abstract requirement
-                if self.schema_loader.is_abstract_required(doc_type) && doc.abstract_content().is_none() {
+                if schema.is_abstract_required() && doc.abstract_content().is_none() {
                     errors.push(ValidationError {
                         error_type: ErrorType::Structure,
                         severity: Severity::Error,
@@ -167,7 +253,7 @@ impl DocumentValidator {
                 }
 
                 // Validate title format
-                if let Some(title_format) = self.schema_loader.get_title_format(doc_type) {
+                if let Some(title_format) = schema.title_format() {
                     if title_format.contains('{') {
                         warnings.push(ValidationError {
                             error_type: ErrorType::Format,
@@ -179,6 +265,27 @@ impl DocumentValidator {
                         });
                     }
                 }
+
+                // Report finished documents with open checklist items, leaving
+                // out items a schema rule already reported
+                let reported: Vec<usize> = errors
+                    .iter()
+                    .filter(|e| e.rule.as_deref() == Some("section-checklist"))
+                    .filter_map(|e| e.location.as_ref().and_then(|l| l.line))
+                    .collect();
+                warnings.extend(check_unchecked_items(doc, &reported));
+                match doc.document_type().map(String::as_str) {
+                    Some("okr") => warnings.extend(check_okr(doc, self.today())),
+                    _ => warnings.extend(check_progress_attribute(doc)),
+                }
+                warnings.extend(check_wsjf(doc));
+
+                // Report overdue reviews and stale documents
+                if let Some((due_config, today)) = &self.due_checks {
+                    for item in find_due_items(doc, schema, due_config, *today) {
+                        warnings.push(item.to_warning());
+                    }
+                }
             }
         }
 
@@ -194,6 +301,17 @@ impl DocumentValidator {
             });
         }
 
+        // Validate embedded Gherkin scenarios
+        errors.extend(check_gherkin(doc));
+
+        // Validate feature-flag defaults against their variations
+        errors.extend(check_flag_variations(doc));
+
+        // Validate api-specs against their linked specification file
+        if doc.document_type().map(String::as_str) == Some("api-spec") {
+            errors.extend(check_api_spec(doc));
+        }
+
         // Validate cross-references
         let reference_errors = self.validate_references(doc);
         errors.extend(reference_errors.errors);
@@ -253,7 +371,9 @@ impl DocumentValidator {
                             section: None,
                         }),
                         rule: Some("reference-integrity".to_string()),
-                        suggestion: Some("Create the referenced document or fix the reference".to_string()),
+                        suggestion: self.suggest_reference(&reference).or_else(|| {
+                            Some("Create the referenced document or fix the reference".to_string())
+                        }),
                     });
                 }
             }
@@ -262,6 +382,674 @@ impl DocumentValidator {
         ValidationResults { errors, warnings }
     }
 
+    /// Validate section content against the schema's content requirements
+    fn validate_section_content(doc: &ForgepointDocument, schema: &CompiledSchema) -> Vec<ValidationError> {
+        match schema.section_requirements() {
+            Some(requirements) => Self::check_sections(doc, None, requirements),
+            None => Vec::new(),
+        }
+    }
+
+    /// Validate the section requirements of the schema's conditions that the
+    /// document's attributes meet
+    fn validate_structural_conditions(doc: &ForgepointDocument, schema: &CompiledSchema) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for condition in schema.structural_conditions().iter().filter(|c| c.applies(&doc.attributes)) {
+            let Some(requirements) = &condition.then.sections else {
+                continue;
+            };
+            let when = condition.describe();
+
+            // A missing section is reported on the attribute that made it required
+            let condition_location = condition
+                .when
+                .keys()
+                .min()
+                .and_then(|attribute| doc.attribute_location(attribute))
+                .map(|location| Location {
+                    line: Some(location.line),
+                    column: Some(location.column),
+                    section: Some("attributes".to_string()),
+                });
+
+            for required in requirements.required.iter().flatten() {
+                if !doc.level_2_sections().iter().any(|section| &section.title == required) {
+                    errors.push(ValidationError {
+                        error_type: ErrorType::Structure,
+                        severity: Severity::Error,
+                        message: format!("Missing required section: {} when {}", required, when),
+                        location: condition_location.clone(),
+                        rule: Some("required-sections".to_string()),
+                        suggestion: Some(format!("Add a '== {}' section to your document", required)),
+                    });
+                }
+            }
+
+            for mut error in Self::check_sections(doc, None, requirements) {
+                error.message = format!("{} when {}", error.message, when);
+                errors.push(error);
+            }
+        }
+
+        errors
+    }
+
+    /// Check the sections below `parent` (or the level-2 sections when there is
+    /// no parent) against a set of section requirements
+    fn check_sections(
+        doc: &ForgepointDocument,
+        parent: Option<usize>,
+        requirements: &SectionRequirements,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        let candidates: Vec<(usize, &Section)> = match parent {
+            Some(index) => doc.child_sections(index),
+            None => doc
+                .sections
+                .iter()
+                .enumerate()
+                .filter(|(_, section)| section.level == 2)
+                .collect(),
+        };
+
+        // Required level-2 sections are checked by the required-sections rule
+        if let Some(parent_section) = parent.and_then(|index| doc.sections.get(index)) {
+            for required in requirements.required.iter().flatten() {
+                if !candidates.iter().any(|(_, section)| &section.title == required) {
+                    errors.push(Self::section_error(
+                        parent_section,
+                        "required-subsections",
+                        format!("Section '{}' is missing required subsection: {}", parent_section.title, required),
+                        Some(format!(
+                            "Add a '{} {}' subsection",
+                            "=".repeat(parent_section.level + 1),
+                            required
+                        )),
+                    ));
+                }
+            }
+        }
+
+        if requirements.ordered.unwrap_or(false) {
+            errors.extend(Self::check_section_order(&candidates, requirements));
+        }
+
+        errors.extend(Self::check_unknown_sections(&candidates, requirements));
+
+        let mut content_requirements: Vec<_> = requirements.content.iter().flatten().collect();
+        content_requirements.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (title, requirement) in content_requirements {
+            for (index, section) in candidates.iter().filter(|(_, section)| &section.title == title) {
+                errors.extend(Self::check_section_content(doc, *index, section, requirement));
+
+                if let Some(subsections) = &requirement.subsections {
+                    errors.extend(Self::check_sections(doc, Some(*index), subsections));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Check that sections appear in the order the schema lists them: the
+    /// required sections, then the optional ones
+    fn check_section_order(candidates: &[(usize, &Section)], requirements: &SectionRequirements) -> Vec<ValidationError> {
+        let expected_order: Vec<&String> = requirements.required.iter().chain(requirements.optional.iter()).flatten().collect();
+        let mut errors = Vec::new();
+        let mut previous: Option<(usize, &Section)> = None;
+
+        for (_, section) in candidates {
+            let position = match expected_order.iter().position(|title| *title == &section.title) {
+                Some(position) => position,
+                None => continue,
+            };
+
+            match previous {
+                Some((previous_position, previous_section)) if position < previous_position => {
+                    errors.push(Self::section_error(
+                        section,
+                        "section-order",
+                        format!(
+                            "Section '{}' should appear before '{}'",
+                            section.title, previous_section.title
+                        ),
+                        Some(format!(
+                            "Expected order: {}",
+                            expected_order.iter().map(|title| title.as_str()).collect::<Vec<_>>().join(", ")
+                        )),
+                    ));
+                }
+                _ => previous = Some((position, section)),
+            }
+        }
+
+        errors
+    }
+
+    /// Report sections that the schema neither requires nor allows
+    fn check_unknown_sections(candidates: &[(usize, &Section)], requirements: &SectionRequirements) -> Vec<ValidationError> {
+        let severity = match requirements.additional_sections {
+            Some(AdditionalSections::Allowed(false)) => Severity::Error,
+            Some(AdditionalSections::Mode(AdditionalSectionsMode::Warn)) => Severity::Warning,
+            _ => return Vec::new(),
+        };
+
+        let known_sections: Vec<&str> = requirements
+            .required
+            .iter()
+            .chain(requirements.optional.iter())
+            .flatten()
+            .map(String::as_str)
+            .collect();
+
+        candidates
+            .iter()
+            .filter(|(_, section)| !known_sections.contains(&section.title.as_str()))
+            .map(|(_, section)| {
+                let suggestion = match closest_match(&section.title, known_sections.iter().copied()) {
+                    Some(known) => format!("Did you mean '{}'?", known),
+                    None => format!("Allowed sections: {}", known_sections.join(", ")),
+                };
+
+                let mut error = Self::section_error(
+                    section,
+                    "unknown-sections",
+                    format!("Unexpected section: {}", section.title),
+                    Some(suggestion),
+                );
+                error.severity = severity.clone();
+                error
+            })
+            .collect()
+    }
+
+    /// Check a single section against its content requirement
+    fn check_section_content(
+        doc: &ForgepointDocument,
+        index: usize,
+        section: &Section,
+        requirement: &SectionContentRequirement,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let lines = doc.section_lines(index);
+
+        if let Some(checklist) = &requirement.checklist {
+            let count = doc.section_checklist_items(index).len();
+
+            if let Some(min_items) = checklist.min_items {
+                if count < min_items {
+                    errors.push(Self::section_error(
+                        section,
+                        "section-checklist",
+                        format!(
+                            "Section '{}' has {} checklist item(s), expected at least {}",
+                            section.title, count, min_items
+                        ),
+                        Some("Add checklist items using '* [ ] Item'".to_string()),
+                    ));
+                }
+            }
+
+            if let Some(max_items) = checklist.max_items {
+                if count > max_items {
+                    errors.push(Self::section_error(
+                        section,
+                        "section-checklist",
+                        format!(
+                            "Section '{}' has {} checklist item(s), expected at most {}",
+                            section.title, count, max_items
+                        ),
+                        None,
+                    ));
+                }
+            }
+
+            if checklist.all_checked.unwrap_or(false) {
+                for item in doc.section_checklist_items(index).into_iter().filter(|item| !item.checked) {
+                    errors.push(ValidationError {
+                        error_type: ErrorType::Structure,
+                        severity: Severity::Error,
+                        message: format!("Unchecked item in section '{}': {}", section.title, item.text),
+                        location: Some(Location {
+                            line: Some(item.line_number),
+                            column: None,
+                            section: Some(section.title.clone()),
+                        }),
+                        rule: Some("section-checklist".to_string()),
+                        suggestion: Some("Check the item with '* [x]' once it is met".to_string()),
+                    });
+                }
+            }
+        }
+
+        if let Some(table) = &requirement.table {
+            let headers = Self::table_headers(&lines);
+            let missing_columns = headers
+                .iter()
+                .map(|header| {
+                    table
+                        .columns
+                        .iter()
+                        .filter(|column| !header.iter().any(|cell| cell.eq_ignore_ascii_case(column)))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .min_by_key(|missing| missing.len());
+
+            match missing_columns {
+                None => errors.push(Self::section_error(
+                    section,
+                    "section-table",
+                    format!("Section '{}' must contain a table", section.title),
+                    Some(format!("Add a |=== table with columns: {}", table.columns.join(", "))),
+                )),
+                Some(missing) if !missing.is_empty() => errors.push(Self::section_error(
+                    section,
+                    "section-table",
+                    format!(
+                        "Table in section '{}' is missing columns: {}",
+                        section.title,
+                        missing.join(", ")
+                    ),
+                    Some(format!("Expected columns: {}", table.columns.join(", "))),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        if let Some(source_block) = &requirement.source_block {
+            let source_regex = regex::Regex::new(r"^\[source\s*,\s*([^,\]\s]+)").unwrap();
+            let has_block = lines.iter().any(|(_, line)| {
+                source_regex
+                    .captures(line.trim())
+                    .map(|cap| cap[1].eq_ignore_ascii_case(&source_block.language))
+                    .unwrap_or(false)
+            });
+
+            if !has_block {
+                errors.push(Self::section_error(
+                    section,
+                    "section-source-block",
+                    format!(
+                        "Section '{}' must contain a [source,{}] block",
+                        section.title, source_block.language
+                    ),
+                    Some(format!("Add a [source,{}] block delimited by ----", source_block.language)),
+                ));
+            }
+        }
+
+        if let Some(min_words) = requirement.min_words {
+            let word_count: usize = lines
+                .iter()
+                .map(|(_, line)| line.trim())
+                .filter(|line| !line.starts_with("//") && !line.starts_with('='))
+                .map(|line| line.split_whitespace().count())
+                .sum();
+
+            if word_count < min_words {
+                errors.push(Self::section_error(
+                    section,
+                    "section-min-words",
+                    format!(
+                        "Section '{}' has {} word(s), expected at least {}",
+                        section.title, word_count, min_words
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// Extract the header row of each |=== table in a block of lines
+    fn table_headers(lines: &[(usize, &str)]) -> Vec<Vec<String>> {
+        let mut headers = Vec::new();
+        let mut in_table = false;
+        let mut header: Vec<String> = Vec::new();
+        let mut header_done = false;
+
+        for (_, line) in lines {
+            let line = line.trim();
+
+            if line == "|===" {
+                if in_table && !header.is_empty() {
+                    headers.push(std::mem::take(&mut header));
+                }
+                in_table = !in_table;
+                header.clear();
+                header_done = false;
+                continue;
+            }
+
+            if !in_table || header_done {
+                continue;
+            }
+
+            // The header row ends at the first blank line, or after a single
+            // line that holds more than one cell
+            if line.is_empty() {
+                header_done = !header.is_empty();
+            } else if line.starts_with('|') {
+                let cells: Vec<String> = line
+                    .split('|')
+                    .skip(1)
+                    .map(|cell| cell.trim().to_string())
+                    .collect();
+                header_done = header.is_empty() && cells.len() > 1;
+                header.extend(cells);
+            }
+        }
+
+        headers
+    }
+
+    /// Build a structure error located at a section heading
+    fn section_error(section: &Section, rule: &str, message: String, suggestion: Option<String>) -> ValidationError {
+        ValidationError {
+            error_type: ErrorType::Structure,
+            severity: Severity::Error,
+            message,
+            location: Some(Location {
+                line: section.line_number,
+                column: None,
+                section: Some(section.title.clone()),
+            }),
+            rule: Some(rule.to_string()),
+            suggestion,
+        }
+    }
+
+    /// Check that a document's lifecycle state (usually `:status:`) moved
+    /// along an allowed transition since an earlier version of it
+    pub fn validate_transition(&self, doc: &ForgepointDocument, previous: &ForgepointDocument) -> Option<ValidationError> {
+        let doc_type = doc.document_type()?;
+        if previous.document_type() != Some(doc_type) {
+            return None;
+        }
+        let schema = self
+            .schema_loader
+            .get_schema_for_version(doc_type, doc.schema_version().map(String::as_str))
+            .or_else(|| self.schema_loader.get_schema(doc_type))?;
+        let lifecycle = schema.lifecycle.as_ref()?;
+
+        let from = previous.attributes.get(&lifecycle.attribute)?;
+        let to = doc.attributes.get(&lifecycle.attribute)?;
+        // States the lifecycle doesn't know are reported by schema validation
+        if lifecycle.allows(from, to) || !lifecycle.knows(from) || !lifecycle.knows(to) {
+            return None;
+        }
+
+        let allowed = lifecycle.transitions.get(from).cloned().unwrap_or_default();
+        let location = doc.attribute_location(&lifecycle.attribute);
+        Some(ValidationError {
+            error_type: ErrorType::Schema,
+            severity: Severity::Error,
+            message: format!(
+                "Illegal {} change for {}: {} → {} (allowed from {}: {})",
+                lifecycle.attribute,
+                doc_type,
+                from,
+                to,
+                from,
+                if allowed.is_empty() { "none".to_string() } else { allowed.join(", ") }
+            ),
+            location: Some(Location {
+                line: location.map(|l| l.line),
+                column: location.map(|l| l.column),
+                section: Some("attributes".to_string()),
+            }),
+            rule: Some("status-transition".to_string()),
+            suggestion: Some(match lifecycle.path(from, to) {
+                Some(path) => format!("Move through the intermediate states: {}", path.join(" → ")),
+                None => format!("'{}' cannot be reached from '{}'", to, from),
+            }),
+        })
+    }
+
+    /// Check the schema's date rules, reporting each violation on the header
+    /// line of the attribute the rule constrains
+    fn validate_date_rules(doc: &ForgepointDocument, schema: &CompiledSchema) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for rule in &schema.date_rules {
+            let Some((reference, inclusive)) = rule.reference() else {
+                continue;
+            };
+            // Missing or malformed dates are reported by schema validation
+            let (Some(value), Some(reference_value)) = (doc.attributes.get(&rule.attribute), doc.attributes.get(reference))
+            else {
+                continue;
+            };
+            let (Some(date), Some(reference_date)) = (parse_date(value), parse_date(reference_value)) else {
+                continue;
+            };
+            if date > reference_date || (inclusive && date == reference_date) {
+                continue;
+            }
+
+            let location = doc.attribute_location(&rule.attribute);
+            let reference_line = doc.attribute_location(reference).map(|l| l.line);
+            errors.push(ValidationError {
+                error_type: ErrorType::Schema,
+                severity: Severity::Error,
+                message: format!(
+                    "Date :{}: ({}) must be {} :{}: ({})",
+                    rule.attribute,
+                    value,
+                    if inclusive { "on or after" } else { "after" },
+                    reference,
+                    reference_value
+                ),
+                location: Some(Location {
+                    line: location.map(|l| l.line),
+                    column: location.map(|l| l.column),
+                    section: Some("attributes".to_string()),
+                }),
+                rule: Some("date-order".to_string()),
+                suggestion: rule.description.clone().or_else(|| {
+                    reference_line.map(|line| format!("Check :{}: against :{}: on line {}", rule.attribute, reference, line))
+                }),
+            });
+        }
+
+        errors
+    }
+
+    /// Pick the schema matching the document's `:schema-version:`. An unknown
+    /// version is reported and the current schema is used instead; a deprecated
+    /// one produces a warning pointing at `forgepoint migrate`.
+    fn resolve_schema(
+        &self,
+        doc: &ForgepointDocument,
+        doc_type: &str,
+        errors: &mut Vec<ValidationError>,
+        warnings: &mut Vec<ValidationError>,
+    ) -> Option<&CompiledSchema> {
+        let version = doc.schema_version().map(String::as_str);
+        let location = doc.attribute_location("schema-version").map(|l| Location {
+            line: Some(l.line),
+            column: Some(l.column),
+            section: Some("attributes".to_string()),
+        });
+        let latest = self.schema_loader.get_schema(doc_type)?;
+
+        match self.schema_loader.get_schema_for_version(doc_type, version) {
+            Some(schema) => {
+                if schema.deprecated {
+                    warnings.push(ValidationError {
+                        error_type: ErrorType::Schema,
+                        severity: Severity::Warning,
+                        message: format!(
+                            "Schema version {} of '{}' is deprecated",
+                            version.unwrap_or_default(),
+                            doc_type
+                        ),
+                        location,
+                        rule: Some("deprecated-schema-version".to_string()),
+                        suggestion: latest
+                            .version
+                            .as_deref()
+                            .filter(|latest| Some(*latest) != version)
+                            .map(|latest| format!("Run 'forgepoint migrate --to {}' to upgrade the document", latest)),
+                    });
+                }
+                Some(schema)
+            }
+            None => {
+                let versions = self.schema_loader.get_schema_versions(doc_type);
+                errors.push(ValidationError {
+                    error_type: ErrorType::Schema,
+                    severity: Severity::Error,
+                    message: format!(
+                        "Unsupported schema version {} for '{}', supported versions: {}",
+                        version.unwrap_or_default(),
+                        doc_type,
+                        versions.join(", ")
+                    ),
+                    location,
+                    rule: Some("schema-version".to_string()),
+                    suggestion: latest
+                        .version
+                        .as_ref()
+                        .map(|latest| format!("Use ':schema-version: {}'", latest)),
+                });
+                Some(latest)
+            }
+        }
+    }
+
+    /// Suggest known document types close to an unknown one
+    fn suggest_document_type(&self, doc_type: &str) -> Option<String> {
+        let document_types = self.schema_loader.get_document_types();
+        let matches = closest_matches(doc_type, document_types.iter().map(|dt| dt.doc_type.as_str()), 3);
+        did_you_mean(&matches)
+    }
+
+    /// Turn a structured schema violation into a human-readable message
+    fn describe_attribute_error(error: &AttributeError, doc: &ForgepointDocument) -> String {
+        let attribute = match &error.attribute {
+            Some(attribute) => attribute,
+            None => return format!("Schema validation error: {}", error.message),
+        };
+        let value = doc.attributes.get(attribute).map(String::as_str).unwrap_or_default();
+        let expected = error.expected.join(", ");
+
+        let message = match error.keyword.as_str() {
+            "required" => format!("Missing required attribute :{}:", attribute),
+            "additionalProperties" => format!("Unknown attribute :{}:", attribute),
+            "enum" => format!(
+                "Invalid value '{}' for :{}:, allowed values: {}",
+                value, attribute, expected
+            ),
+            "const" => format!("Attribute :{}: must be '{}', found '{}'", attribute, expected, value),
+            "pattern" => format!(
+                "Value '{}' for :{}: does not match the pattern {}",
+                value, attribute, expected
+            ),
+            "format" => format!("Value '{}' for :{}: is not a valid {}", value, attribute, expected),
+            "type" => format!("Value '{}' for :{}: must be of type {}", value, attribute, expected),
+            "minimum" => format!(
+                "Value '{}' for :{}: must be at least {}",
+                value, attribute, expected
+            ),
+            "exclusiveMinimum" => format!(
+                "Value '{}' for :{}: must be greater than {}",
+                value, attribute, expected
+            ),
+            "maximum" => format!(
+                "Value '{}' for :{}: must be at most {}",
+                value, attribute, expected
+            ),
+            "exclusiveMaximum" => format!(
+                "Value '{}' for :{}: must be less than {}",
+                value, attribute, expected
+            ),
+            "minLength" => format!(
+                "Value for :{}: must be at least {} characters long",
+                attribute, expected
+            ),
+            "maxLength" => format!(
+                "Value for :{}: must be at most {} characters long",
+                attribute, expected
+            ),
+            _ => format!("Invalid value for :{}:: {}", attribute, error.message),
+        };
+
+        match &error.condition {
+            Some(condition) => format!("{} when {}", message, condition),
+            None => message,
+        }
+    }
+
+    /// Suggest a fix for a schema violation, such as the closest allowed enum
+    /// value or the closest known attribute name
+    fn suggest_attribute_fix(error: &AttributeError, doc: &ForgepointDocument) -> Option<String> {
+        let attribute = error.attribute.as_ref()?;
+
+        match error.keyword.as_str() {
+            "enum" => {
+                let value = doc.attributes.get(attribute)?;
+                let matches = closest_matches(value, error.expected.iter().map(String::as_str), 3);
+                let options: Vec<String> = matches.iter().map(|m| format!(":{}: {}", attribute, m)).collect();
+                did_you_mean(&options)
+            }
+            "additionalProperties" => {
+                let matches = closest_matches(attribute, error.expected.iter().map(String::as_str), 3);
+                let options: Vec<String> = matches.iter().map(|m| format!(":{}:", m)).collect();
+                did_you_mean(&options)
+            }
+            "required" => Some(format!("Add ':{}: <value>' to the document header", attribute)),
+            "format" => match error.expected.first().map(String::as_str) {
+                Some("date") => Some("Use the YYYY-MM-DD format, e.g. 2024-01-15".to_string()),
+                Some("date-time") => Some("Use the RFC 3339 format, e.g. 2024-01-15T09:00:00Z".to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Suggest indexed documents close to a reference that doesn't resolve
+    fn suggest_reference(&self, reference: &CrossReference) -> Option<String> {
+        let format_xref = |ref_type: &str, id: &str| format!("xref:{}:{}[]", ref_type, id);
+
+        // A close ID of the same type, e.g. a typo in the ID
+        if let Some(docs) = self.document_index.get(&reference.ref_type) {
+            let matches = closest_matches(&reference.id, docs.keys().map(String::as_str), 3);
+            if !matches.is_empty() {
+                let options: Vec<String> = matches.iter().map(|id| format_xref(&reference.ref_type, id)).collect();
+                return did_you_mean(&options);
+            }
+        }
+
+        // The same ID under a different type
+        let mut other_types: Vec<&str> = self
+            .document_index
+            .iter()
+            .filter(|(_, docs)| docs.contains_key(&reference.id))
+            .map(|(doc_type, _)| doc_type.as_str())
+            .collect();
+        other_types.sort();
+        if !other_types.is_empty() {
+            let options: Vec<String> = other_types.iter().map(|t| format_xref(t, &reference.id)).collect();
+            return did_you_mean(&options);
+        }
+
+        // A misspelled document type
+        if !self.schema_loader.is_valid_document_type(&reference.ref_type) {
+            let document_types = self.schema_loader.get_document_types();
+            let matches = closest_matches(&reference.ref_type, document_types.iter().map(|dt| dt.doc_type.as_str()), 3);
+            let options: Vec<String> = matches.iter().map(|t| format_xref(t, &reference.id)).collect();
+            return did_you_mean(&options);
+        }
+
+        None
+    }
+
     /// Index a document for cross-reference validation
     fn index_document(&mut self, doc: &ForgepointDocument) {
         if let (Some(doc_type), Some(doc_id)) = (doc.document_type(), doc.document_id()) {
@@ -279,6 +1067,9 @@ impl DocumentValidator {
                     },
                 );
         }
+
+        self.sprint_plans.extend(SprintPlan::from_document(doc));
+        self.sprint_items.extend(SprintItem::from_document(doc));
     }
 
     /// Check for duplicate IDs across all documents
@@ -326,9 +1117,23 @@ impl DocumentValidator {
         errors
     }
 
+    /// Check sprint plans against the points of the stories and tasks
+    /// assigned to them, returning warnings by sprint-plan ID
+    pub fn check_sprint_commitments(&self) -> Vec<(String, ValidationError)> {
+        self.sprint_plans
+            .iter()
+            .filter_map(|plan| {
+                let report = SprintReport::build(plan.clone(), &self.sprint_items);
+                Some((plan.document_id.clone(), report.check_commitment()?))
+            })
+            .collect()
+    }
+
     /// Clear the document index
     pub fn clear_index(&mut self) {
         self.document_index.clear();
+        self.sprint_plans.clear();
+        self.sprint_items.clear();
     }
 
     /// Get the document index
@@ -361,6 +1166,7 @@ mod tests {
             attributes: HashMap::new(),
             content: "test content".to_string(),
             sections: Vec::new(),
+            attribute_locations: HashMap::new(),
         };
 
         let mut validator = DocumentValidator::new(SchemaLoader::new("test"));
@@ -370,4 +1176,578 @@ mod tests {
         assert_eq!(result.errors.len(), 1);
         assert!(result.errors[0].message.contains("missing required Forgepoint attributes"));
     }
-}
\ No newline at end of file
+
+    /// Write a schema set with one document type to `schema_dir` and load a
+    /// validator for it. `versions` are older schemas as (version, schema)
+    /// pairs, registered as deprecated
+    fn validator_with_versions(
+        schema_dir: &std::path::Path,
+        doc_type: &str,
+        schema: &str,
+        versions: &[(&str, &str)],
+    ) -> DocumentValidator {
+        let mut registered = Vec::new();
+        for (version, version_schema) in versions {
+            let file = format!("{}-{}.json", doc_type, version);
+            std::fs::write(schema_dir.join(&file), version_schema).unwrap();
+            registered.push(serde_json::json!({ "version": version, "schema": file, "deprecated": true }));
+        }
+        let file = format!("{}.json", doc_type);
+        std::fs::write(schema_dir.join(&file), schema).unwrap();
+        let index = serde_json::json!({
+            "schemaVersion": "1.0",
+            "schemas": { doc_type: { "$ref": file } },
+            "documentTypes": [{
+                "type": doc_type,
+                "name": doc_type,
+                "description": format!("Test {}", doc_type),
+                "category": "design",
+                "schema": file,
+                "versions": registered,
+            }]
+        });
+        std::fs::write(schema_dir.join("index.json"), index.to_string()).unwrap();
+
+        let mut schema_loader = SchemaLoader::new(schema_dir);
+        schema_loader.load_schemas().unwrap();
+        DocumentValidator::new(schema_loader)
+    }
+
+    fn validator_with_schema(schema_dir: &std::path::Path, doc_type: &str, schema: &str) -> DocumentValidator {
+        validator_with_versions(schema_dir, doc_type, schema, &[])
+    }
+
+    fn section_content_validator(schema_dir: &std::path::Path) -> DocumentValidator {
+        let okr_schema = r#"{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": { "forgepoint-type": { "const": "okr" } },
+            "structuralRequirements": {
+                "sections": {
+                    "required": ["Key Results"],
+                    "content": {
+                        "Key Results": { "checklist": { "minItems": 1, "maxItems": 2 } },
+                        "Risks": { "table": { "columns": ["Risk", "Impact"] } },
+                        "Scenarios": { "sourceBlock": { "language": "gherkin" } },
+                        "Context": {
+                            "minWords": 5,
+                            "subsections": { "required": ["Background"] }
+                        }
+                    }
+                }
+            }
+        }"#;
+        validator_with_schema(schema_dir, "okr", okr_schema)
+    }
+
+    fn rule_errors<'a>(result: &'a ValidationResult, rule: &str) -> Vec<&'a ValidationError> {
+        result
+            .errors
+            .iter()
+            .filter(|e| e.rule.as_deref() == Some(rule))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_section_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut validator = section_content_validator(temp_dir.path());
+
+        let content = r#"= Grow Engagement
+:forgepoint-type: okr
+:id: grow-engagement
+:schema-version: 1.0
+
+== Key Results
+
+* [ ] First
+* [x] Second
+* [ ] Third
+
+== Risks
+
+|===
+| Risk | Likelihood
+
+| Churn | High
+|===
+
+== Scenarios
+
+[source,cucumber]
+----
+Scenario: Nothing
+----
+
+== Context
+
+Too short.
+
+=== Details
+
+More.
+"#;
+        let doc = DocumentParser::new().parse_content(content, "okr.adoc".into()).unwrap();
+        let result = validator.validate_document(&doc);
+
+        let checklist = rule_errors(&result, "section-checklist");
+        assert_eq!(checklist.len(), 1);
+        assert!(checklist[0].message.contains("at most 2"));
+        assert_eq!(checklist[0].location.as_ref().unwrap().line, Some(6));
+
+        let table = rule_errors(&result, "section-table");
+        assert_eq!(table.len(), 1);
+        assert!(table[0].message.contains("missing columns: Impact"));
+        assert_eq!(table[0].location.as_ref().unwrap().line, Some(12));
+
+        let source = rule_errors(&result, "section-source-block");
+        assert_eq!(source.len(), 1);
+        assert_eq!(source[0].location.as_ref().unwrap().line, Some(20));
+
+        let words = rule_errors(&result, "section-min-words");
+        assert_eq!(words.len(), 1);
+        assert!(words[0].message.contains("has 3 word(s)"));
+
+        let subsections = rule_errors(&result, "required-subsections");
+        assert_eq!(subsections.len(), 1);
+        assert!(subsections[0].message.contains("Background"));
+        assert_eq!(subsections[0].location.as_ref().unwrap().line, Some(27));
+    }
+
+    #[test]
+    fn test_section_order_and_unknown_sections() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let story_schema = r#"{
+            "type": "object",
+            "structuralRequirements": {
+                "sections": {
+                    "required": ["Acceptance Criteria", "Scenarios"],
+                    "optional": ["Technical Notes"],
+                    "ordered": true,
+                    "additionalSections": "warn"
+                }
+            }
+        }"#;
+        let mut validator = validator_with_schema(temp_dir.path(), "story", story_schema);
+
+        let content = r#"= Login
+:forgepoint-type: story
+:id: login
+:schema-version: 1.0
+
+== Scenarios
+
+== Acceptance Criteria
+
+== Acceptence Criterias
+"#;
+        let doc = DocumentParser::new().parse_content(content, "story.adoc".into()).unwrap();
+        let result = validator.validate_document(&doc);
+
+        let order = rule_errors(&result, "section-order");
+        assert_eq!(order.len(), 1);
+        assert_eq!(order[0].message, "Section 'Acceptance Criteria' should appear before 'Scenarios'");
+        assert_eq!(order[0].location.as_ref().unwrap().line, Some(8));
+
+        let unknown: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| w.rule.as_deref() == Some("unknown-sections"))
+            .collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("Did you mean 'Acceptance Criteria'?"));
+        assert_eq!(unknown[0].location.as_ref().unwrap().line, Some(10));
+
+        // Optional sections follow the required ones
+        let content = "= Login\n:forgepoint-type: story\n:id: login\n:schema-version: 1.0\n\n== Acceptance Criteria\n\n== Technical Notes\n\n== Scenarios\n";
+        let doc = DocumentParser::new().parse_content(content, "story.adoc".into()).unwrap();
+        let result = validator.validate_document(&doc);
+        let order = rule_errors(&result, "section-order");
+        assert_eq!(order.len(), 1);
+        assert_eq!(order[0].message, "Section 'Scenarios' should appear before 'Technical Notes'");
+        assert_eq!(
+            order[0].suggestion.as_deref(),
+            Some("Expected order: Acceptance Criteria, Scenarios, Technical Notes")
+        );
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let story_schema = r#"{
+            "type": "object",
+            "properties": {
+                "status": { "enum": ["draft", "ready", "in-progress", "done"] }
+            },
+            "structuralRequirements": {
+                "sections": { "required": ["Acceptance Criteria"] }
+            }
+        }"#;
+        let mut validator = validator_with_schema(temp_dir.path(), "story", story_schema);
+        let parser = DocumentParser::new();
+
+        let login = parser
+            .parse_content(
+                "= Login\n:forgepoint-type: story\n:id: auth-login\n:schema-version: 1.0\n\n== Acceptance Criteria\n",
+                "login.adoc".into(),
+            )
+            .unwrap();
+        assert!(validator.validate_document(&login).valid);
+
+        let content = r#"= Logout
+:forgepoint-type: story
+:id: auth-logout
+:status: inprogress
+:schema-version: 1.0
+
+== Acceptence Criteria
+
+Depends on xref:story:auth-logn[].
+"#;
+        let doc = parser.parse_content(content, "logout.adoc".into()).unwrap();
+        let result = validator.validate_document(&doc);
+
+        let schema = rule_errors(&result, "schema-validation");
+        assert_eq!(schema.len(), 1);
+        assert_eq!(
+            schema[0].message,
+            "Invalid value 'inprogress' for :status:, allowed values: draft, ready, in-progress, done"
+        );
+        assert_eq!(schema[0].suggestion.as_deref(), Some("Did you mean ':status: in-progress'?"));
+        let location = schema[0].location.as_ref().unwrap();
+        assert_eq!((location.line, location.column), (Some(4), Some(10)));
+
+        let sections = rule_errors(&result, "required-sections");
+        assert_eq!(sections.len(), 1);
+        assert!(sections[0].suggestion.as_deref().unwrap().starts_with("Did you mean 'Acceptance Criteria'?"));
+        assert_eq!(sections[0].location.as_ref().unwrap().line, Some(7));
+
+        let references = rule_errors(&result, "reference-integrity");
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].suggestion.as_deref(), Some("Did you mean 'xref:story:auth-login[]'?"));
+
+        let unknown_type = parser
+            .parse_content(
+                "= Story\n:forgepoint-type: user-story\n:id: unknown\n:schema-version: 1.0\n",
+                "unknown.adoc".into(),
+            )
+            .unwrap();
+        let result = validator.validate_document(&unknown_type);
+        let types = rule_errors(&result, "valid-document-type");
+        assert_eq!(types[0].suggestion.as_deref(), Some("Did you mean 'story'?"));
+    }
+
+    #[test]
+    fn test_bound_messages() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let story_schema = r#"{
+            "type": "object",
+            "properties": {
+                "points": { "type": "integer", "minimum": 1, "maximum": 13 },
+                "confidence": { "type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1 }
+            }
+        }"#;
+        let mut validator = validator_with_schema(temp_dir.path(), "story", story_schema);
+
+        let mut messages = |attributes: &str| {
+            let content = format!("= Login\n:forgepoint-type: story\n:id: login\n{}:schema-version: 1.0\n", attributes);
+            let doc = DocumentParser::new().parse_content(&content, "story.adoc".into()).unwrap();
+            let result = validator.validate_document(&doc);
+            let mut messages: Vec<String> = rule_errors(&result, "schema-validation").iter().map(|e| e.message.clone()).collect();
+            messages.sort();
+            messages
+        };
+
+        assert_eq!(
+            messages(":points: 0\n:confidence: 0\n"),
+            vec![
+                "Value '0' for :confidence: must be greater than 0",
+                "Value '0' for :points: must be at least 1",
+            ]
+        );
+        assert_eq!(
+            messages(":points: 20\n:confidence: 1\n"),
+            vec![
+                "Value '1' for :confidence: must be less than 1",
+                "Value '20' for :points: must be at most 13",
+            ]
+        );
+        assert!(messages(":points: 13\n:confidence: 0.5\n").is_empty());
+    }
+
+    #[test]
+    fn test_valid_section_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut validator = section_content_validator(temp_dir.path());
+
+        let content = r#"= Grow Engagement
+:forgepoint-type: okr
+:id: grow-engagement
+:schema-version: 1.0
+
+== Key Results
+
+* [x] Increase weekly active users
+
+== Risks
+
+[options="header"]
+|===
+|Risk
+|Impact
+
+|Churn
+|High
+|===
+
+== Scenarios
+
+[source,gherkin]
+----
+Scenario: Nothing
+  Given nothing
+  When nothing happens
+  Then nothing changes
+----
+
+== Context
+
+Engagement has been flat for two quarters.
+
+=== Background
+
+Details.
+"#;
+        let doc = DocumentParser::new().parse_content(content, "okr.adoc".into()).unwrap();
+        let result = validator.validate_document(&doc);
+
+        assert!(result.valid, "unexpected errors: {:?}", result.errors);
+    }
+
+    #[test]
+    fn test_schema_versions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let story_schema = r#"{
+            "type": "object",
+            "properties": {
+                "schema-version": { "const": "2.0" },
+                "status": { "enum": ["draft", "active", "done"] }
+            }
+        }"#;
+        let story_1_0_schema = r#"{
+            "type": "object",
+            "properties": {
+                "schema-version": { "const": "1.0" },
+                "status": { "enum": ["draft", "in-progress", "done"] }
+            }
+        }"#;
+        let mut validator = validator_with_versions(temp_dir.path(), "story", story_schema, &[("1.0", story_1_0_schema)]);
+        assert_eq!(validator.schema_loader.get_schema_versions("story"), vec!["1.0", "2.0"]);
+        let parser = DocumentParser::new();
+
+        let story = |version: &str, status: &str| {
+            parser
+                .parse_content(
+                    &format!("= Login\n:forgepoint-type: story\n:id: auth-login\n:status: {}\n:schema-version: {}\n", status, version),
+                    "login.adoc".into(),
+                )
+                .unwrap()
+        };
+
+        // Old documents validate against the old schema, with a deprecation warning
+        let result = validator.validate_document(&story("1.0", "in-progress"));
+        assert!(result.valid, "unexpected errors: {:?}", result.errors);
+        let deprecated: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| w.rule.as_deref() == Some("deprecated-schema-version"))
+            .collect();
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(
+            deprecated[0].suggestion.as_deref(),
+            Some("Run 'forgepoint migrate --to 2.0' to upgrade the document")
+        );
+
+        let result = validator.validate_document(&story("2.0", "in-progress"));
+        assert_eq!(rule_errors(&result, "schema-validation").len(), 1);
+        assert!(result.warnings.is_empty());
+
+        let result = validator.validate_document(&story("3.0", "active"));
+        let errors = rule_errors(&result, "schema-version");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Unsupported schema version 3.0 for 'story', supported versions: 1.0, 2.0"
+        );
+        assert_eq!(errors[0].location.as_ref().and_then(|l| l.line), Some(5));
+        assert!(rule_errors(&result, "schema-validation").is_empty());
+    }
+
+    #[test]
+    fn test_date_rules() {
+        let mut schema_loader = SchemaLoader::embedded();
+        schema_loader.load_schemas().unwrap();
+        let mut validator = DocumentValidator::new(schema_loader);
+        let parser = DocumentParser::new();
+
+        let sprint = |start: &str, end: &str, created: &str, updated: &str| {
+            let content = format!(
+                "= Sprint 1 Plan - Core\n:forgepoint-type: sprint-plan\n:id: sprint-1\n:status: planning\n:sprint-number: 1\n:team: Core\n:start-date: {}\n:end-date: {}\n:sprint-goal: Ship login\n:created: {}\n:updated: {}\n:schema-version: 1.0\n",
+                start, end, created, updated
+            );
+            parser.parse_content(&content, "sprint.adoc".into()).unwrap()
+        };
+
+        let result = validator.validate_document(&sprint("2024-01-01", "2024-01-14", "2024-01-01", "2024-01-01"));
+        assert!(rule_errors(&result, "date-order").is_empty());
+
+        let result = validator.validate_document(&sprint("2024-01-14", "2024-01-14", "2024-01-02", "2024-01-01"));
+        let errors = rule_errors(&result, "date-order");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].message,
+            "Date :end-date: (2024-01-14) must be after :start-date: (2024-01-14)"
+        );
+        assert_eq!(errors[0].location.as_ref().and_then(|l| l.line), Some(8));
+        assert_eq!(
+            errors[0].suggestion.as_deref(),
+            Some("Check :end-date: against :start-date: on line 7")
+        );
+        assert_eq!(
+            errors[1].message,
+            "Date :updated: (2024-01-01) must be on or after :created: (2024-01-02)"
+        );
+        assert_eq!(errors[1].location.as_ref().and_then(|l| l.line), Some(11));
+
+        // Malformed dates are format errors, not ordering errors
+        let result = validator.validate_document(&sprint("2024-01-01", "2024-02-30", "2024-01-01", "2024-01-01"));
+        assert!(rule_errors(&result, "date-order").is_empty());
+        let format_errors: Vec<_> = rule_errors(&result, "schema-validation")
+            .into_iter()
+            .filter(|e| e.message.contains("is not a valid date"))
+            .collect();
+        assert_eq!(format_errors.len(), 1);
+        assert_eq!(format_errors[0].location.as_ref().and_then(|l| l.line), Some(8));
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let mut schema_loader = SchemaLoader::embedded();
+        schema_loader.load_schemas().unwrap();
+        let validator = DocumentValidator::new(schema_loader);
+        let parser = DocumentParser::new();
+
+        let prd = |status: &str| {
+            parser
+                .parse_content(
+                    &format!("= Payments\n:forgepoint-type: prd\n:id: payments\n:status: {}\n:schema-version: 1.0\n", status),
+                    "payments.adoc".into(),
+                )
+                .unwrap()
+        };
+
+        assert!(validator.validate_transition(&prd("review"), &prd("draft")).is_none());
+        assert!(validator.validate_transition(&prd("draft"), &prd("draft")).is_none());
+        // Unknown states are left to schema validation
+        assert!(validator.validate_transition(&prd("done"), &prd("draft")).is_none());
+
+        let error = validator.validate_transition(&prd("archived"), &prd("draft")).unwrap();
+        assert_eq!(error.rule.as_deref(), Some("status-transition"));
+        assert_eq!(
+            error.message,
+            "Illegal status change for prd: draft → archived (allowed from draft: review)"
+        );
+        assert_eq!(error.location.as_ref().and_then(|l| l.line), Some(4));
+        assert_eq!(
+            error.suggestion.as_deref(),
+            Some("Move through the intermediate states: draft → review → approved → archived")
+        );
+
+        let error = validator.validate_transition(&prd("draft"), &prd("archived")).unwrap();
+        assert_eq!(error.suggestion.as_deref(), Some("'draft' cannot be reached from 'archived'"));
+
+        // A state that is only a target is terminal
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let story_schema = r#"{
+            "type": "object",
+            "lifecycle": { "transitions": { "draft": ["done"] } }
+        }"#;
+        let validator = validator_with_schema(temp_dir.path(), "story", story_schema);
+        let story = |status: &str| {
+            parser
+                .parse_content(
+                    &format!("= Login\n:forgepoint-type: story\n:id: login\n:status: {}\n:schema-version: 1.0\n", status),
+                    "login.adoc".into(),
+                )
+                .unwrap()
+        };
+        assert!(validator.validate_transition(&story("done"), &story("draft")).is_none());
+        let error = validator.validate_transition(&story("draft"), &story("done")).unwrap();
+        assert_eq!(
+            error.message,
+            "Illegal status change for story: done → draft (allowed from done: none)"
+        );
+    }
+
+    #[test]
+    fn test_conditional_requirements() {
+        let mut schema_loader = SchemaLoader::embedded();
+        schema_loader.load_schemas().unwrap();
+        let mut validator = DocumentValidator::new(schema_loader);
+        let parser = DocumentParser::new();
+        let messages = |result: &ValidationResult| result.errors.iter().map(|e| e.message.clone()).collect::<Vec<_>>();
+
+        let adr = |status: &str| {
+            let content = format!(
+                "= ADR-1: Use Postgres\n:forgepoint-type: adr\n:id: adr-1\n:status: {}\n:number: 1\n:decision: Use Postgres\n:deciders: alice\n:date: 2024-01-15\n:schema-version: 1.0\n\n== Context\n\nText\n\n== Decision\n\nText\n\n== Consequences\n\nText\n",
+                status
+            );
+            parser.parse_content(&content, "adr.adoc".into()).unwrap()
+        };
+        assert!(validator.validate_document(&adr("accepted")).valid);
+        assert_eq!(
+            messages(&validator.validate_document(&adr("superseded"))),
+            vec!["Missing required attribute :superseded-by: when :status: is superseded"]
+        );
+
+        let story = |status: &str| {
+            let content = format!(
+                "= Checkout\n:forgepoint-type: story\n:id: checkout\n:status: {}\n:schema-version: 1.0\n\n== Acceptance Criteria\n\n* [x] Pay by card\n* [ ] Pay by invoice\n",
+                status
+            );
+            parser.parse_content(&content, "story.adoc".into()).unwrap()
+        };
+        assert!(validator.validate_document(&story("in-progress")).valid);
+        let result = validator.validate_document(&story("done"));
+        let errors = rule_errors(&result, "section-checklist");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Unchecked item in section 'Acceptance Criteria': Pay by invoice when :status: is done"
+        );
+        assert_eq!(errors[0].location.as_ref().and_then(|l| l.line), Some(10));
+
+        let postmortem = |status: &str, blameless: &str| {
+            let content = format!(
+                "= Checkout Outage\n:forgepoint-type: postmortem\n:id: checkout-outage\n:status: {}\n:incident-date: 2024-01-15T09:00:00Z\n:severity: high\n:impact: Checkout down\n:incident-commander: alice\n{}:schema-version: 1.0\n\n== Summary\n\nText\n\n== Timeline\n\nText\n\n== Root Cause Analysis\n\nText\n\n== Action Items\n",
+                status, blameless
+            );
+            parser.parse_content(&content, "postmortem.adoc".into()).unwrap()
+        };
+        assert!(validator.validate_document(&postmortem("draft", "")).valid);
+        assert_eq!(
+            messages(&validator.validate_document(&postmortem("published", ":blameless: false\n"))),
+            vec![
+                "Attribute :blameless: must be 'true', found 'false' when :status: is published",
+                "Section 'Action Items' has 0 word(s), expected at least 1 when :status: is published",
+            ]
+        );
+        assert_eq!(
+            messages(&validator.validate_document(&postmortem("published", ""))),
+            vec![
+                "Missing required attribute :blameless: when :status: is published",
+                "Section 'Action Items' has 0 word(s), expected at least 1 when :status: is published",
+            ]
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::document::ForgepointDocument;
+use crate::schema::CompiledSchema;
+use crate::validator::{ErrorType, Location, Severity, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Attributes whose sum is the cost of delay
+pub const COST_OF_DELAY_ATTRIBUTES: &[&str] = &["business-value", "time-criticality", "rr-oe"];
+
+/// How far a stated `wsjf` may be from the computed score, allowing for rounding
+pub const WSJF_TOLERANCE: f64 = 0.05;
+
+/// Weighted Shortest Job First: cost of delay divided by job size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Wsjf {
+    pub cost_of_delay: u32,
+    pub job_size: u32,
+    pub score: f64,
+}
+
+impl Wsjf {
+    pub fn new(cost_of_delay: u32, job_size: u32) -> Option<Self> {
+        (job_size > 0).then(|| Self {
+            cost_of_delay,
+            job_size,
+            score: cost_of_delay as f64 / job_size as f64,
+        })
+    }
+
+    /// Compute WSJF from a document's cost-of-delay components and `job-size`
+    pub fn from_document(doc: &ForgepointDocument) -> Option<Self> {
+        Self::new(cost_of_delay(doc)?, integer_attribute(doc, "job-size")?)
+    }
+}
+
+/// Whether documents of this schema can be ranked by WSJF, i.e. it has a `job-size`
+pub fn is_rankable(schema: &CompiledSchema) -> bool {
+    schema.attribute_schema("job-size").is_some()
+}
+
+/// The sum of the cost-of-delay components, if they are all set
+pub fn cost_of_delay(doc: &ForgepointDocument) -> Option<u32> {
+    COST_OF_DELAY_ATTRIBUTES
+        .iter()
+        .map(|name| integer_attribute(doc, name))
+        .sum()
+}
+
+fn integer_attribute(doc: &ForgepointDocument, name: &str) -> Option<u32> {
+    doc.attributes.get(name)?.trim().parse().ok()
+}
+
+/// Warn when a stated `wsjf` disagrees with the score computed from its
+/// components, or can't be checked because `job-size` is missing
+pub fn check_wsjf(doc: &ForgepointDocument) -> Option<ValidationError> {
+    let value = doc.attributes.get("wsjf")?;
+    let stated: f64 = value.trim().parse().ok()?;
+    cost_of_delay(doc)?;
+
+    let (message, suggestion) = match Wsjf::from_document(doc) {
+        Some(wsjf) if (stated - wsjf.score).abs() > WSJF_TOLERANCE => (
+            format!(
+                ":wsjf: is {} but the cost of delay {} over a job size of {} gives {:.2}",
+                value, wsjf.cost_of_delay, wsjf.job_size, wsjf.score
+            ),
+            format!("Set :wsjf: to {:.2}, or remove it and let it be computed", wsjf.score),
+        ),
+        Some(_) => return None,
+        None => (
+            format!(":wsjf: is {} but there is no :job-size: to check it against", value),
+            "Add :job-size: so WSJF can be computed".to_string(),
+        ),
+    };
+
+    Some(ValidationError {
+        error_type: ErrorType::Schema,
+        severity: Severity::Warning,
+        message,
+        location: doc.attribute_location("wsjf").map(|location| Location {
+            line: Some(location.line),
+            column: Some(location.column),
+            section: Some("attributes".to_string()),
+        }),
+        rule: Some("wsjf-mismatch".to_string()),
+        suggestion: Some(suggestion),
+    })
+}
+
+/// A document in a WSJF-ranked backlog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedItem {
+    pub file_path: String,
+    pub document_type: Option<String>,
+    pub document_id: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub art: Option<String>,
+    pub pi: Option<String>,
+    pub wsjf: Option<Wsjf>,
+    /// The `wsjf` attribute, used to rank when the score can't be computed
+    pub stated_wsjf: Option<f64>,
+    /// Position in the ART's backlog, if the item could be scored
+    pub rank: Option<usize>,
+}
+
+impl RankedItem {
+    pub fn from_document(doc: &ForgepointDocument) -> Self {
+        Self {
+            file_path: doc.file_path.to_string_lossy().to_string(),
+            document_type: doc.document_type().cloned(),
+            document_id: doc.document_id().cloned(),
+            title: doc.title.clone(),
+            status: doc.attributes.get("status").cloned(),
+            art: doc.attributes.get("art").cloned(),
+            pi: doc.attributes.get("pi").cloned(),
+            wsjf: Wsjf::from_document(doc),
+            stated_wsjf: doc.attributes.get("wsjf").and_then(|v| v.trim().parse().ok()),
+            rank: None,
+        }
+    }
+
+    /// The score to rank by: computed if possible, otherwise stated
+    pub fn score(&self) -> Option<f64> {
+        self.wsjf.map(|wsjf| wsjf.score).or(self.stated_wsjf)
+    }
+}
+
+/// The ranked backlog of one Agile Release Train
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtBacklog {
+    pub art: Option<String>,
+    pub items: Vec<RankedItem>,
+}
+
+/// Group items by ART and rank each backlog by WSJF, highest first; ties go
+/// to the higher cost of delay, and unscored items come last
+pub fn rank_by_wsjf(items: Vec<RankedItem>) -> Vec<ArtBacklog> {
+    let mut arts: BTreeMap<(bool, Option<String>), Vec<RankedItem>> = BTreeMap::new();
+    for item in items {
+        arts.entry((item.art.is_none(), item.art.clone())).or_default().push(item);
+    }
+
+    arts.into_iter()
+        .map(|((_, art), mut items)| {
+            items.sort_by(|a, b| {
+                let score = |item: &RankedItem| item.score().unwrap_or(f64::NEG_INFINITY);
+                let cost_of_delay = |item: &RankedItem| item.wsjf.map(|wsjf| wsjf.cost_of_delay);
+                score(b)
+                    .total_cmp(&score(a))
+                    .then(cost_of_delay(b).cmp(&cost_of_delay(a)))
+                    .then(a.document_id.cmp(&b.document_id))
+            });
+            for (index, item) in items.iter_mut().enumerate() {
+                item.rank = item.score().map(|_| index + 1);
+            }
+            ArtBacklog { art, items }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DocumentParser;
+    use std::path::PathBuf;
+
+    fn feature(id: &str, art: &str, attributes: &str) -> ForgepointDocument {
+        DocumentParser::new()
+            .parse_content(
+                &format!(
+                    "= {}\n:forgepoint-type: safe-feature\n:id: {}\n:status: backlog\n:art: {}\n:pi: 2024-PI3\n{}:schema-version: 1.0\n",
+                    id, id, art, attributes
+                ),
+                PathBuf::from("feature.adoc"),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_rankable() {
+        let mut loader = crate::schema::SchemaLoader::embedded();
+        loader.load_schemas().unwrap();
+
+        assert!(is_rankable(loader.get_schema("safe-feature").unwrap()));
+        assert!(!is_rankable(loader.get_schema("story").unwrap()));
+    }
+
+    #[test]
+    fn test_check_wsjf() {
+        let scored = |wsjf: &str| {
+            feature(
+                "checkout",
+                "payments",
+                &format!(":business-value: 8\n:time-criticality: 5\n:rr-oe: 3\n:job-size: 5\n:wsjf: {}\n", wsjf),
+            )
+        };
+        assert_eq!(Wsjf::from_document(&scored("3.2")).unwrap().score, 3.2);
+        assert!(check_wsjf(&scored("3.2")).is_none());
+        assert!(check_wsjf(&scored("3.24")).is_none());
+
+        let warning = check_wsjf(&scored("4")).unwrap();
+        assert_eq!(warning.message, ":wsjf: is 4 but the cost of delay 16 over a job size of 5 gives 3.20");
+        assert_eq!(warning.location.unwrap().line, Some(11));
+
+        let unsized_feature = feature("checkout", "payments", ":business-value: 8\n:time-criticality: 5\n:rr-oe: 3\n:wsjf: 4\n");
+        assert_eq!(
+            check_wsjf(&unsized_feature).unwrap().message,
+            ":wsjf: is 4 but there is no :job-size: to check it against"
+        );
+    }
+
+    #[test]
+    fn test_rank_by_wsjf() {
+        let items: Vec<RankedItem> = [
+            feature("refunds", "payments", ":business-value: 5\n:time-criticality: 3\n:rr-oe: 2\n:job-size: 5\n"),
+            feature("checkout", "payments", ":business-value: 8\n:time-criticality: 5\n:rr-oe: 3\n:job-size: 8\n"),
+            feature("ledger", "payments", ":business-value: 2\n"),
+            feature("wallet", "payments", ":wsjf: 3\n"),
+            feature("search", "discovery", ":business-value: 1\n:time-criticality: 1\n:rr-oe: 1\n:job-size: 1\n"),
+        ]
+        .iter()
+        .map(RankedItem::from_document)
+        .collect();
+
+        let backlogs = rank_by_wsjf(items);
+        assert_eq!(backlogs.len(), 2);
+        assert_eq!(backlogs[0].art.as_deref(), Some("discovery"));
+
+        let payments: Vec<_> = backlogs[1]
+            .items
+            .iter()
+            .map(|item| (item.document_id.as_deref().unwrap(), item.rank))
+            .collect();
+        assert_eq!(
+            payments,
+            vec![("wallet", Some(1)), ("checkout", Some(2)), ("refunds", Some(3)), ("ledger", None)]
+        );
+    }
+}